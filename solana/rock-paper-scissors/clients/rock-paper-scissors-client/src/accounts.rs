@@ -0,0 +1,257 @@
+use std::ops::Deref;
+
+use anchor_lang::{prelude::Pubkey, AccountDeserialize, Result};
+use rock_paper_scissors::{Game, GameState, MintConfig, Player, Round, Settings};
+
+/// How a settled game ended.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Outcome {
+    Won { winner: Pubkey, amount_won: u64 },
+    Draw,
+}
+
+/// A decoded `Game` along with its address.
+#[derive(Clone)]
+pub struct GameAccount {
+    pub address: Pubkey,
+    pub game: Game,
+}
+
+impl GameAccount {
+    /// Decodes raw account data, discriminator included.
+    pub fn try_from_bytes(address: Pubkey, mut data: &[u8]) -> Result<Self> {
+        Ok(Self {
+            address,
+            game: Game::try_deserialize(&mut data)?,
+        })
+    }
+
+    pub fn player(&self, player: &Player) -> Option<Pubkey> {
+        match player {
+            Player::First => Some(self.game.first_player),
+            Player::Second => self.game.second_player,
+        }
+    }
+
+    /// Whether a second player can still join at `now`.
+    pub fn is_open(&self, now: i64) -> bool {
+        self.game.state == GameState::Created && !self.game.is_past_join_deadline(now)
+    }
+
+    /// Whether anyone can expire the game at `now`.
+    pub fn is_expirable(&self, now: i64) -> bool {
+        self.game.state == GameState::Created && self.game.is_past_join_deadline(now)
+    }
+
+    /// Earliest time `settle_game` can succeed, `None` while it's waiting on
+    /// both players or once the game is settled. `settings` only matters for
+    /// games created before their windows were snapshotted.
+    pub fn settleable_at(&self, settings: &Settings) -> Option<i64> {
+        match self.game.state {
+            GameState::Started => self
+                .game
+                .settleable_at(self.game.time_for_penalization(settings)),
+            _ => None,
+        }
+    }
+
+    pub fn is_stale(&self, now: i64, settings: &Settings) -> bool {
+        self.game.is_stale(now, self.game.time_for_stale(settings))
+    }
+
+    /// Rake taken from each escrow holding `amount`, fixed when the game was
+    /// created. `settings` only matters for games created before it was.
+    pub fn rake_for(&self, amount: u64, settings: &Settings) -> Result<u64> {
+        self.game.rake_terms(settings).rake_for(amount)
+    }
+
+    /// Rounds won by the first and second player.
+    pub fn score(&self) -> (u8, u8) {
+        (self.game.first_player_wins, self.game.second_player_wins)
+    }
+
+    pub fn rounds(&self) -> &[Round] {
+        &self.game.rounds
+    }
+
+    pub fn outcome(&self) -> Option<Outcome> {
+        let amount_won = self.game.amount_won.unwrap_or_default();
+        match self.game.state {
+            GameState::FirstPlayerWon => Some(Outcome::Won {
+                winner: self.game.first_player,
+                amount_won,
+            }),
+            GameState::SecondPlayerWon => self
+                .game
+                .second_player
+                .map(|winner| Outcome::Won { winner, amount_won }),
+            GameState::Draw => Some(Outcome::Draw),
+            GameState::Created | GameState::Started => None,
+        }
+    }
+}
+
+impl Deref for GameAccount {
+    type Target = Game;
+
+    fn deref(&self) -> &Self::Target {
+        &self.game
+    }
+}
+
+/// A decoded `Settings` along with its address.
+#[derive(Clone)]
+pub struct SettingsAccount {
+    pub address: Pubkey,
+    pub settings: Settings,
+}
+
+impl SettingsAccount {
+    /// Decodes raw account data, discriminator included.
+    pub fn try_from_bytes(address: Pubkey, mut data: &[u8]) -> Result<Self> {
+        Ok(Self {
+            address,
+            settings: Settings::try_deserialize(&mut data)?,
+        })
+    }
+
+    /// Lamports a player pays to create or join a game in the mint of
+    /// `mint_config`, which may override the settings' fee.
+    pub fn player_fee_lamports(&self, mint_config: Option<&MintConfigAccount>) -> u64 {
+        match mint_config {
+            Some(mint_config) => mint_config.player_fee_lamports(&self.settings),
+            None => self.settings.player_fee_lamports,
+        }
+    }
+
+    /// Rake taken from each escrow of a game created now wagering `amount`.
+    pub fn rake_for(&self, amount: u64) -> Result<u64> {
+        self.settings.rake_for(amount)
+    }
+}
+
+impl Deref for SettingsAccount {
+    type Target = Settings;
+
+    fn deref(&self) -> &Self::Target {
+        &self.settings
+    }
+}
+
+/// A decoded `MintConfig` along with its address.
+#[derive(Clone)]
+pub struct MintConfigAccount {
+    pub address: Pubkey,
+    pub mint_config: MintConfig,
+}
+
+impl MintConfigAccount {
+    /// Decodes raw account data, discriminator included.
+    pub fn try_from_bytes(address: Pubkey, mut data: &[u8]) -> Result<Self> {
+        Ok(Self {
+            address,
+            mint_config: MintConfig::try_deserialize(&mut data)?,
+        })
+    }
+}
+
+impl Deref for MintConfigAccount {
+    type Target = MintConfig;
+
+    fn deref(&self) -> &Self::Target {
+        &self.mint_config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::AccountSerialize;
+    use rock_paper_scissors::{GameVariant, RakeTerms, WagerMode};
+
+    use super::*;
+
+    #[test]
+    fn decodes_a_settled_game() {
+        let first_player = Pubkey::new_unique();
+        let second_player = Pubkey::new_unique();
+        let mut game = Game::new(
+            255,
+            "game".to_string(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            10,
+            first_player,
+            [1; 32],
+            Pubkey::new_unique(),
+            0,
+            1,
+            GameVariant::Classic,
+            None,
+            WagerMode::Token,
+            50,
+            100,
+            0,
+            RakeTerms::default(),
+        );
+        game.join_game(second_player, [2; 32], Pubkey::new_unique(), 1, &[])
+            .unwrap();
+        game.set_claimed(&Some(Player::Second), 20, 2);
+
+        let mut data = vec![];
+        game.try_serialize(&mut data).unwrap();
+        let address = Pubkey::new_unique();
+        let account = GameAccount::try_from_bytes(address, &data).unwrap();
+
+        assert_eq!(account.address, address);
+        assert_eq!(account.player(&Player::Second), Some(second_player));
+        assert!(!account.is_open(2));
+        assert_eq!(
+            account.outcome(),
+            Some(Outcome::Won {
+                winner: second_player,
+                amount_won: 20,
+            })
+        );
+    }
+
+    #[test]
+    fn resolves_the_player_fee_of_a_mint() {
+        let settings = SettingsAccount {
+            address: Pubkey::new_unique(),
+            settings: Settings::new(
+                255,
+                50,
+                100,
+                Pubkey::new_unique(),
+                1_000,
+                0,
+                false,
+                0,
+                Pubkey::new_unique(),
+            ),
+        };
+        let mint_config = |player_fee_lamports| MintConfigAccount {
+            address: Pubkey::new_unique(),
+            mint_config: MintConfig::new(
+                255,
+                settings.address,
+                Pubkey::new_unique(),
+                true,
+                1,
+                10,
+                player_fee_lamports,
+            ),
+        };
+
+        assert_eq!(settings.player_fee_lamports(None), 1_000);
+        assert_eq!(
+            settings.player_fee_lamports(Some(&mint_config(None))),
+            1_000
+        );
+        assert_eq!(
+            settings.player_fee_lamports(Some(&mint_config(Some(250)))),
+            250
+        );
+        assert_eq!(settings.player_fee_lamports(Some(&mint_config(Some(0)))), 0);
+    }
+}
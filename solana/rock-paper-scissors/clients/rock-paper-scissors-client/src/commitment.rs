@@ -0,0 +1,18 @@
+use anchor_lang::prelude::Pubkey;
+use rand::RngCore;
+use rock_paper_scissors::{Choice, CommitmentScheme};
+
+/// Creates a random 32 byte salt. Keep it until the choice is revealed, the
+/// commitment can't be opened without it.
+pub fn generate_salt() -> [u8; 32] {
+    let mut salt = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut salt);
+    salt
+}
+
+/// Commitment `reveal_choice` checks for games created with the bound scheme.
+pub fn commitment(game: &Pubkey, player: &Pubkey, choice: &Choice, salt: &[u8; 32]) -> [u8; 32] {
+    CommitmentScheme::Bound
+        .hash(game, player, choice, salt)
+        .to_bytes()
+}
@@ -0,0 +1,1620 @@
+use anchor_lang::{
+    prelude::{AccountMeta, Pubkey},
+    solana_program::instruction::Instruction,
+    system_program, InstructionData, ToAccountMetas,
+};
+use rock_paper_scissors::{
+    accounts, instruction, Choice, GameReferral, GameVariant, JoinRestriction, RatingBand,
+    SeasonRanking, SideBetOutcome, Tournament, ID,
+};
+
+use crate::{
+    commitment, find_escrow_address, find_event_authority_address, find_game_address,
+    find_lobby_address, find_mint_config_address, find_player_profile_address,
+    find_player_rating_address, find_program_data_address, find_referral_address,
+    find_referrer_rewards_address, find_season_address, find_season_entry_address,
+    find_settings_address, find_side_bet_address, find_side_pool_address, find_tournament_address,
+    find_tournament_game_address,
+};
+
+/// `player`'s entry in `season`, for instructions taking both optionally.
+fn season_entry(season: Option<Pubkey>, player: &Pubkey) -> Option<Pubkey> {
+    season.map(|season| find_season_entry_address(&season, player).0)
+}
+
+/// Rewards of an optional referrer.
+fn referrer_rewards(referrer: Option<Pubkey>) -> Option<Pubkey> {
+    referrer.map(|referrer| find_referrer_rewards_address(&referrer).0)
+}
+
+/// Rewards of the referrer of a seat, as snapshotted by `Game`.
+fn referral_rewards(referral: &Option<GameReferral>) -> Option<Pubkey> {
+    referrer_rewards(referral.as_ref().map(|referral| referral.referrer))
+}
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+/// Appends a hooked mint's transfer hook accounts, forwarded by the program
+/// to every token transfer it makes.
+fn with_transfer_hook_accounts(
+    mut instruction: Instruction,
+    transfer_hook_accounts: &[AccountMeta],
+) -> Instruction {
+    instruction
+        .accounts
+        .extend_from_slice(transfer_hook_accounts);
+    instruction
+}
+
+fn event_authority() -> Pubkey {
+    find_event_authority_address().0
+}
+
+/// Signed by the program's upgrade authority, who becomes admin.
+pub struct InitializeSettings {
+    pub signer: Pubkey,
+    pub time_for_penalization: i64,
+    pub time_for_stale: i64,
+    pub player_fee_lamports: u64,
+    pub rake_bps: u16,
+    pub rake_draws: bool,
+    pub game_retention_period: i64,
+    pub treasury: Pubkey,
+}
+
+impl InitializeSettings {
+    pub fn instruction(&self) -> Instruction {
+        build(
+            accounts::InitializeSettings {
+                settings: find_settings_address().0,
+                signer: self.signer,
+                program_data: find_program_data_address().0,
+                system_program: system_program::ID,
+                event_authority: event_authority(),
+                program: ID,
+            },
+            instruction::InitializeSettings {
+                time_for_penalization: self.time_for_penalization,
+                time_for_stale: self.time_for_stale,
+                player_fee_lamports: self.player_fee_lamports,
+                rake_bps: self.rake_bps,
+                rake_draws: self.rake_draws,
+                game_retention_period: self.game_retention_period,
+                treasury: self.treasury,
+            },
+        )
+    }
+}
+
+pub struct UpdateSettings {
+    pub signer: Pubkey,
+    pub time_for_penalization: i64,
+    pub time_for_stale: i64,
+    pub player_fee_lamports: u64,
+    pub rake_bps: u16,
+    pub rake_draws: bool,
+    pub game_retention_period: i64,
+    pub treasury: Pubkey,
+}
+
+impl UpdateSettings {
+    pub fn instruction(&self) -> Instruction {
+        build(
+            accounts::UpdateSettings {
+                settings: find_settings_address().0,
+                signer: self.signer,
+                event_authority: event_authority(),
+                program: ID,
+            },
+            instruction::UpdateSettings {
+                time_for_penalization: self.time_for_penalization,
+                time_for_stale: self.time_for_stale,
+                player_fee_lamports: self.player_fee_lamports,
+                rake_bps: self.rake_bps,
+                rake_draws: self.rake_draws,
+                game_retention_period: self.game_retention_period,
+                treasury: self.treasury,
+            },
+        )
+    }
+}
+
+/// Bounds on the windows creators may pick, all must be positive.
+pub struct UpdateWindowBounds {
+    pub signer: Pubkey,
+    pub min_time_for_penalization: i64,
+    pub max_time_for_penalization: i64,
+    pub min_time_for_stale: i64,
+    pub max_time_for_stale: i64,
+}
+
+impl UpdateWindowBounds {
+    pub fn instruction(&self) -> Instruction {
+        build(
+            accounts::UpdateWindowBounds {
+                settings: find_settings_address().0,
+                signer: self.signer,
+                event_authority: event_authority(),
+                program: ID,
+            },
+            instruction::UpdateWindowBounds {
+                min_time_for_penalization: self.min_time_for_penalization,
+                max_time_for_penalization: self.max_time_for_penalization,
+                min_time_for_stale: self.min_time_for_stale,
+                max_time_for_stale: self.max_time_for_stale,
+            },
+        )
+    }
+}
+
+/// Signed by the admin, or by the treasury of a `Settings` account created
+/// before admin rights were split out of it.
+pub struct MigrateSettings {
+    pub signer: Pubkey,
+}
+
+impl MigrateSettings {
+    pub fn instruction(&self) -> Instruction {
+        build(
+            accounts::MigrateSettings {
+                settings: find_settings_address().0,
+                signer: self.signer,
+                system_program: system_program::ID,
+                event_authority: event_authority(),
+                program: ID,
+            },
+            instruction::MigrateSettings {},
+        )
+    }
+}
+
+/// `None` withdraws a pending proposal.
+pub struct ProposeAdmin {
+    pub signer: Pubkey,
+    pub pending_admin: Option<Pubkey>,
+}
+
+impl ProposeAdmin {
+    pub fn instruction(&self) -> Instruction {
+        build(
+            accounts::ProposeAdmin {
+                settings: find_settings_address().0,
+                signer: self.signer,
+                event_authority: event_authority(),
+                program: ID,
+            },
+            instruction::ProposeAdmin {
+                pending_admin: self.pending_admin,
+            },
+        )
+    }
+}
+
+pub struct AcceptAdmin {
+    pub signer: Pubkey,
+}
+
+impl AcceptAdmin {
+    pub fn instruction(&self) -> Instruction {
+        build(
+            accounts::AcceptAdmin {
+                settings: find_settings_address().0,
+                signer: self.signer,
+                event_authority: event_authority(),
+                program: ID,
+            },
+            instruction::AcceptAdmin {},
+        )
+    }
+}
+
+/// `paused` is a bitmask of the program's `PAUSE_*` flags.
+pub struct SetPaused {
+    pub signer: Pubkey,
+    pub paused: u8,
+}
+
+impl SetPaused {
+    pub fn instruction(&self) -> Instruction {
+        build(
+            accounts::SetPaused {
+                settings: find_settings_address().0,
+                signer: self.signer,
+                event_authority: event_authority(),
+                program: ID,
+            },
+            instruction::SetPaused {
+                paused: self.paused,
+            },
+        )
+    }
+}
+
+/// Lists a mint for wagers, `Pubkey::default()` for native SOL.
+pub struct InitializeMintConfig {
+    pub signer: Pubkey,
+    pub mint: Pubkey,
+    pub enabled: bool,
+    pub min_amount: u64,
+    pub max_amount: u64,
+    pub player_fee_lamports: Option<u64>,
+}
+
+impl InitializeMintConfig {
+    pub fn instruction(&self) -> Instruction {
+        build(
+            accounts::InitializeMintConfig {
+                mint_config: find_mint_config_address(&self.mint).0,
+                settings: find_settings_address().0,
+                signer: self.signer,
+                system_program: system_program::ID,
+                event_authority: event_authority(),
+                program: ID,
+            },
+            instruction::InitializeMintConfig {
+                mint: self.mint,
+                enabled: self.enabled,
+                min_amount: self.min_amount,
+                max_amount: self.max_amount,
+                player_fee_lamports: self.player_fee_lamports,
+            },
+        )
+    }
+}
+
+pub struct UpdateMintConfig {
+    pub signer: Pubkey,
+    pub mint: Pubkey,
+    pub enabled: bool,
+    pub min_amount: u64,
+    pub max_amount: u64,
+    pub player_fee_lamports: Option<u64>,
+}
+
+impl UpdateMintConfig {
+    pub fn instruction(&self) -> Instruction {
+        build(
+            accounts::UpdateMintConfig {
+                mint_config: find_mint_config_address(&self.mint).0,
+                settings: find_settings_address().0,
+                signer: self.signer,
+                event_authority: event_authority(),
+                program: ID,
+            },
+            instruction::UpdateMintConfig {
+                enabled: self.enabled,
+                min_amount: self.min_amount,
+                max_amount: self.max_amount,
+                player_fee_lamports: self.player_fee_lamports,
+            },
+        )
+    }
+}
+
+/// Creates a game and commits the first player's choice for its first round.
+pub struct InitializeGame {
+    pub player: Pubkey,
+    pub player_token_account: Pubkey,
+    pub mint: Pubkey,
+    pub token_program: Pubkey,
+    /// Must match `Settings::treasury`.
+    pub treasury: Pubkey,
+    pub game_id: String,
+    pub amount: u64,
+    pub choice: Choice,
+    pub salt: [u8; 32],
+    pub best_of: u8,
+    pub variant: GameVariant,
+    pub join_deadline: Option<i64>,
+    /// `None` for the settings' default, otherwise within the admin's bounds.
+    pub time_for_penalization: Option<i64>,
+    pub time_for_stale: Option<i64>,
+    /// `None` for games anyone can join, see `InviteList` for merkle roots.
+    pub join_restriction: Option<JoinRestriction>,
+    /// `None` for games open to every rating.
+    pub rating_band: Option<RatingBand>,
+    /// Running season the game counts towards, which `player` joined.
+    pub season: Option<Pubkey>,
+    /// `Referral::referrer` of `player`, required once registered.
+    pub referrer: Option<Pubkey>,
+    /// Token account of the referrer's rewards in `mint`, paid a share of
+    /// the player's rake, required along with `referrer`.
+    pub referrer_vault: Option<Pubkey>,
+    /// The mint's transfer hook program, its `ExtraAccountMetaList` and the
+    /// extra accounts it lists, empty for mints without a transfer hook.
+    pub transfer_hook_accounts: Vec<AccountMeta>,
+}
+
+impl InitializeGame {
+    pub fn instruction(&self) -> Instruction {
+        let game = find_game_address(&self.player, &self.game_id).0;
+        with_transfer_hook_accounts(
+            build(
+                accounts::InitializeGame {
+                    game,
+                    player_escrow_token_account: find_escrow_address(&game, &self.player).0,
+                    player_token_account: self.player_token_account,
+                    mint: self.mint,
+                    treasury: self.treasury,
+                    settings: find_settings_address().0,
+                    mint_config: find_mint_config_address(&self.mint).0,
+                    player_profile: find_player_profile_address(&self.player).0,
+                    player_rating: find_player_rating_address(&self.player).0,
+                    referral: find_referral_address(&self.player).0,
+                    player: self.player,
+                    token_program: self.token_program,
+                    system_program: system_program::ID,
+                    lobby: find_lobby_address(&self.mint, self.amount).0,
+                    season: self.season,
+                    season_entry: season_entry(self.season, &self.player),
+                    referrer_rewards: referrer_rewards(self.referrer),
+                    referrer_vault: self.referrer_vault,
+                    event_authority: event_authority(),
+                    program: ID,
+                },
+                instruction::InitializeGame {
+                    game_id: self.game_id.clone(),
+                    amount: self.amount,
+                    hash: commitment(&game, &self.player, &self.choice, &self.salt),
+                    best_of: self.best_of,
+                    variant: self.variant.clone(),
+                    join_deadline: self.join_deadline,
+                    time_for_penalization: self.time_for_penalization,
+                    time_for_stale: self.time_for_stale,
+                    join_restriction: self.join_restriction.clone(),
+                    rating_band: self.rating_band.clone(),
+                },
+            ),
+            &self.transfer_hook_accounts,
+        )
+    }
+}
+
+/// Creates a game staking lamports, held by the game account itself.
+pub struct InitializeNativeGame {
+    pub player: Pubkey,
+    /// Must match `Settings::treasury`.
+    pub treasury: Pubkey,
+    pub game_id: String,
+    pub amount: u64,
+    pub choice: Choice,
+    pub salt: [u8; 32],
+    pub best_of: u8,
+    pub variant: GameVariant,
+    pub join_deadline: Option<i64>,
+    /// `None` for the settings' default, otherwise within the admin's bounds.
+    pub time_for_penalization: Option<i64>,
+    pub time_for_stale: Option<i64>,
+    /// `None` for games anyone can join, see `InviteList` for merkle roots.
+    pub join_restriction: Option<JoinRestriction>,
+    /// `None` for games open to every rating.
+    pub rating_band: Option<RatingBand>,
+    /// Running season the game counts towards, which `player` joined.
+    pub season: Option<Pubkey>,
+    /// `Referral::referrer` of `player`, required once registered.
+    pub referrer: Option<Pubkey>,
+}
+
+impl InitializeNativeGame {
+    pub fn instruction(&self) -> Instruction {
+        let game = find_game_address(&self.player, &self.game_id).0;
+        build(
+            accounts::InitializeNativeGame {
+                game,
+                treasury: self.treasury,
+                settings: find_settings_address().0,
+                mint_config: find_mint_config_address(&Pubkey::default()).0,
+                player_profile: find_player_profile_address(&self.player).0,
+                player_rating: find_player_rating_address(&self.player).0,
+                referral: find_referral_address(&self.player).0,
+                player: self.player,
+                system_program: system_program::ID,
+                lobby: find_lobby_address(&Pubkey::default(), self.amount).0,
+                season: self.season,
+                season_entry: season_entry(self.season, &self.player),
+                referrer_rewards: referrer_rewards(self.referrer),
+                event_authority: event_authority(),
+                program: ID,
+            },
+            instruction::InitializeNativeGame {
+                game_id: self.game_id.clone(),
+                amount: self.amount,
+                hash: commitment(&game, &self.player, &self.choice, &self.salt),
+                best_of: self.best_of,
+                variant: self.variant.clone(),
+                join_deadline: self.join_deadline,
+                time_for_penalization: self.time_for_penalization,
+                time_for_stale: self.time_for_stale,
+                join_restriction: self.join_restriction.clone(),
+                rating_band: self.rating_band.clone(),
+            },
+        )
+    }
+}
+
+/// Creates `player`'s profile, needed to settle games created before
+/// profiles existed. Later games create it along the way.
+pub struct InitializePlayerProfile {
+    pub player: Pubkey,
+    pub payer: Pubkey,
+}
+
+impl InitializePlayerProfile {
+    pub fn instruction(&self) -> Instruction {
+        build(
+            accounts::InitializePlayerProfile {
+                player_profile: find_player_profile_address(&self.player).0,
+                player: self.player,
+                payer: self.payer,
+                system_program: system_program::ID,
+            },
+            instruction::InitializePlayerProfile {},
+        )
+    }
+}
+
+/// Takes the second seat of a game and commits to its first round.
+pub struct JoinGame {
+    pub game: Pubkey,
+    pub player: Pubkey,
+    pub player_token_account: Pubkey,
+    pub mint: Pubkey,
+    pub token_program: Pubkey,
+    /// Must match `Settings::treasury`.
+    pub treasury: Pubkey,
+    pub choice: Choice,
+    pub salt: [u8; 32],
+    /// From `InviteList::proof` for invite lists, empty otherwise.
+    pub proof: Vec<[u8; 32]>,
+    /// `Game::lobby`, the game's listing to remove.
+    pub lobby: Option<Pubkey>,
+    /// `Game::season`, which `player` must have joined.
+    pub season: Option<Pubkey>,
+    /// `Referral::referrer` of `player`, required once registered.
+    pub referrer: Option<Pubkey>,
+    /// Token account of the referrer's rewards in `mint`, paid a share of
+    /// the player's rake, required along with `referrer`.
+    pub referrer_vault: Option<Pubkey>,
+    /// Empty for mints without a transfer hook.
+    pub transfer_hook_accounts: Vec<AccountMeta>,
+}
+
+impl JoinGame {
+    pub fn instruction(&self) -> Instruction {
+        with_transfer_hook_accounts(
+            build(
+                accounts::JoinGame {
+                    game: self.game,
+                    player_escrow_token_account: find_escrow_address(&self.game, &self.player).0,
+                    player_token_account: self.player_token_account,
+                    mint: self.mint,
+                    treasury: self.treasury,
+                    settings: find_settings_address().0,
+                    mint_config: find_mint_config_address(&self.mint).0,
+                    player_profile: find_player_profile_address(&self.player).0,
+                    player_rating: find_player_rating_address(&self.player).0,
+                    referral: find_referral_address(&self.player).0,
+                    player: self.player,
+                    token_program: self.token_program,
+                    system_program: system_program::ID,
+                    lobby: self.lobby,
+                    season: self.season,
+                    season_entry: season_entry(self.season, &self.player),
+                    referrer_rewards: referrer_rewards(self.referrer),
+                    referrer_vault: self.referrer_vault,
+                    event_authority: event_authority(),
+                    program: ID,
+                },
+                instruction::JoinGame {
+                    hash: commitment(&self.game, &self.player, &self.choice, &self.salt),
+                    proof: self.proof.clone(),
+                },
+            ),
+            &self.transfer_hook_accounts,
+        )
+    }
+}
+
+pub struct JoinNativeGame {
+    pub game: Pubkey,
+    pub player: Pubkey,
+    /// Must match `Settings::treasury`.
+    pub treasury: Pubkey,
+    pub choice: Choice,
+    pub salt: [u8; 32],
+    /// From `InviteList::proof` for invite lists, empty otherwise.
+    pub proof: Vec<[u8; 32]>,
+    /// `Game::lobby`, the game's listing to remove.
+    pub lobby: Option<Pubkey>,
+    /// `Game::season`, which `player` must have joined.
+    pub season: Option<Pubkey>,
+    /// `Referral::referrer` of `player`, required once registered.
+    pub referrer: Option<Pubkey>,
+}
+
+impl JoinNativeGame {
+    pub fn instruction(&self) -> Instruction {
+        build(
+            accounts::JoinNativeGame {
+                game: self.game,
+                treasury: self.treasury,
+                settings: find_settings_address().0,
+                mint_config: find_mint_config_address(&Pubkey::default()).0,
+                player_profile: find_player_profile_address(&self.player).0,
+                player_rating: find_player_rating_address(&self.player).0,
+                referral: find_referral_address(&self.player).0,
+                player: self.player,
+                system_program: system_program::ID,
+                lobby: self.lobby,
+                season: self.season,
+                season_entry: season_entry(self.season, &self.player),
+                referrer_rewards: referrer_rewards(self.referrer),
+                event_authority: event_authority(),
+                program: ID,
+            },
+            instruction::JoinNativeGame {
+                hash: commitment(&self.game, &self.player, &self.choice, &self.salt),
+                proof: self.proof.clone(),
+            },
+        )
+    }
+}
+
+/// Commits a player to the next round of a match.
+pub struct CommitChoice {
+    pub game: Pubkey,
+    pub player: Pubkey,
+    pub choice: Choice,
+    pub salt: [u8; 32],
+}
+
+impl CommitChoice {
+    pub fn instruction(&self) -> Instruction {
+        build(
+            accounts::CommitChoice {
+                game: self.game,
+                player: self.player,
+                event_authority: event_authority(),
+                program: ID,
+            },
+            instruction::CommitChoice {
+                hash: commitment(&self.game, &self.player, &self.choice, &self.salt),
+            },
+        )
+    }
+}
+
+pub struct RevealChoice {
+    pub game: Pubkey,
+    pub player: Pubkey,
+    pub choice: Choice,
+    pub salt: [u8; 32],
+}
+
+impl RevealChoice {
+    pub fn instruction(&self) -> Instruction {
+        build(
+            accounts::RevealChoice {
+                game: self.game,
+                player: self.player,
+                event_authority: event_authority(),
+                program: ID,
+            },
+            instruction::RevealChoice {
+                choice: self.choice.clone(),
+                salt: self.salt,
+            },
+        )
+    }
+}
+
+pub struct CancelGame {
+    pub game: Pubkey,
+    pub player: Pubkey,
+    pub player_token_account: Pubkey,
+    pub mint: Pubkey,
+    pub token_program: Pubkey,
+    /// `Game::lobby`, the game's listing to remove.
+    pub lobby: Option<Pubkey>,
+    /// Empty for mints without a transfer hook.
+    pub transfer_hook_accounts: Vec<AccountMeta>,
+}
+
+impl CancelGame {
+    pub fn instruction(&self) -> Instruction {
+        with_transfer_hook_accounts(
+            build(
+                accounts::CancelGame {
+                    game: self.game,
+                    player_escrow_token_account: find_escrow_address(&self.game, &self.player).0,
+                    player_token_account: self.player_token_account,
+                    mint: self.mint,
+                    token_program: self.token_program,
+                    player: self.player,
+                    lobby: self.lobby,
+                    event_authority: event_authority(),
+                    program: ID,
+                },
+                instruction::CancelGame {},
+            ),
+            &self.transfer_hook_accounts,
+        )
+    }
+}
+
+pub struct CancelNativeGame {
+    pub game: Pubkey,
+    pub player: Pubkey,
+    /// `Game::lobby`, the game's listing to remove.
+    pub lobby: Option<Pubkey>,
+}
+
+impl CancelNativeGame {
+    pub fn instruction(&self) -> Instruction {
+        build(
+            accounts::CancelNativeGame {
+                game: self.game,
+                player: self.player,
+                lobby: self.lobby,
+                event_authority: event_authority(),
+                program: ID,
+            },
+            instruction::CancelNativeGame {},
+        )
+    }
+}
+
+/// Permissionless, refunds a game nobody joined before its deadline.
+pub struct ExpireGame {
+    pub game: Pubkey,
+    pub first_player: Pubkey,
+    pub first_player_token_account: Pubkey,
+    pub mint: Pubkey,
+    pub token_program: Pubkey,
+    pub signer: Pubkey,
+    /// `Game::lobby`, the game's listing to remove.
+    pub lobby: Option<Pubkey>,
+    /// Empty for mints without a transfer hook.
+    pub transfer_hook_accounts: Vec<AccountMeta>,
+}
+
+impl ExpireGame {
+    pub fn instruction(&self) -> Instruction {
+        with_transfer_hook_accounts(
+            build(
+                accounts::ExpireGame {
+                    game: self.game,
+                    first_player_escrow_token_account: find_escrow_address(
+                        &self.game,
+                        &self.first_player,
+                    )
+                    .0,
+                    first_player_token_account: self.first_player_token_account,
+                    first_player: self.first_player,
+                    mint: self.mint,
+                    signer: self.signer,
+                    token_program: self.token_program,
+                    lobby: self.lobby,
+                    event_authority: event_authority(),
+                    program: ID,
+                },
+                instruction::ExpireGame {},
+            ),
+            &self.transfer_hook_accounts,
+        )
+    }
+}
+
+/// Permissionless, refunds a native game nobody joined before its deadline.
+pub struct ExpireNativeGame {
+    pub game: Pubkey,
+    pub first_player: Pubkey,
+    pub signer: Pubkey,
+    /// `Game::lobby`, the game's listing to remove.
+    pub lobby: Option<Pubkey>,
+}
+
+impl ExpireNativeGame {
+    pub fn instruction(&self) -> Instruction {
+        build(
+            accounts::ExpireNativeGame {
+                game: self.game,
+                first_player: self.first_player,
+                signer: self.signer,
+                lobby: self.lobby,
+                event_authority: event_authority(),
+                program: ID,
+            },
+            instruction::ExpireNativeGame {},
+        )
+    }
+}
+
+/// Permissionless, refunds both players of a stale game.
+pub struct UnwindGame {
+    pub game: Pubkey,
+    pub first_player: Pubkey,
+    pub first_player_token_account: Pubkey,
+    pub second_player: Pubkey,
+    pub second_player_token_account: Pubkey,
+    pub mint: Pubkey,
+    pub token_program: Pubkey,
+    pub signer: Pubkey,
+    /// `Game::side_pool`, required for games with one.
+    pub side_pool: Option<Pubkey>,
+    /// Empty for mints without a transfer hook.
+    pub transfer_hook_accounts: Vec<AccountMeta>,
+}
+
+impl UnwindGame {
+    pub fn instruction(&self) -> Instruction {
+        with_transfer_hook_accounts(
+            build(
+                accounts::UnwindGame {
+                    game: self.game,
+                    first_player_escrow_token_account: find_escrow_address(
+                        &self.game,
+                        &self.first_player,
+                    )
+                    .0,
+                    first_player_token_account: self.first_player_token_account,
+                    first_player: self.first_player,
+                    second_player_escrow_token_account: find_escrow_address(
+                        &self.game,
+                        &self.second_player,
+                    )
+                    .0,
+                    second_player_token_account: self.second_player_token_account,
+                    second_player: self.second_player,
+                    mint: self.mint,
+                    signer: self.signer,
+                    settings: find_settings_address().0,
+                    token_program: self.token_program,
+                    side_pool: self.side_pool,
+                    event_authority: event_authority(),
+                    program: ID,
+                },
+                instruction::UnwindGame {},
+            ),
+            &self.transfer_hook_accounts,
+        )
+    }
+}
+
+/// Permissionless, refunds both players of a stale native game.
+pub struct UnwindNativeGame {
+    pub game: Pubkey,
+    pub first_player: Pubkey,
+    pub second_player: Pubkey,
+    pub signer: Pubkey,
+    /// `Game::side_pool`, required for games with one.
+    pub side_pool: Option<Pubkey>,
+}
+
+impl UnwindNativeGame {
+    pub fn instruction(&self) -> Instruction {
+        build(
+            accounts::UnwindNativeGame {
+                game: self.game,
+                first_player: self.first_player,
+                second_player: self.second_player,
+                signer: self.signer,
+                settings: find_settings_address().0,
+                side_pool: self.side_pool,
+                event_authority: event_authority(),
+                program: ID,
+            },
+            instruction::UnwindNativeGame {},
+        )
+    }
+}
+
+/// Permissionless, pays out a decided game.
+pub struct SettleGame {
+    pub game: Pubkey,
+    pub first_player: Pubkey,
+    pub first_player_token_account: Pubkey,
+    pub second_player: Pubkey,
+    pub second_player_token_account: Pubkey,
+    pub mint: Pubkey,
+    pub token_program: Pubkey,
+    /// Treasury's token account for `mint`, receives the rake.
+    pub treasury_token_account: Pubkey,
+    pub signer: Pubkey,
+    /// `Game::season`, required for games counting towards one.
+    pub season: Option<Pubkey>,
+    /// `Game::first_player_referral` and `Game::second_player_referral`,
+    /// whose vaults are paid their share of the rake.
+    pub first_player_referral: Option<GameReferral>,
+    pub second_player_referral: Option<GameReferral>,
+    /// `Game::side_pool`, required for games with one.
+    pub side_pool: Option<Pubkey>,
+    /// Empty for mints without a transfer hook.
+    pub transfer_hook_accounts: Vec<AccountMeta>,
+}
+
+impl SettleGame {
+    pub fn instruction(&self) -> Instruction {
+        with_transfer_hook_accounts(
+            build(
+                accounts::SettleGame {
+                    game: self.game,
+                    settings: find_settings_address().0,
+                    first_player_escrow_token_account: find_escrow_address(
+                        &self.game,
+                        &self.first_player,
+                    )
+                    .0,
+                    first_player_token_account: self.first_player_token_account,
+                    first_player: self.first_player,
+                    second_player_escrow_token_account: find_escrow_address(
+                        &self.game,
+                        &self.second_player,
+                    )
+                    .0,
+                    second_player_token_account: self.second_player_token_account,
+                    second_player: self.second_player,
+                    mint: self.mint,
+                    treasury_token_account: self.treasury_token_account,
+                    first_player_profile: find_player_profile_address(&self.first_player).0,
+                    second_player_profile: find_player_profile_address(&self.second_player).0,
+                    first_player_rating: find_player_rating_address(&self.first_player).0,
+                    second_player_rating: find_player_rating_address(&self.second_player).0,
+                    signer: self.signer,
+                    token_program: self.token_program,
+                    season: self.season,
+                    first_player_season_entry: season_entry(self.season, &self.first_player),
+                    second_player_season_entry: season_entry(self.season, &self.second_player),
+                    first_player_referrer_vault: self
+                        .first_player_referral
+                        .as_ref()
+                        .and_then(|referral| referral.vault),
+                    second_player_referrer_vault: self
+                        .second_player_referral
+                        .as_ref()
+                        .and_then(|referral| referral.vault),
+                    side_pool: self.side_pool,
+                    event_authority: event_authority(),
+                    program: ID,
+                },
+                instruction::SettleGame {},
+            ),
+            &self.transfer_hook_accounts,
+        )
+    }
+}
+
+/// Permissionless, pays out a decided native game.
+pub struct SettleNativeGame {
+    pub game: Pubkey,
+    pub first_player: Pubkey,
+    pub second_player: Pubkey,
+    /// Must match `Settings::treasury`, receives the rake.
+    pub treasury: Pubkey,
+    pub signer: Pubkey,
+    /// `Game::season`, required for games counting towards one.
+    pub season: Option<Pubkey>,
+    /// `Game::first_player_referral` and `Game::second_player_referral`,
+    /// whose rewards are paid their share of the rake.
+    pub first_player_referral: Option<GameReferral>,
+    pub second_player_referral: Option<GameReferral>,
+    /// `Game::side_pool`, required for games with one.
+    pub side_pool: Option<Pubkey>,
+}
+
+impl SettleNativeGame {
+    pub fn instruction(&self) -> Instruction {
+        build(
+            accounts::SettleNativeGame {
+                game: self.game,
+                settings: find_settings_address().0,
+                first_player: self.first_player,
+                second_player: self.second_player,
+                treasury: self.treasury,
+                first_player_profile: find_player_profile_address(&self.first_player).0,
+                second_player_profile: find_player_profile_address(&self.second_player).0,
+                first_player_rating: find_player_rating_address(&self.first_player).0,
+                second_player_rating: find_player_rating_address(&self.second_player).0,
+                signer: self.signer,
+                season: self.season,
+                first_player_season_entry: season_entry(self.season, &self.first_player),
+                second_player_season_entry: season_entry(self.season, &self.second_player),
+                first_player_referrer_rewards: referral_rewards(&self.first_player_referral),
+                second_player_referrer_rewards: referral_rewards(&self.second_player_referral),
+                side_pool: self.side_pool,
+                event_authority: event_authority(),
+                program: ID,
+            },
+            instruction::SettleNativeGame {},
+        )
+    }
+}
+
+/// Permissionless, closes a settled game once its retention period is over.
+pub struct CloseGame {
+    pub game: Pubkey,
+    pub first_player: Pubkey,
+    pub signer: Pubkey,
+}
+
+impl CloseGame {
+    pub fn instruction(&self) -> Instruction {
+        build(
+            accounts::CloseGame {
+                game: self.game,
+                first_player: self.first_player,
+                settings: find_settings_address().0,
+                signer: self.signer,
+                event_authority: event_authority(),
+                program: ID,
+            },
+            instruction::CloseGame {},
+        )
+    }
+}
+
+/// Grows a game created before the latest `Game` fields to the current
+/// layout, `signer` paying the extra rent.
+pub struct MigrateGame {
+    pub game: Pubkey,
+    pub signer: Pubkey,
+}
+
+impl MigrateGame {
+    pub fn instruction(&self) -> Instruction {
+        build(
+            accounts::MigrateGame {
+                game: self.game,
+                signer: self.signer,
+                system_program: system_program::ID,
+                event_authority: event_authority(),
+                program: ID,
+            },
+            instruction::MigrateGame {},
+        )
+    }
+}
+
+/// Opens a single elimination tournament, entry fees in lamports.
+pub struct CreateTournament {
+    pub organizer: Pubkey,
+    pub tournament_id: String,
+    pub entry_fee: u64,
+    /// A power of two, up to `MAX_TOURNAMENT_PLAYERS`.
+    pub max_players: u8,
+    pub best_of: u8,
+    pub variant: GameVariant,
+    /// Share of the prize pool per place, champion first, adding up to `MAX_BPS`.
+    /// Players knocked out in the same round are paid the same.
+    pub prize_split_bps: Vec<u16>,
+    /// `None` for the settings' default, otherwise within the admin's bounds.
+    pub time_for_penalization: Option<i64>,
+    pub time_for_stale: Option<i64>,
+}
+
+impl CreateTournament {
+    pub fn instruction(&self) -> Instruction {
+        build(
+            accounts::CreateTournament {
+                tournament: find_tournament_address(&self.organizer, &self.tournament_id).0,
+                settings: find_settings_address().0,
+                organizer: self.organizer,
+                system_program: system_program::ID,
+                event_authority: event_authority(),
+                program: ID,
+            },
+            instruction::CreateTournament {
+                tournament_id: self.tournament_id.clone(),
+                entry_fee: self.entry_fee,
+                max_players: self.max_players,
+                best_of: self.best_of,
+                variant: self.variant.clone(),
+                prize_split_bps: self.prize_split_bps.clone(),
+                time_for_penalization: self.time_for_penalization,
+                time_for_stale: self.time_for_stale,
+            },
+        )
+    }
+}
+
+/// Pays the entry fee and takes a spot in the bracket.
+pub struct RegisterForTournament {
+    pub tournament: Pubkey,
+    pub player: Pubkey,
+}
+
+impl RegisterForTournament {
+    pub fn instruction(&self) -> Instruction {
+        build(
+            accounts::RegisterForTournament {
+                tournament: self.tournament,
+                settings: find_settings_address().0,
+                player: self.player,
+                system_program: system_program::ID,
+                event_authority: event_authority(),
+                program: ID,
+            },
+            instruction::RegisterForTournament {},
+        )
+    }
+}
+
+/// Gives up a spot before the tournament starts, refunding the entry fee.
+pub struct LeaveTournament {
+    pub tournament: Pubkey,
+    pub player: Pubkey,
+}
+
+impl LeaveTournament {
+    pub fn instruction(&self) -> Instruction {
+        build(
+            accounts::LeaveTournament {
+                tournament: self.tournament,
+                player: self.player,
+                event_authority: event_authority(),
+                program: ID,
+            },
+            instruction::LeaveTournament {},
+        )
+    }
+}
+
+/// Signed by the organizer once the bracket is full.
+pub struct StartTournament {
+    pub tournament: Pubkey,
+    pub organizer: Pubkey,
+    /// Registration index of each bracket slot, slots `2n` and `2n + 1` meet
+    /// in the first round.
+    pub seeding: Vec<u8>,
+}
+
+impl StartTournament {
+    pub fn instruction(&self) -> Instruction {
+        build(
+            accounts::StartTournament {
+                tournament: self.tournament,
+                organizer: self.organizer,
+                event_authority: event_authority(),
+                program: ID,
+            },
+            instruction::StartTournament {
+                seeding: self.seeding.clone(),
+            },
+        )
+    }
+}
+
+/// Opens the game of a current round match, only the opponent can join it.
+pub struct CreateTournamentGame {
+    pub tournament: Pubkey,
+    pub player: Pubkey,
+    /// `Tournament::round`.
+    pub round: u8,
+    pub match_index: u8,
+    pub choice: Choice,
+    pub salt: [u8; 32],
+}
+
+impl CreateTournamentGame {
+    pub fn instruction(&self) -> Instruction {
+        let game = find_tournament_game_address(
+            &self.tournament,
+            self.round,
+            self.match_index,
+            &self.player,
+        )
+        .0;
+        build(
+            accounts::CreateTournamentGame {
+                tournament: self.tournament,
+                game,
+                player: self.player,
+                system_program: system_program::ID,
+                event_authority: event_authority(),
+                program: ID,
+            },
+            instruction::CreateTournamentGame {
+                match_index: self.match_index,
+                game_id: Tournament::game_id(&self.tournament, self.round, self.match_index),
+                hash: commitment(&game, &self.player, &self.choice, &self.salt),
+            },
+        )
+    }
+}
+
+pub struct JoinTournamentGame {
+    pub tournament: Pubkey,
+    pub game: Pubkey,
+    pub player: Pubkey,
+    pub match_index: u8,
+    pub choice: Choice,
+    pub salt: [u8; 32],
+}
+
+impl JoinTournamentGame {
+    pub fn instruction(&self) -> Instruction {
+        build(
+            accounts::JoinTournamentGame {
+                tournament: self.tournament,
+                game: self.game,
+                player: self.player,
+                event_authority: event_authority(),
+                program: ID,
+            },
+            instruction::JoinTournamentGame {
+                match_index: self.match_index,
+                hash: commitment(&self.game, &self.player, &self.choice, &self.salt),
+            },
+        )
+    }
+}
+
+/// Permissionless, decides a match with its game's result and closes the game.
+pub struct SettleTournamentGame {
+    pub tournament: Pubkey,
+    pub game: Pubkey,
+    pub first_player: Pubkey,
+    pub match_index: u8,
+    pub signer: Pubkey,
+}
+
+impl SettleTournamentGame {
+    pub fn instruction(&self) -> Instruction {
+        build(
+            accounts::SettleTournamentGame {
+                tournament: self.tournament,
+                game: self.game,
+                settings: find_settings_address().0,
+                first_player: self.first_player,
+                signer: self.signer,
+                event_authority: event_authority(),
+                program: ID,
+            },
+            instruction::SettleTournamentGame {
+                match_index: self.match_index,
+            },
+        )
+    }
+}
+
+/// Permissionless, decides a match nobody played once the round deadline
+/// passes.
+pub struct ClaimTournamentWalkover {
+    pub tournament: Pubkey,
+    /// The match's unjoined game and its creator, if one was opened.
+    pub game: Option<(Pubkey, Pubkey)>,
+    pub match_index: u8,
+    pub signer: Pubkey,
+}
+
+impl ClaimTournamentWalkover {
+    pub fn instruction(&self) -> Instruction {
+        build(
+            accounts::ClaimTournamentWalkover {
+                tournament: self.tournament,
+                game: self.game.map(|(game, _)| game),
+                first_player: self.game.map(|(_, first_player)| first_player),
+                signer: self.signer,
+                event_authority: event_authority(),
+                program: ID,
+            },
+            instruction::ClaimTournamentWalkover {
+                match_index: self.match_index,
+            },
+        )
+    }
+}
+
+/// Pays a finished tournament's prize for the signer's place.
+pub struct ClaimTournamentPrize {
+    pub tournament: Pubkey,
+    pub player: Pubkey,
+}
+
+impl ClaimTournamentPrize {
+    pub fn instruction(&self) -> Instruction {
+        build(
+            accounts::ClaimTournamentPrize {
+                tournament: self.tournament,
+                player: self.player,
+                event_authority: event_authority(),
+                program: ID,
+            },
+            instruction::ClaimTournamentPrize {},
+        )
+    }
+}
+
+/// Signed by the organizer once every prize is paid, or nobody is registered.
+pub struct CloseTournament {
+    pub tournament: Pubkey,
+    pub organizer: Pubkey,
+}
+
+impl CloseTournament {
+    pub fn instruction(&self) -> Instruction {
+        build(
+            accounts::CloseTournament {
+                tournament: self.tournament,
+                settings: find_settings_address().0,
+                organizer: self.organizer,
+                event_authority: event_authority(),
+                program: ID,
+            },
+            instruction::CloseTournament {},
+        )
+    }
+}
+
+/// Signed by the admin, `referral_share_bps` of fees and rake paid by
+/// referred players goes to their referrers.
+pub struct SetReferralShare {
+    pub signer: Pubkey,
+    pub referral_share_bps: u16,
+}
+
+impl SetReferralShare {
+    pub fn instruction(&self) -> Instruction {
+        build(
+            accounts::SetReferralShare {
+                settings: find_settings_address().0,
+                signer: self.signer,
+                event_authority: event_authority(),
+                program: ID,
+            },
+            instruction::SetReferralShare {
+                referral_share_bps: self.referral_share_bps,
+            },
+        )
+    }
+}
+
+/// Signed by the admin.
+pub struct CreateSeason {
+    pub signer: Pubkey,
+    pub season_id: u32,
+    pub leaderboard_size: u8,
+    pub start_at: i64,
+    pub end_at: i64,
+    pub ranking: SeasonRanking,
+    pub fee_share_bps: u16,
+    /// First place first, adds up to `MAX_BPS`.
+    pub prize_split_bps: Vec<u16>,
+}
+
+impl CreateSeason {
+    pub fn instruction(&self) -> Instruction {
+        build(
+            accounts::CreateSeason {
+                season: find_season_address(self.season_id).0,
+                settings: find_settings_address().0,
+                signer: self.signer,
+                system_program: system_program::ID,
+                event_authority: event_authority(),
+                program: ID,
+            },
+            instruction::CreateSeason {
+                season_id: self.season_id,
+                leaderboard_size: self.leaderboard_size,
+                start_at: self.start_at,
+                end_at: self.end_at,
+                ranking: self.ranking.clone(),
+                fee_share_bps: self.fee_share_bps,
+                prize_split_bps: self.prize_split_bps.clone(),
+            },
+        )
+    }
+}
+
+/// Creates `player`'s entry, needed to play games counting towards the
+/// season.
+pub struct JoinSeason {
+    pub season: Pubkey,
+    pub player: Pubkey,
+}
+
+impl JoinSeason {
+    pub fn instruction(&self) -> Instruction {
+        build(
+            accounts::JoinSeason {
+                season: self.season,
+                season_entry: find_season_entry_address(&self.season, &self.player).0,
+                player: self.player,
+                system_program: system_program::ID,
+                event_authority: event_authority(),
+                program: ID,
+            },
+            instruction::JoinSeason {},
+        )
+    }
+}
+
+/// Ranks `player`'s entry at its current score when the leaderboard left
+/// it behind, signed by anyone.
+pub struct RankSeasonEntry {
+    pub season: Pubkey,
+    pub player: Pubkey,
+    pub signer: Pubkey,
+}
+
+impl RankSeasonEntry {
+    pub fn instruction(&self) -> Instruction {
+        build(
+            accounts::RankSeasonEntry {
+                season: self.season,
+                season_entry: find_season_entry_address(&self.season, &self.player).0,
+                signer: self.signer,
+                event_authority: event_authority(),
+                program: ID,
+            },
+            instruction::RankSeasonEntry {},
+        )
+    }
+}
+
+/// Signed by a player on the leaderboard of a finished season.
+pub struct ClaimSeasonPrize {
+    pub season: Pubkey,
+    pub player: Pubkey,
+}
+
+impl ClaimSeasonPrize {
+    pub fn instruction(&self) -> Instruction {
+        build(
+            accounts::ClaimSeasonPrize {
+                season: self.season,
+                player: self.player,
+                event_authority: event_authority(),
+                program: ID,
+            },
+            instruction::ClaimSeasonPrize {},
+        )
+    }
+}
+
+/// Signed by the admin once a season's claims are over, the prizes left go
+/// to `treasury`.
+pub struct SweepSeasonPrizes {
+    pub season: Pubkey,
+    pub treasury: Pubkey,
+    pub signer: Pubkey,
+}
+
+impl SweepSeasonPrizes {
+    pub fn instruction(&self) -> Instruction {
+        build(
+            accounts::SweepSeasonPrizes {
+                season: self.season,
+                settings: find_settings_address().0,
+                treasury: self.treasury,
+                signer: self.signer,
+                event_authority: event_authority(),
+                program: ID,
+            },
+            instruction::SweepSeasonPrizes {},
+        )
+    }
+}
+
+/// Records `referrer` as `player`'s, once and for good.
+pub struct RegisterReferrer {
+    pub player: Pubkey,
+    pub referrer: Pubkey,
+}
+
+impl RegisterReferrer {
+    pub fn instruction(&self) -> Instruction {
+        build(
+            accounts::RegisterReferrer {
+                referral: find_referral_address(&self.player).0,
+                referrer_rewards: find_referrer_rewards_address(&self.referrer).0,
+                referrer: self.referrer,
+                player: self.player,
+                system_program: system_program::ID,
+                event_authority: event_authority(),
+                program: ID,
+            },
+            instruction::RegisterReferrer {},
+        )
+    }
+}
+
+/// Signed by the referrer, pays out its SOL rewards.
+pub struct ClaimReferralRewards {
+    pub referrer: Pubkey,
+}
+
+impl ClaimReferralRewards {
+    pub fn instruction(&self) -> Instruction {
+        build(
+            accounts::ClaimReferralRewards {
+                referrer_rewards: find_referrer_rewards_address(&self.referrer).0,
+                referrer: self.referrer,
+                event_authority: event_authority(),
+                program: ID,
+            },
+            instruction::ClaimReferralRewards {},
+        )
+    }
+}
+
+/// Signed by the referrer, empties one of its rewards' token accounts.
+pub struct ClaimReferralTokenRewards {
+    pub referrer: Pubkey,
+    /// Token account owned by the referrer's rewards, e.g. its associated
+    /// token account.
+    pub referrer_vault: Pubkey,
+    pub referrer_token_account: Pubkey,
+    pub mint: Pubkey,
+    pub token_program: Pubkey,
+    /// Empty for mints without a transfer hook.
+    pub transfer_hook_accounts: Vec<AccountMeta>,
+}
+
+impl ClaimReferralTokenRewards {
+    pub fn instruction(&self) -> Instruction {
+        with_transfer_hook_accounts(
+            build(
+                accounts::ClaimReferralTokenRewards {
+                    referrer_rewards: find_referrer_rewards_address(&self.referrer).0,
+                    referrer_vault: self.referrer_vault,
+                    referrer_token_account: self.referrer_token_account,
+                    mint: self.mint,
+                    referrer: self.referrer,
+                    token_program: self.token_program,
+                    event_authority: event_authority(),
+                    program: ID,
+                },
+                instruction::ClaimReferralTokenRewards {},
+            ),
+            &self.transfer_hook_accounts,
+        )
+    }
+}
+
+/// Opens spectators' side bets on a started game until a player reveals.
+pub struct OpenSidePool {
+    pub game: Pubkey,
+    pub creator: Pubkey,
+}
+
+impl OpenSidePool {
+    pub fn instruction(&self) -> Instruction {
+        build(
+            accounts::OpenSidePool {
+                game: self.game,
+                side_pool: find_side_pool_address(&self.game).0,
+                creator: self.creator,
+                system_program: system_program::ID,
+                event_authority: event_authority(),
+                program: ID,
+            },
+            instruction::OpenSidePool {},
+        )
+    }
+}
+
+/// Stakes `amount` lamports on `outcome`, once per bettor and pool.
+pub struct PlaceSideBet {
+    pub game: Pubkey,
+    pub bettor: Pubkey,
+    pub outcome: SideBetOutcome,
+    pub amount: u64,
+}
+
+impl PlaceSideBet {
+    pub fn instruction(&self) -> Instruction {
+        let side_pool = find_side_pool_address(&self.game).0;
+        build(
+            accounts::PlaceSideBet {
+                game: self.game,
+                side_pool,
+                side_bet: find_side_bet_address(&side_pool, &self.bettor).0,
+                bettor: self.bettor,
+                system_program: system_program::ID,
+                event_authority: event_authority(),
+                program: ID,
+            },
+            instruction::PlaceSideBet {
+                outcome: self.outcome.clone(),
+                amount: self.amount,
+            },
+        )
+    }
+}
+
+/// Signed by the bettor once the game settled or unwound.
+pub struct ClaimSideBet {
+    pub side_pool: Pubkey,
+    pub bettor: Pubkey,
+}
+
+impl ClaimSideBet {
+    pub fn instruction(&self) -> Instruction {
+        build(
+            accounts::ClaimSideBet {
+                side_pool: self.side_pool,
+                side_bet: find_side_bet_address(&self.side_pool, &self.bettor).0,
+                bettor: self.bettor,
+                event_authority: event_authority(),
+                program: ID,
+            },
+            instruction::ClaimSideBet {},
+        )
+    }
+}
+
+/// Permissionless, closes a side pool once every bet is claimed.
+pub struct CloseSidePool {
+    pub side_pool: Pubkey,
+    /// `SidePool::creator`, refunded the rent.
+    pub creator: Pubkey,
+    pub signer: Pubkey,
+}
+
+impl CloseSidePool {
+    pub fn instruction(&self) -> Instruction {
+        build(
+            accounts::CloseSidePool {
+                side_pool: self.side_pool,
+                creator: self.creator,
+                signer: self.signer,
+                event_authority: event_authority(),
+                program: ID,
+            },
+            instruction::CloseSidePool {},
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::{AnchorDeserialize, Discriminator};
+
+    use super::*;
+
+    #[test]
+    fn builds_initialize_game_with_a_bound_commitment() {
+        let player = Pubkey::new_unique();
+        let salt = [7; 32];
+        let ix = InitializeGame {
+            player,
+            player_token_account: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            token_program: anchor_spl::token::ID,
+            treasury: Pubkey::new_unique(),
+            game_id: "game".to_string(),
+            amount: 10,
+            choice: Choice::Paper,
+            salt,
+            best_of: 3,
+            variant: GameVariant::Classic,
+            join_deadline: None,
+            time_for_penalization: None,
+            time_for_stale: None,
+            join_restriction: None,
+            rating_band: None,
+            season: None,
+            referrer: None,
+            referrer_vault: None,
+            transfer_hook_accounts: vec![],
+        }
+        .instruction();
+
+        let game = find_game_address(&player, "game").0;
+        assert_eq!(ix.program_id, ID);
+        assert_eq!(ix.accounts[0].pubkey, game);
+        assert_eq!(ix.accounts[1].pubkey, find_escrow_address(&game, &player).0);
+        assert_eq!(ix.data[..8], instruction::InitializeGame::DISCRIMINATOR);
+
+        let args = instruction::InitializeGame::try_from_slice(&ix.data[8..]).unwrap();
+        assert_eq!(args.hash, commitment(&game, &player, &Choice::Paper, &salt));
+        assert_eq!(args.best_of, 3);
+    }
+}
@@ -0,0 +1,80 @@
+use anchor_lang::prelude::Pubkey;
+use rock_paper_scissors::{invite_leaf, merkle_parent, JoinRestriction};
+
+/// Merkle tree of the players allowed to join an invite-only game.
+pub struct InviteList {
+    players: Vec<Pubkey>,
+    /// Leaves first, the root alone last. A level's odd node out is carried
+    /// up unchanged.
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+impl InviteList {
+    /// ### Panics
+    ///
+    /// Panics if `players` is empty.
+    pub fn new(players: &[Pubkey]) -> Self {
+        assert!(!players.is_empty(), "an invite list needs a player");
+        let mut levels = vec![players.iter().map(invite_leaf).collect::<Vec<_>>()];
+        while levels[levels.len() - 1].len() > 1 {
+            let next = levels[levels.len() - 1]
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => merkle_parent(left, right),
+                    [node] => *node,
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(next);
+        }
+        Self {
+            players: players.to_vec(),
+            levels,
+        }
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.levels[self.levels.len() - 1][0]
+    }
+
+    /// What to pass `initialize_game` to restrict joins to the list.
+    pub fn join_restriction(&self) -> JoinRestriction {
+        JoinRestriction::MerkleRoot(self.root())
+    }
+
+    /// Proof `player` passes `join_game`, `None` if they aren't invited.
+    pub fn proof(&self, player: &Pubkey) -> Option<Vec<[u8; 32]>> {
+        let mut index = self.players.iter().position(|p| p == player)?;
+        let mut proof = vec![];
+        for level in &self.levels[..self.levels.len() - 1] {
+            if let Some(sibling) = level.get(index ^ 1) {
+                proof.push(*sibling);
+            }
+            index /= 2;
+        }
+        Some(proof)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rock_paper_scissors::verify_merkle_proof;
+
+    use super::*;
+
+    #[test]
+    fn proves_every_invited_player() {
+        let players: Vec<_> = (0..5).map(|_| Pubkey::new_unique()).collect();
+        let invites = InviteList::new(&players);
+
+        for player in &players {
+            let proof = invites.proof(player).unwrap();
+            assert!(verify_merkle_proof(
+                &invites.root(),
+                invite_leaf(player),
+                &proof
+            ));
+        }
+        assert_eq!(invites.proof(&Pubkey::new_unique()), None);
+    }
+}
@@ -0,0 +1,22 @@
+//! Rust client for the rock-paper-scissors program: instruction builders,
+//! PDA derivation, commitment hashing, invite lists and account decoding.
+pub mod accounts;
+pub mod commitment;
+pub mod instructions;
+pub mod invites;
+pub mod pda;
+
+pub use accounts::*;
+pub use commitment::*;
+pub use instructions::*;
+pub use invites::*;
+pub use pda::*;
+
+pub use rock_paper_scissors::{
+    Choice, Game, GameReferral, GameState, GameVariant, JoinRestriction, Lobby, MintConfig,
+    MintStats, Player, PlayerProfile, PlayerRating, RatingBand, Referral, ReferrerRewards, Round,
+    Season, SeasonEntry, SeasonRanking, Settings, SideBet, SideBetOutcome, SidePool,
+    SidePoolResult, Tournament, TournamentMatch, TournamentState, WagerMode, DEFAULT_RATING,
+    ID as PROGRAM_ID, MAX_LEADERBOARD_SIZE, MAX_TOURNAMENT_PLAYERS, PAUSE_ALL, PAUSE_JOINS,
+    PAUSE_NEW_GAMES, SEASON_CLAIM_PERIOD, SEASON_RANKING_PERIOD,
+};
@@ -0,0 +1,128 @@
+use anchor_lang::{prelude::Pubkey, solana_program::bpf_loader_upgradeable};
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use rock_paper_scissors::{
+    Tournament, GAME, GAME_ESCROW, ID, LOBBY, MINT_CONFIG, PLAYER_PROFILE, PLAYER_RATING, REFERRAL,
+    REFERRER_REWARDS, SEASON, SEASON_ENTRY, SETTINGS, SIDE_BET, SIDE_POOL, TOURNAMENT,
+};
+
+pub fn find_settings_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SETTINGS.as_ref()], &ID)
+}
+
+/// Holds the program's upgrade authority, who alone can initialize settings.
+pub fn find_program_data_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ID.as_ref()], &bpf_loader_upgradeable::ID)
+}
+
+/// `Pubkey::default()` for native SOL wagers.
+pub fn find_mint_config_address(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            MINT_CONFIG.as_ref(),
+            find_settings_address().0.as_ref(),
+            mint.as_ref(),
+        ],
+        &ID,
+    )
+}
+
+/// Open games wagering `amount` of `mint`, `Pubkey::default()` for native SOL.
+pub fn find_lobby_address(mint: &Pubkey, amount: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[LOBBY.as_ref(), mint.as_ref(), amount.to_le_bytes().as_ref()],
+        &ID,
+    )
+}
+
+pub fn find_player_profile_address(player: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PLAYER_PROFILE.as_ref(), player.as_ref()], &ID)
+}
+
+pub fn find_player_rating_address(player: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PLAYER_RATING.as_ref(), player.as_ref()], &ID)
+}
+
+/// Records who referred `player`, if anyone did.
+pub fn find_referral_address(player: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[REFERRAL.as_ref(), player.as_ref()], &ID)
+}
+
+/// Holds a referrer's SOL rewards and owns its token reward accounts.
+pub fn find_referrer_rewards_address(referrer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[REFERRER_REWARDS.as_ref(), referrer.as_ref()], &ID)
+}
+
+pub fn find_game_address(first_player: &Pubkey, game_id: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[GAME.as_ref(), first_player.as_ref(), game_id.as_bytes()],
+        &ID,
+    )
+}
+
+pub fn find_tournament_address(organizer: &Pubkey, tournament_id: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            TOURNAMENT.as_ref(),
+            organizer.as_ref(),
+            tournament_id.as_bytes(),
+        ],
+        &ID,
+    )
+}
+
+/// Game of a tournament match, as opened by `player`.
+pub fn find_tournament_game_address(
+    tournament: &Pubkey,
+    round: u8,
+    match_index: u8,
+    player: &Pubkey,
+) -> (Pubkey, u8) {
+    find_game_address(player, &Tournament::game_id(tournament, round, match_index))
+}
+
+pub fn find_season_address(season_id: u32) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SEASON.as_ref(), &season_id.to_le_bytes()], &ID)
+}
+
+pub fn find_season_entry_address(season: &Pubkey, player: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[SEASON_ENTRY.as_ref(), season.as_ref(), player.as_ref()],
+        &ID,
+    )
+}
+
+/// Spectators' side bets on `game`.
+pub fn find_side_pool_address(game: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SIDE_POOL.as_ref(), game.as_ref()], &ID)
+}
+
+pub fn find_side_bet_address(side_pool: &Pubkey, bettor: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[SIDE_BET.as_ref(), side_pool.as_ref(), bettor.as_ref()],
+        &ID,
+    )
+}
+
+pub fn find_escrow_address(game: &Pubkey, player: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[GAME_ESCROW.as_ref(), game.as_ref(), player.as_ref()], &ID)
+}
+
+/// Signer of the self-CPI the program emits its events through.
+pub fn find_event_authority_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"__event_authority"], &ID)
+}
+
+pub fn associated_token_address(owner: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(owner, mint, token_program)
+}
+
+/// Where a transfer hook program keeps the extra accounts it needs for `mint`.
+pub fn find_extra_account_metas_address(
+    mint: &Pubkey,
+    transfer_hook_program: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"extra-account-metas", mint.as_ref()],
+        transfer_hook_program,
+    )
+}
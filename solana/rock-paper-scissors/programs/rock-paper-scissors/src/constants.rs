@@ -3,6 +3,70 @@ use anchor_lang::prelude::*;
 #[constant]
 pub const SETTINGS: [u8; 8] = *b"settings";
 
+#[constant]
+pub const MINT_CONFIG: [u8; 11] = *b"mint_config";
+
+#[constant]
+pub const LOBBY: [u8; 5] = *b"lobby";
+/// Open games a lobby lists at once.
+pub const LOBBY_CAPACITY: usize = 16;
+
+#[constant]
+pub const PLAYER_PROFILE: [u8; 14] = *b"player_profile";
+/// Mints a profile keeps wager totals for, later ones only count results.
+pub const MAX_PROFILE_MINTS: usize = 8;
+
+#[constant]
+pub const PLAYER_RATING: [u8; 13] = *b"player_rating";
+/// Rating of a player without rated games.
+#[constant]
+pub const DEFAULT_RATING: u32 = 1_200;
+/// Floor ratings can't drop below.
+pub const MIN_RATING: u32 = 100;
+/// Elo K-factor of established players, and of players with fewer than
+/// `PROVISIONAL_GAMES` rated games.
+pub const RATING_K_FACTOR: u32 = 20;
+pub const PROVISIONAL_K_FACTOR: u32 = 40;
+pub const PROVISIONAL_GAMES: u32 = 30;
+
+#[constant]
+pub const TOURNAMENT: [u8; 10] = *b"tournament";
+/// Largest bracket a tournament can run.
+#[constant]
+pub const MAX_TOURNAMENT_PLAYERS: u8 = 16;
+/// Most finishing places a prize split can pay.
+#[constant]
+pub const MAX_PRIZE_PLACES: u8 = 4;
+
+#[constant]
+pub const REFERRAL: [u8; 8] = *b"referral";
+#[constant]
+pub const REFERRER_REWARDS: [u8; 16] = *b"referrer_rewards";
+
+#[constant]
+pub const SIDE_POOL: [u8; 9] = *b"side_pool";
+#[constant]
+pub const SIDE_BET: [u8; 8] = *b"side_bet";
+
+#[constant]
+pub const SEASON: [u8; 6] = *b"season";
+#[constant]
+pub const SEASON_ENTRY: [u8; 12] = *b"season_entry";
+/// Most players a season leaderboard ranks.
+#[constant]
+pub const MAX_LEADERBOARD_SIZE: u8 = 10;
+/// Points a season game is worth to its winner, and to each player on a draw.
+pub const SEASON_WIN_POINTS: u64 = 3;
+pub const SEASON_DRAW_POINTS: u64 = 1;
+/// Time after a season ends during which entries can still be ranked, before
+/// its prizes can be claimed.
+#[constant]
+pub const SEASON_RANKING_PERIOD: i64 = 24 * 60 * 60;
+/// Time season prizes can be claimed for, the admin sweeps what's left to
+/// the treasury after it.
+#[constant]
+pub const SEASON_CLAIM_PERIOD: i64 = 30 * 24 * 60 * 60;
+
 #[constant]
 pub const GAME: [u8; 4] = *b"game";
 #[constant]
@@ -10,4 +74,25 @@ pub const GAME_ESCROW: [u8; 11] = *b"game_escrow";
 #[constant]
 pub const FIRST_PLAYER: [u8; 12] = *b"first_player";
 #[constant]
-pub const SECOND_PLAYER: [u8; 13] = *b"second_player";
\ No newline at end of file
+pub const SECOND_PLAYER: [u8; 13] = *b"second_player";
+
+#[constant]
+pub const COMMITMENT_DOMAIN: [u8; 17] = *b"rps_commitment_v1";
+
+#[constant]
+pub const MAX_BEST_OF: u8 = 7;
+/// Upper bound on rounds played in a match, replayed ties included.
+#[constant]
+pub const MAX_MATCH_ROUNDS: u8 = 15;
+
+#[constant]
+pub const MAX_BPS: u16 = 10_000;
+
+/// `Settings::paused` bit blocking game creation.
+#[constant]
+pub const PAUSE_NEW_GAMES: u8 = 1 << 0;
+/// `Settings::paused` bit blocking joins.
+#[constant]
+pub const PAUSE_JOINS: u8 = 1 << 1;
+#[constant]
+pub const PAUSE_ALL: u8 = PAUSE_NEW_GAMES | PAUSE_JOINS;
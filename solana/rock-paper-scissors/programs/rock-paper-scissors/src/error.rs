@@ -18,4 +18,128 @@ pub enum RockPaperScissorsError {
     PlayerAlreadyRevealed,
     #[msg("Numeric overflow")]
     NumericOverflow,
+    #[msg("Both players can't commit the same hash")]
+    DuplicateHash,
+    #[msg("Invalid number of rounds")]
+    InvalidBestOf,
+    #[msg("Player already committed")]
+    PlayerAlreadyCommitted,
+    #[msg("Both players must commit before revealing")]
+    AwaitingCommitments,
+    #[msg("Awaiting reveals from both players")]
+    AwaitingReveals,
+    #[msg("Choice is not allowed in this game variant")]
+    InvalidChoice,
+    #[msg("Rake can't exceed 10000 basis points")]
+    InvalidRake,
+    #[msg("Join deadline must be in the future")]
+    InvalidJoinDeadline,
+    #[msg("Join deadline has passed")]
+    JoinDeadlinePassed,
+    #[msg("Join deadline has not passed")]
+    JoinDeadlineNotReached,
+    #[msg("Penalization window has not elapsed")]
+    PenalizationWindowNotElapsed,
+    #[msg("Game retention period has not elapsed")]
+    GameRetentionPeriodNotElapsed,
+    #[msg("Instruction doesn't support the game's wager mode")]
+    InvalidWagerMode,
+    #[msg("Mint has an unsupported Token-2022 extension")]
+    UnsupportedMintExtension,
+    #[msg("Mint is not enabled for wagers")]
+    MintNotEnabled,
+    #[msg("Minimum amount must be positive and not exceed the maximum")]
+    InvalidAmountLimits,
+    #[msg("Amount is below the mint's minimum")]
+    AmountBelowMinimum,
+    #[msg("Amount is above the mint's maximum")]
+    AmountAboveMaximum,
+    #[msg("No admin handover is pending for this signer")]
+    NoPendingAdmin,
+    #[msg("Settings account is already up to date")]
+    SettingsAlreadyMigrated,
+    #[msg("Unknown pause flags")]
+    InvalidPauseFlags,
+    #[msg("Creating games is paused")]
+    NewGamesPaused,
+    #[msg("Joining games is paused")]
+    JoinsPaused,
+    #[msg("Minimum window must be positive and not exceed the maximum")]
+    InvalidWindowBounds,
+    #[msg("Window is outside the admin's bounds")]
+    WindowOutOfBounds,
+    #[msg("Player is not invited to this game")]
+    NotInvited,
+    #[msg("Lobby doesn't match the one the game is listed in")]
+    InvalidLobby,
+    #[msg("Tournament size must be a power of two within the maximum")]
+    InvalidTournamentSize,
+    #[msg("Prize split must pay whole rounds of places, equally within a round, up to the maximum and add up to the whole pool")]
+    InvalidPrizeSplit,
+    #[msg("Tournament is not in the expected state")]
+    InvalidTournamentState,
+    #[msg("Tournament is full")]
+    TournamentFull,
+    #[msg("Player is already registered")]
+    AlreadyRegistered,
+    #[msg("Player is not registered")]
+    NotRegistered,
+    #[msg("Tournament can only start with a full bracket")]
+    TournamentNotFull,
+    #[msg("Seeding must order every registered player once")]
+    InvalidSeeding,
+    #[msg("Match doesn't exist, is decided or doesn't match this game")]
+    InvalidMatch,
+    #[msg("Round deadline has not been reached")]
+    RoundDeadlineNotReached,
+    #[msg("No prize for this player")]
+    NoPrizeToClaim,
+    #[msg("Prize already claimed")]
+    PrizeAlreadyClaimed,
+    #[msg("Tournament still holds entry fees or prizes")]
+    TournamentNotClosable,
+    #[msg("Rating band minimum can't exceed its maximum")]
+    InvalidRatingBand,
+    #[msg("Player's rating is outside the game's rating band")]
+    RatingOutOfBand,
+    #[msg("Season must end after it starts, and in the future")]
+    InvalidSeasonWindow,
+    #[msg("Leaderboard size must be between one and the maximum")]
+    InvalidLeaderboardSize,
+    #[msg("Fee share can't exceed 100%")]
+    InvalidFeeShare,
+    #[msg("Season is not running")]
+    SeasonNotActive,
+    #[msg("Season standings are not final yet")]
+    SeasonNotEnded,
+    #[msg("Season or season entry doesn't match the game or player")]
+    SeasonMismatch,
+    #[msg("Season ranks net winnings in a different mint")]
+    SeasonMintMismatch,
+    #[msg("Players can't refer themselves")]
+    SelfReferral,
+    #[msg("Referral accounts don't match the player's referrer")]
+    ReferralMismatch,
+    #[msg("No referral rewards to claim")]
+    NoReferralRewards,
+    #[msg("Side bets close once a player reveals")]
+    SideBetsClosed,
+    #[msg("Side pool doesn't match the game's")]
+    SidePoolMismatch,
+    #[msg("Side pool has not been resolved yet")]
+    SidePoolNotResolved,
+    #[msg("Side pool still holds unclaimed bets")]
+    SidePoolHasOpenBets,
+    #[msg("Side bets must be positive and can't come from the game's players")]
+    InvalidSideBet,
+    #[msg("Game already fits the current layout")]
+    GameAlreadyMigrated,
+    #[msg("Season standings are final")]
+    SeasonStandingsFinal,
+    #[msg("Season entry is already ranked at its current score")]
+    SeasonEntryAlreadyRanked,
+    #[msg("Season prize claims are over")]
+    SeasonClaimsClosed,
+    #[msg("Season prizes can still be claimed")]
+    SeasonClaimsNotOver,
 }
@@ -0,0 +1,504 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    Choice, GameState, GameVariant, JoinRestriction, LeaderboardEntry, MintConfig, Player,
+    RatingBand, Round, SeasonRanking, Settings, SideBetOutcome, SidePoolResult, TournamentMatch,
+    TournamentState,
+};
+
+/// Every configurable value in `Settings`, so settings events can carry
+/// before and after snapshots.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct SettingsValues {
+    pub time_for_penalization: i64,
+    pub time_for_stale: i64,
+    pub treasury: Pubkey,
+    pub player_fee_lamports: u64,
+    pub rake_bps: u16,
+    pub rake_draws: bool,
+    pub game_retention_period: i64,
+}
+
+impl From<&Settings> for SettingsValues {
+    fn from(settings: &Settings) -> Self {
+        Self {
+            time_for_penalization: settings.time_for_penalization,
+            time_for_stale: settings.time_for_stale,
+            treasury: settings.treasury,
+            player_fee_lamports: settings.player_fee_lamports,
+            rake_bps: settings.rake_bps,
+            rake_draws: settings.rake_draws,
+            game_retention_period: settings.game_retention_period,
+        }
+    }
+}
+
+#[event]
+pub struct SettingsInitialized {
+    pub settings: Pubkey,
+    pub admin: Pubkey,
+    pub values: SettingsValues,
+}
+
+#[event]
+pub struct SettingsUpdated {
+    pub settings: Pubkey,
+    pub old_values: SettingsValues,
+    pub new_values: SettingsValues,
+}
+
+/// Bounds on the windows creators may pick for their games.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct WindowBoundsValues {
+    pub min_time_for_penalization: i64,
+    pub max_time_for_penalization: i64,
+    pub min_time_for_stale: i64,
+    pub max_time_for_stale: i64,
+}
+
+impl From<&Settings> for WindowBoundsValues {
+    fn from(settings: &Settings) -> Self {
+        Self {
+            min_time_for_penalization: settings.min_time_for_penalization,
+            max_time_for_penalization: settings.max_time_for_penalization,
+            min_time_for_stale: settings.min_time_for_stale,
+            max_time_for_stale: settings.max_time_for_stale,
+        }
+    }
+}
+
+#[event]
+pub struct WindowBoundsUpdated {
+    pub settings: Pubkey,
+    pub old_values: WindowBoundsValues,
+    pub new_values: WindowBoundsValues,
+}
+
+#[event]
+pub struct SettingsMigrated {
+    pub settings: Pubkey,
+    pub admin: Pubkey,
+    pub values: SettingsValues,
+}
+
+#[event]
+pub struct AdminProposed {
+    pub settings: Pubkey,
+    pub admin: Pubkey,
+    /// `None` when a previous proposal is withdrawn.
+    pub pending_admin: Option<Pubkey>,
+}
+
+#[event]
+pub struct PauseUpdated {
+    pub settings: Pubkey,
+    pub old_paused: u8,
+    pub new_paused: u8,
+}
+
+#[event]
+pub struct AdminAccepted {
+    pub settings: Pubkey,
+    pub old_admin: Pubkey,
+    pub new_admin: Pubkey,
+}
+
+/// Every configurable value in a `MintConfig`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct MintConfigValues {
+    pub enabled: bool,
+    pub min_amount: u64,
+    pub max_amount: u64,
+    pub player_fee_lamports: Option<u64>,
+}
+
+impl From<&MintConfig> for MintConfigValues {
+    fn from(mint_config: &MintConfig) -> Self {
+        Self {
+            enabled: mint_config.enabled,
+            min_amount: mint_config.min_amount,
+            max_amount: mint_config.max_amount,
+            player_fee_lamports: mint_config.player_fee_lamports,
+        }
+    }
+}
+
+#[event]
+pub struct MintConfigInitialized {
+    pub mint_config: Pubkey,
+    pub mint: Pubkey,
+    pub values: MintConfigValues,
+}
+
+#[event]
+pub struct MintConfigUpdated {
+    pub mint_config: Pubkey,
+    pub mint: Pubkey,
+    pub old_values: MintConfigValues,
+    pub new_values: MintConfigValues,
+}
+
+#[event]
+pub struct GameCreated {
+    pub game: Pubkey,
+    pub game_id: String,
+    pub first_player: Pubkey,
+    pub mint: Pubkey,
+    pub amount_to_match: u64,
+    pub player_fee_lamports: u64,
+    pub best_of: u8,
+    pub variant: GameVariant,
+    pub join_deadline: Option<i64>,
+    pub created_at: i64,
+    pub time_for_penalization: i64,
+    pub time_for_stale: i64,
+    pub join_restriction: Option<JoinRestriction>,
+    pub lobby: Option<Pubkey>,
+    pub rating_band: Option<RatingBand>,
+    pub season: Option<Pubkey>,
+}
+
+#[event]
+pub struct GameJoined {
+    pub game: Pubkey,
+    pub first_player: Pubkey,
+    pub second_player: Pubkey,
+    pub mint: Pubkey,
+    pub amount_to_match: u64,
+    pub player_fee_lamports: u64,
+    pub started_at: i64,
+}
+
+#[event]
+pub struct ChoiceCommitted {
+    pub game: Pubkey,
+    pub player: Pubkey,
+    /// Zero based index of the round committed to.
+    pub round: u8,
+    pub committed_at: i64,
+}
+
+#[event]
+pub struct ChoiceRevealed {
+    pub game: Pubkey,
+    pub player: Pubkey,
+    /// Zero based index of the round revealed.
+    pub round: u8,
+    pub choice: Choice,
+    pub revealed_at: i64,
+}
+
+#[event]
+pub struct RoundResolved {
+    pub game: Pubkey,
+    /// Zero based index of the round resolved.
+    pub round: u8,
+    pub result: Round,
+    pub first_player_wins: u8,
+    pub second_player_wins: u8,
+}
+
+#[event]
+pub struct GameSettled {
+    pub game: Pubkey,
+    pub first_player: Pubkey,
+    pub second_player: Pubkey,
+    pub mint: Pubkey,
+    pub amount_to_match: u64,
+    /// `None` on a draw.
+    pub winner: Option<Player>,
+    pub amount_won: Option<u64>,
+    /// Rake taken across both escrows.
+    pub rake: u64,
+    pub settled_at: i64,
+}
+
+#[event]
+pub struct GameCancelled {
+    pub game: Pubkey,
+    pub first_player: Pubkey,
+    pub mint: Pubkey,
+    pub amount_to_match: u64,
+    pub cancelled_at: i64,
+}
+
+#[event]
+pub struct GameExpired {
+    pub game: Pubkey,
+    pub first_player: Pubkey,
+    pub mint: Pubkey,
+    pub amount_to_match: u64,
+    pub expired_at: i64,
+}
+
+#[event]
+pub struct GameUnwound {
+    pub game: Pubkey,
+    pub first_player: Pubkey,
+    pub second_player: Pubkey,
+    pub mint: Pubkey,
+    pub amount_to_match: u64,
+    pub unwound_at: i64,
+}
+
+/// Ratings of both players before and after a settled game.
+#[event]
+pub struct RatingsUpdated {
+    pub game: Pubkey,
+    pub first_player: Pubkey,
+    pub first_player_old_rating: u32,
+    pub first_player_new_rating: u32,
+    pub second_player: Pubkey,
+    pub second_player_old_rating: u32,
+    pub second_player_new_rating: u32,
+}
+
+/// Final record of a settled game, emitted right before its account is closed.
+#[event]
+pub struct GameClosed {
+    pub game: Pubkey,
+    pub game_id: String,
+    pub mint: Pubkey,
+    pub amount_to_match: u64,
+    pub first_player: Pubkey,
+    pub second_player: Option<Pubkey>,
+    pub state: GameState,
+    pub amount_won: Option<u64>,
+    pub rounds: Vec<Round>,
+    pub created_at: i64,
+    pub settled_at: Option<i64>,
+    pub closed_at: i64,
+}
+
+#[event]
+pub struct GameMigrated {
+    pub game: Pubkey,
+    pub previous_size: u32,
+    pub size: u32,
+}
+
+#[event]
+pub struct TournamentCreated {
+    pub tournament: Pubkey,
+    pub tournament_id: String,
+    pub organizer: Pubkey,
+    pub entry_fee: u64,
+    pub max_players: u8,
+    pub best_of: u8,
+    pub variant: GameVariant,
+    pub time_for_penalization: i64,
+    pub time_for_stale: i64,
+    pub prize_split_bps: Vec<u16>,
+    pub created_at: i64,
+}
+
+#[event]
+pub struct TournamentPlayerRegistered {
+    pub tournament: Pubkey,
+    pub player: Pubkey,
+    pub entry_fee: u64,
+    pub players: u8,
+}
+
+#[event]
+pub struct TournamentPlayerLeft {
+    pub tournament: Pubkey,
+    pub player: Pubkey,
+    pub refund: u64,
+    pub players: u8,
+}
+
+/// Emitted when the bracket is seeded and whenever a round starts.
+#[event]
+pub struct TournamentRoundStarted {
+    pub tournament: Pubkey,
+    /// Zero based.
+    pub round: u8,
+    pub matches: Vec<TournamentMatch>,
+    pub started_at: i64,
+}
+
+#[event]
+pub struct TournamentGameCreated {
+    pub tournament: Pubkey,
+    pub round: u8,
+    pub match_index: u8,
+    pub game: Pubkey,
+}
+
+/// A match game ended without a winner, the players replay the match.
+#[event]
+pub struct TournamentMatchReplayed {
+    pub tournament: Pubkey,
+    pub round: u8,
+    pub match_index: u8,
+    pub game: Pubkey,
+}
+
+#[event]
+pub struct TournamentMatchDecided {
+    pub tournament: Pubkey,
+    pub round: u8,
+    pub match_index: u8,
+    pub winner: Pubkey,
+    pub loser: Pubkey,
+    /// `None` for a walkover.
+    pub game: Option<Pubkey>,
+}
+
+#[event]
+pub struct TournamentFinished {
+    pub tournament: Pubkey,
+    pub champion: Pubkey,
+    pub prize_pool: u64,
+    pub finished_at: i64,
+}
+
+#[event]
+pub struct TournamentPrizeClaimed {
+    pub tournament: Pubkey,
+    pub player: Pubkey,
+    /// Zero based, the champion's is 0.
+    pub place: u8,
+    pub amount: u64,
+}
+
+#[event]
+pub struct TournamentClosed {
+    pub tournament: Pubkey,
+    pub organizer: Pubkey,
+    pub state: TournamentState,
+    pub champion: Option<Pubkey>,
+    /// Prizes nobody claimed in time, returned to the organizer.
+    pub unclaimed_prizes: u64,
+}
+
+#[event]
+pub struct ReferralShareUpdated {
+    pub settings: Pubkey,
+    pub old_referral_share_bps: u16,
+    pub new_referral_share_bps: u16,
+}
+
+#[event]
+pub struct ReferrerRegistered {
+    pub player: Pubkey,
+    pub referrer: Pubkey,
+}
+
+/// A referrer's share of a fee or rake paid by a player they referred.
+#[event]
+pub struct ReferralRewarded {
+    pub referrer: Pubkey,
+    pub player: Pubkey,
+    pub game: Pubkey,
+    /// `Pubkey::default()` for SOL.
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ReferralRewardsClaimed {
+    pub referrer: Pubkey,
+    /// `Pubkey::default()` for SOL.
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct SidePoolOpened {
+    pub side_pool: Pubkey,
+    pub game: Pubkey,
+    pub creator: Pubkey,
+}
+
+#[event]
+pub struct SideBetPlaced {
+    pub side_pool: Pubkey,
+    pub game: Pubkey,
+    pub bettor: Pubkey,
+    pub outcome: SideBetOutcome,
+    pub amount: u64,
+}
+
+/// Emitted for every claim, losing bets included with a zero payout.
+#[event]
+pub struct SideBetClaimed {
+    pub side_pool: Pubkey,
+    pub bettor: Pubkey,
+    pub outcome: SideBetOutcome,
+    pub amount: u64,
+    pub payout: u64,
+}
+
+#[event]
+pub struct SidePoolResolved {
+    pub side_pool: Pubkey,
+    pub game: Pubkey,
+    pub result: SidePoolResult,
+    pub total: u64,
+}
+
+#[event]
+pub struct SidePoolClosed {
+    pub side_pool: Pubkey,
+    pub game: Pubkey,
+    pub creator: Pubkey,
+}
+
+#[event]
+pub struct SeasonCreated {
+    pub season: Pubkey,
+    pub season_id: u32,
+    pub start_at: i64,
+    pub end_at: i64,
+    pub ranking: SeasonRanking,
+    pub fee_share_bps: u16,
+    pub prize_split_bps: Vec<u16>,
+    pub leaderboard_size: u8,
+}
+
+#[event]
+pub struct SeasonJoined {
+    pub season: Pubkey,
+    pub player: Pubkey,
+}
+
+/// Scores of both players after a season game settled, and the leaderboard
+/// they left behind.
+#[event]
+pub struct SeasonStandingsUpdated {
+    pub season: Pubkey,
+    pub game: Pubkey,
+    pub first_player: Pubkey,
+    pub first_player_score: i64,
+    pub second_player: Pubkey,
+    pub second_player_score: i64,
+    pub leaderboard: Vec<LeaderboardEntry>,
+}
+
+/// An entry missing from the leaderboard ranked at its current score.
+#[event]
+pub struct SeasonEntryRanked {
+    pub season: Pubkey,
+    pub player: Pubkey,
+    pub score: i64,
+    pub leaderboard: Vec<LeaderboardEntry>,
+}
+
+#[event]
+pub struct SeasonPrizeClaimed {
+    pub season: Pubkey,
+    pub player: Pubkey,
+    /// Zero based leaderboard place.
+    pub place: u8,
+    pub amount: u64,
+}
+
+/// Prizes nobody claimed in time, places left empty included.
+#[event]
+pub struct SeasonPrizesSwept {
+    pub season: Pubkey,
+    pub treasury: Pubkey,
+    pub amount: u64,
+}
@@ -1,12 +1,12 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token_interface::{
-    close_account, CloseAccount, Mint, TokenAccount, TokenInterface,
-};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::{
-    error::RockPaperScissorsError, transfer_spl_compatible, Game, GameState, GAME, GAME_ESCROW,
+    close_escrow_spl_compatible, error::RockPaperScissorsError, transfer_spl_compatible, Game,
+    GameCancelled, GameState, Lobby, WagerMode, GAME, GAME_ESCROW,
 };
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct CancelGame<'info> {
     #[account(
@@ -15,6 +15,7 @@ pub struct CancelGame<'info> {
         seeds = [GAME.as_ref(), player.key().as_ref(), game.game_id.as_bytes()],
         bump = game.bump,
         constraint = game.state == GameState::Created @ RockPaperScissorsError::InvalidGameState,
+        constraint = game.wager_mode == WagerMode::Token @ RockPaperScissorsError::InvalidWagerMode,
     )]
     pub game: Account<'info, Game>,
     #[account(
@@ -39,6 +40,7 @@ pub struct CancelGame<'info> {
     )]
     pub player_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
+        mut,
         mint::token_program = token_program,
         address = game.mint
     )]
@@ -50,11 +52,17 @@ pub struct CancelGame<'info> {
         address = game.first_player @ RockPaperScissorsError::InvalidPlayer,
     )]
     pub player: Signer<'info>,
+
+    /// The lobby the game is listed in, if any.
+    #[account(mut)]
+    pub lobby: Option<Box<Account<'info, Lobby>>>,
 }
 
-pub fn processor(ctx: Context<CancelGame>) -> Result<()> {
+pub fn processor<'info>(ctx: Context<'_, '_, '_, 'info, CancelGame<'info>>) -> Result<()> {
+    let clock = Clock::get()?;
     let player = &ctx.accounts.player;
     let game = &ctx.accounts.game;
+    Lobby::unlist(ctx.accounts.lobby.as_deref_mut(), game.lobby, &game.key())?;
     let player_key = player.key();
 
     let game_seeds = &[
@@ -65,27 +73,34 @@ pub fn processor(ctx: Context<CancelGame>) -> Result<()> {
     ];
     let game_signer = &[&game_seeds[..]];
 
+    let balance = ctx.accounts.player_escrow_token_account.amount;
     transfer_spl_compatible(
         &ctx.accounts.token_program,
         &mut ctx.accounts.player_escrow_token_account,
         &mut ctx.accounts.player_token_account,
         &game.to_account_info(),
         &ctx.accounts.mint,
-        game.amount_to_match,
+        balance,
         Some(game_signer),
+        ctx.remaining_accounts,
     )?;
 
-    close_account(
-        CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            CloseAccount {
-                account: ctx.accounts.player_escrow_token_account.to_account_info(),
-                destination: ctx.accounts.player.to_account_info(),
-                authority: ctx.accounts.game.to_account_info(),
-            },
-        )
-        .with_signer(game_signer),
+    close_escrow_spl_compatible(
+        &ctx.accounts.token_program,
+        &ctx.accounts.player_escrow_token_account,
+        &ctx.accounts.mint,
+        &ctx.accounts.player.to_account_info(),
+        &game.to_account_info(),
+        game_signer,
     )?;
 
+    emit_cpi!(GameCancelled {
+        game: game.key(),
+        first_player: player_key,
+        mint: game.mint,
+        amount_to_match: game.amount_to_match,
+        cancelled_at: clock.unix_timestamp,
+    });
+
     Ok(())
 }
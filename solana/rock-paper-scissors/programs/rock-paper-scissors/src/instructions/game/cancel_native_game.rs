@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RockPaperScissorsError, Game, GameCancelled, GameState, Lobby, WagerMode, GAME,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelNativeGame<'info> {
+    #[account(
+        mut,
+        // Refunds the stake along with the rent.
+        close = player,
+        seeds = [GAME.as_ref(), player.key().as_ref(), game.game_id.as_bytes()],
+        bump = game.bump,
+        constraint = game.state == GameState::Created @ RockPaperScissorsError::InvalidGameState,
+        constraint = game.wager_mode == WagerMode::Native @ RockPaperScissorsError::InvalidWagerMode,
+    )]
+    pub game: Account<'info, Game>,
+    #[account(
+        mut,
+        address = game.first_player @ RockPaperScissorsError::InvalidPlayer,
+    )]
+    pub player: Signer<'info>,
+
+    /// The lobby the game is listed in, if any.
+    #[account(mut)]
+    pub lobby: Option<Box<Account<'info, Lobby>>>,
+}
+
+pub fn processor(ctx: Context<CancelNativeGame>) -> Result<()> {
+    let clock = Clock::get()?;
+    let game = &ctx.accounts.game;
+    Lobby::unlist(ctx.accounts.lobby.as_deref_mut(), game.lobby, &game.key())?;
+
+    emit_cpi!(GameCancelled {
+        game: game.key(),
+        first_player: game.first_player,
+        mint: game.mint,
+        amount_to_match: game.amount_to_match,
+        cancelled_at: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
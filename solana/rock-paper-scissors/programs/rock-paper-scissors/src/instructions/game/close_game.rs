@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::RockPaperScissorsError, Game, GameClosed, Settings, GAME, SETTINGS};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseGame<'info> {
+    #[account(
+        mut,
+        close = first_player,
+        seeds = [GAME.as_ref(), first_player.key().as_ref(), game.game_id.as_bytes()],
+        bump = game.bump,
+        constraint = game.is_settled() @ RockPaperScissorsError::InvalidGameState,
+    )]
+    pub game: Account<'info, Game>,
+    /// CHECK: No check needed.
+    #[account(
+        mut,
+        address = game.first_player
+    )]
+    pub first_player: AccountInfo<'info>,
+    #[account(
+        seeds = [SETTINGS.as_ref()],
+        bump = settings.bump,
+    )]
+    pub settings: Account<'info, Settings>,
+
+    /// Not checked, so this call is permissionless.
+    pub signer: Signer<'info>,
+}
+
+pub fn processor(ctx: Context<CloseGame>) -> Result<()> {
+    let clock = Clock::get()?;
+    let game = &ctx.accounts.game;
+    let settings = &ctx.accounts.settings;
+
+    require!(
+        game.is_closable(clock.unix_timestamp, settings.game_retention_period),
+        RockPaperScissorsError::GameRetentionPeriodNotElapsed
+    );
+
+    emit_cpi!(GameClosed {
+        game: game.key(),
+        game_id: game.game_id.clone(),
+        mint: game.mint,
+        amount_to_match: game.amount_to_match,
+        first_player: game.first_player,
+        second_player: game.second_player,
+        state: game.state.clone(),
+        amount_won: game.amount_won,
+        rounds: game.rounds.clone(),
+        created_at: game.created_at,
+        settled_at: game.drawn_at,
+        closed_at: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
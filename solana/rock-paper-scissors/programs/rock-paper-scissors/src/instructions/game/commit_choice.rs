@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::RockPaperScissorsError, ChoiceCommitted, Game, GameState, GAME};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CommitChoice<'info> {
+    #[account(
+        mut,
+        seeds = [GAME.as_ref(), game.first_player.as_ref(), game.game_id.as_bytes()],
+        bump = game.bump,
+        constraint = game.state == GameState::Started @ RockPaperScissorsError::InvalidGameState,
+    )]
+    pub game: Account<'info, Game>,
+    #[account(
+        constraint = game.is_player(&player.key()) @ RockPaperScissorsError::InvalidPlayer,
+    )]
+    pub player: Signer<'info>,
+}
+
+pub fn processor(
+    ctx: Context<CommitChoice>,
+    hash: [u8; 32], // Commitment, see `CommitmentScheme`
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let game = &mut ctx.accounts.game;
+
+    let player_key = ctx.accounts.player.key();
+    let player = game.to_player(&player_key)?;
+
+    game.commit_choice(player, hash, clock.unix_timestamp)?;
+
+    emit_cpi!(ChoiceCommitted {
+        game: game.key(),
+        player: player_key,
+        round: game.rounds.len() as u8,
+        committed_at: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
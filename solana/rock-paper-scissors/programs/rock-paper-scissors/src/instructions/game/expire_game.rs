@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    close_escrow_spl_compatible, error::RockPaperScissorsError, transfer_spl_compatible, Game,
+    GameExpired, GameState, Lobby, WagerMode, GAME, GAME_ESCROW,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExpireGame<'info> {
+    #[account(
+        mut,
+        close = first_player,
+        seeds = [GAME.as_ref(), first_player.key().as_ref(), game.game_id.as_bytes()],
+        bump = game.bump,
+        constraint = game.state == GameState::Created @ RockPaperScissorsError::InvalidGameState,
+        constraint = game.wager_mode == WagerMode::Token @ RockPaperScissorsError::InvalidWagerMode,
+    )]
+    pub game: Account<'info, Game>,
+    #[account(
+        mut,
+        // close = first_player, // Commented out since this is closed via CPI call
+        token::mint = mint,
+        token::authority = game,
+        token::token_program = token_program,
+        seeds = [
+            GAME_ESCROW.as_ref(),
+            game.key().as_ref(),
+            first_player.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub first_player_escrow_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = first_player,
+        token::token_program = token_program
+    )]
+    pub first_player_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: No check needed.
+    #[account(
+        mut,
+        address = game.first_player
+    )]
+    pub first_player: AccountInfo<'info>,
+    #[account(
+        mut,
+        mint::token_program = token_program,
+        address = game.mint
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Not checked, so this call is permissionless.
+    pub signer: Signer<'info>,
+
+    #[account(address = game.token_program)]
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The lobby the game is listed in, if any.
+    #[account(mut)]
+    pub lobby: Option<Box<Account<'info, Lobby>>>,
+}
+
+pub fn processor<'info>(ctx: Context<'_, '_, '_, 'info, ExpireGame<'info>>) -> Result<()> {
+    let clock = Clock::get()?;
+    let game = &ctx.accounts.game;
+    Lobby::unlist(ctx.accounts.lobby.as_deref_mut(), game.lobby, &game.key())?;
+    let first_player_key = ctx.accounts.first_player.key();
+
+    require!(
+        game.is_past_join_deadline(clock.unix_timestamp),
+        RockPaperScissorsError::JoinDeadlineNotReached
+    );
+
+    let game_seeds = &[
+        GAME.as_ref(),
+        first_player_key.as_ref(),
+        game.game_id.as_bytes(),
+        &[game.bump],
+    ];
+    let game_signer = &[&game_seeds[..]];
+
+    let balance = ctx.accounts.first_player_escrow_token_account.amount;
+    transfer_spl_compatible(
+        &ctx.accounts.token_program,
+        &mut ctx.accounts.first_player_escrow_token_account,
+        &mut ctx.accounts.first_player_token_account,
+        &game.to_account_info(),
+        &ctx.accounts.mint,
+        balance,
+        Some(game_signer),
+        ctx.remaining_accounts,
+    )?;
+
+    close_escrow_spl_compatible(
+        &ctx.accounts.token_program,
+        &ctx.accounts.first_player_escrow_token_account,
+        &ctx.accounts.mint,
+        &ctx.accounts.first_player,
+        &game.to_account_info(),
+        game_signer,
+    )?;
+
+    emit_cpi!(GameExpired {
+        game: game.key(),
+        first_player: first_player_key,
+        mint: game.mint,
+        amount_to_match: game.amount_to_match,
+        expired_at: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::RockPaperScissorsError, Game, GameExpired, GameState, Lobby, WagerMode, GAME};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExpireNativeGame<'info> {
+    #[account(
+        mut,
+        // Refunds the stake along with the rent.
+        close = first_player,
+        seeds = [GAME.as_ref(), first_player.key().as_ref(), game.game_id.as_bytes()],
+        bump = game.bump,
+        constraint = game.state == GameState::Created @ RockPaperScissorsError::InvalidGameState,
+        constraint = game.wager_mode == WagerMode::Native @ RockPaperScissorsError::InvalidWagerMode,
+    )]
+    pub game: Account<'info, Game>,
+    /// CHECK: No check needed.
+    #[account(
+        mut,
+        address = game.first_player
+    )]
+    pub first_player: AccountInfo<'info>,
+
+    /// Not checked, so this call is permissionless.
+    pub signer: Signer<'info>,
+
+    /// The lobby the game is listed in, if any.
+    #[account(mut)]
+    pub lobby: Option<Box<Account<'info, Lobby>>>,
+}
+
+pub fn processor(ctx: Context<ExpireNativeGame>) -> Result<()> {
+    let clock = Clock::get()?;
+    let game = &ctx.accounts.game;
+    Lobby::unlist(ctx.accounts.lobby.as_deref_mut(), game.lobby, &game.key())?;
+
+    require!(
+        game.is_past_join_deadline(clock.unix_timestamp),
+        RockPaperScissorsError::JoinDeadlineNotReached
+    );
+
+    emit_cpi!(GameExpired {
+        game: game.key(),
+        first_player: game.first_player,
+        mint: game.mint,
+        amount_to_match: game.amount_to_match,
+        expired_at: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
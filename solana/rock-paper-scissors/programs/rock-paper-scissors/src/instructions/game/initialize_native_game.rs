@@ -0,0 +1,265 @@
+use anchor_lang::{prelude::*, system_program};
+
+use crate::{
+    error::RockPaperScissorsError, transfer_lamports, transfer_player_fee, Game, GameCreated,
+    GameReferral, GameVariant, JoinRestriction, Lobby, MintConfig, Player, PlayerProfile,
+    PlayerRating, RatingBand, Referral, ReferralRewarded, ReferrerRewards, Season, SeasonEntry,
+    Settings, TransferLamports, WagerMode, GAME, LOBBY, MINT_CONFIG, PAUSE_NEW_GAMES,
+    PLAYER_PROFILE, PLAYER_RATING, REFERRAL, SETTINGS,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(game_id: String, amount: u64, hash: [u8; 32], best_of: u8)]
+pub struct InitializeNativeGame<'info> {
+    #[account(
+        init,
+        payer = player,
+        space = Game::size(best_of),
+        seeds = [GAME.as_ref(), player.key().as_ref(), game_id.as_bytes()],
+        bump,
+    )]
+    pub game: Box<Account<'info, Game>>,
+
+    /// CHECK: No check needed.
+    #[account(
+        mut,
+        address = settings.treasury
+    )]
+    pub treasury: AccountInfo<'info>,
+    #[account(
+        seeds = [SETTINGS.as_ref()],
+        bump = settings.bump,
+        constraint = !settings.is_paused(PAUSE_NEW_GAMES) @ RockPaperScissorsError::NewGamesPaused,
+    )]
+    pub settings: Box<Account<'info, Settings>>,
+    #[account(
+        seeds = [MINT_CONFIG.as_ref(), settings.key().as_ref(), Pubkey::default().as_ref()],
+        bump = mint_config.bump,
+        constraint = mint_config.enabled @ RockPaperScissorsError::MintNotEnabled,
+    )]
+    pub mint_config: Box<Account<'info, MintConfig>>,
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = Lobby::size(),
+        seeds = [LOBBY.as_ref(), Pubkey::default().as_ref(), amount.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub lobby: Box<Account<'info, Lobby>>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = PlayerProfile::size(),
+        seeds = [PLAYER_PROFILE.as_ref(), player.key().as_ref()],
+        bump,
+    )]
+    pub player_profile: Box<Account<'info, PlayerProfile>>,
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = PlayerRating::size(),
+        seeds = [PLAYER_RATING.as_ref(), player.key().as_ref()],
+        bump,
+    )]
+    pub player_rating: Box<Account<'info, PlayerRating>>,
+    /// CHECK: Read for the player's referrer, might not exist, in which case
+    /// the whole fee goes to the treasury.
+    #[account(
+        seeds = [REFERRAL.as_ref(), player.key().as_ref()],
+        bump,
+    )]
+    pub referral: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Running season the game counts towards, if any.
+    #[account(mut)]
+    pub season: Option<Box<Account<'info, Season>>>,
+    /// The player's entry in `season`.
+    pub season_entry: Option<Box<Account<'info, SeasonEntry>>>,
+
+    /// Rewards of the player's referrer, required when `referral` exists.
+    #[account(mut)]
+    pub referrer_rewards: Option<Box<Account<'info, ReferrerRewards>>>,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn processor(
+    ctx: Context<InitializeNativeGame>,
+    game_id: String,
+    amount: u64,
+    hash: [u8; 32], // Commitment, see `CommitmentScheme`
+    best_of: u8,
+    variant: GameVariant,
+    join_deadline: Option<i64>,
+    time_for_penalization: Option<i64>, // Within the admin's bounds, default otherwise
+    time_for_stale: Option<i64>,
+    join_restriction: Option<JoinRestriction>, // `None` for open games
+    rating_band: Option<RatingBand>,           // `None` for games open to every rating
+) -> Result<()> {
+    require!(
+        Game::is_valid_best_of(best_of),
+        RockPaperScissorsError::InvalidBestOf
+    );
+
+    if let Some(rating_band) = &rating_band {
+        require!(
+            rating_band.is_valid(),
+            RockPaperScissorsError::InvalidRatingBand
+        );
+    }
+
+    let clock = Clock::get()?;
+    if let Some(join_deadline) = join_deadline {
+        require!(
+            join_deadline > clock.unix_timestamp,
+            RockPaperScissorsError::InvalidJoinDeadline
+        );
+    }
+    let game = &mut ctx.accounts.game;
+    let settings = &ctx.accounts.settings;
+    let mint_config = &ctx.accounts.mint_config;
+    let player = &ctx.accounts.player;
+
+    mint_config.check_amount(amount)?;
+    let player_fee_lamports = mint_config.player_fee_lamports(settings);
+    let time_for_penalization = settings.pick_time_for_penalization(time_for_penalization)?;
+    let time_for_stale = settings.pick_time_for_stale(time_for_stale)?;
+    let season_fee_lamports = match ctx.accounts.season.as_deref() {
+        Some(season) => Season::fee_share_on_create(
+            season,
+            ctx.accounts.season_entry.as_deref(),
+            &player.key(),
+            &Pubkey::default(),
+            player_fee_lamports,
+            clock.unix_timestamp,
+        )?,
+        None => 0,
+    };
+    let referrer = Referral::referrer_of(&ctx.accounts.referral)?;
+    let mut referral_fee_lamports = 0;
+    let mut game_referral = None;
+    if let Some(rewards) =
+        ReferrerRewards::of_referrer(ctx.accounts.referrer_rewards.as_deref_mut(), referrer)?
+    {
+        referral_fee_lamports =
+            settings.referral_share(player_fee_lamports - season_fee_lamports)?;
+        rewards.record_earned(referral_fee_lamports);
+        game_referral = Some(GameReferral {
+            referrer: rewards.referrer,
+            vault: None,
+        });
+    }
+
+    transfer_lamports(
+        TransferLamports {
+            from: player.to_account_info(),
+            to: game.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        },
+        amount,
+    )?;
+    let mut fee_shares = vec![];
+    if let Some(season) = &ctx.accounts.season {
+        fee_shares.push((season.to_account_info(), season_fee_lamports));
+    }
+    if let Some(rewards) = &ctx.accounts.referrer_rewards {
+        fee_shares.push((rewards.to_account_info(), referral_fee_lamports));
+    }
+    transfer_player_fee(
+        &player.to_account_info(),
+        &ctx.accounts.treasury.to_account_info(),
+        &fee_shares,
+        &ctx.accounts.system_program.to_account_info(),
+        player_fee_lamports,
+    )?;
+
+    let game_key = game.key();
+    game.set_inner(Game::new(
+        ctx.bumps.game,
+        game_id,
+        Pubkey::default(),
+        system_program::ID,
+        amount,
+        player.key(),
+        hash,
+        game_key,
+        clock.unix_timestamp,
+        best_of,
+        variant,
+        join_deadline,
+        WagerMode::Native,
+        time_for_penalization,
+        time_for_stale,
+        player_fee_lamports,
+        settings.rake_terms(),
+    ));
+    game.join_restriction = join_restriction;
+    game.rating_band = rating_band;
+    game.season = ctx.accounts.season.as_ref().map(|season| season.key());
+    game.first_player_referral = game_referral;
+
+    let lobby = &mut ctx.accounts.lobby;
+    if !lobby.is_initialized() {
+        lobby.set_inner(Lobby::new(ctx.bumps.lobby, Pubkey::default(), amount));
+    }
+    // Invite-only games are found through their invite, not the lobby.
+    if game.join_restriction.is_none() {
+        lobby.push(game.key());
+        game.lobby = Some(lobby.key());
+    }
+    game.set_deposit(&Player::First, amount);
+
+    let player_profile = &mut ctx.accounts.player_profile;
+    if !player_profile.is_initialized() {
+        player_profile.set_inner(PlayerProfile::new(
+            ctx.bumps.player_profile,
+            ctx.accounts.player.key(),
+            clock.unix_timestamp,
+        ));
+    }
+    let player_rating = &mut ctx.accounts.player_rating;
+    if !player_rating.is_initialized() {
+        player_rating.set_inner(PlayerRating::new(
+            ctx.bumps.player_rating,
+            ctx.accounts.player.key(),
+            clock.unix_timestamp,
+        ));
+    }
+
+    emit_cpi!(GameCreated {
+        game: game_key,
+        game_id: game.game_id.clone(),
+        first_player: game.first_player,
+        mint: game.mint,
+        amount_to_match: game.amount_to_match,
+        player_fee_lamports,
+        best_of: game.best_of,
+        variant: game.variant.clone(),
+        join_deadline: game.join_deadline,
+        created_at: game.created_at,
+        time_for_penalization,
+        time_for_stale,
+        join_restriction: game.join_restriction.clone(),
+        lobby: game.lobby,
+        rating_band: game.rating_band.clone(),
+        season: game.season,
+    });
+
+    if let Some(referrer) = referrer.filter(|_| referral_fee_lamports > 0) {
+        emit_cpi!(ReferralRewarded {
+            referrer,
+            player: player.key(),
+            game: game.key(),
+            mint: Pubkey::default(),
+            amount: referral_fee_lamports,
+        });
+    }
+
+    Ok(())
+}
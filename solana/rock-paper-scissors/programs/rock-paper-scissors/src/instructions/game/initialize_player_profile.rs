@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use crate::{PlayerProfile, PLAYER_PROFILE};
+
+#[derive(Accounts)]
+pub struct InitializePlayerProfile<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = PlayerProfile::size(),
+        seeds = [PLAYER_PROFILE.as_ref(), player.key().as_ref()],
+        bump,
+    )]
+    pub player_profile: Box<Account<'info, PlayerProfile>>,
+    /// CHECK: Any wallet, the profile only records its games.
+    pub player: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Profiles are created with a player's first game, this covers players of
+/// games created before profiles existed, whose results are only recorded
+/// once they have one.
+pub fn processor(ctx: Context<InitializePlayerProfile>) -> Result<()> {
+    let clock = Clock::get()?;
+    ctx.accounts.player_profile.set_inner(PlayerProfile::new(
+        ctx.bumps.player_profile,
+        ctx.accounts.player.key(),
+        clock.unix_timestamp,
+    ));
+
+    Ok(())
+}
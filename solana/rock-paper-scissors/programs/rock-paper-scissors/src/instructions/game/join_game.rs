@@ -2,17 +2,21 @@ use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::{
-    error::RockPaperScissorsError, transfer_lamports, transfer_spl_compatible, Game, GameState,
-    Settings, TransferLamports, GAME, GAME_ESCROW, SETTINGS,
+    error::RockPaperScissorsError, transfer_player_fee, transfer_spl_compatible, Game, GameJoined,
+    GameReferral, GameState, Lobby, MintConfig, Player, PlayerProfile, PlayerRating, Referral,
+    ReferralRewarded, ReferrerRewards, Season, SeasonEntry, Settings, WagerMode, GAME, GAME_ESCROW,
+    MINT_CONFIG, PAUSE_JOINS, PLAYER_PROFILE, PLAYER_RATING, REFERRAL, SETTINGS,
 };
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct JoinGame<'info> {
     #[account(
         mut,
         seeds = [GAME.as_ref(), game.first_player.key().as_ref(), game.game_id.as_bytes()],
         bump = game.bump,
-        constraint = game.state == GameState::Created @ RockPaperScissorsError::InvalidGameState
+        constraint = game.state == GameState::Created @ RockPaperScissorsError::InvalidGameState,
+        constraint = game.wager_mode == WagerMode::Token @ RockPaperScissorsError::InvalidWagerMode,
     )]
     pub game: Account<'info, Game>,
     #[account(
@@ -51,8 +55,38 @@ pub struct JoinGame<'info> {
     #[account(
         seeds = [SETTINGS.as_ref()],
         bump = settings.bump,
+        constraint = !settings.is_paused(PAUSE_JOINS) @ RockPaperScissorsError::JoinsPaused,
     )]
     pub settings: Account<'info, Settings>,
+    #[account(
+        seeds = [MINT_CONFIG.as_ref(), settings.key().as_ref(), mint.key().as_ref()],
+        bump = mint_config.bump,
+    )]
+    pub mint_config: Box<Account<'info, MintConfig>>,
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = PlayerProfile::size(),
+        seeds = [PLAYER_PROFILE.as_ref(), player.key().as_ref()],
+        bump,
+    )]
+    pub player_profile: Box<Account<'info, PlayerProfile>>,
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = PlayerRating::size(),
+        seeds = [PLAYER_RATING.as_ref(), player.key().as_ref()],
+        bump,
+    )]
+    pub player_rating: Box<Account<'info, PlayerRating>>,
+    /// CHECK: Read for the player's referrer, might not exist, in which case
+    /// the whole fee goes to the treasury.
+    #[account(
+        seeds = [REFERRAL.as_ref(), player.key().as_ref()],
+        bump,
+    )]
+    pub referral: UncheckedAccount<'info>,
+
     #[account(
         mut,
         constraint = player.key() != game.first_player @ RockPaperScissorsError::BothPlayersCantBeTheSame
@@ -61,14 +95,35 @@ pub struct JoinGame<'info> {
     #[account(address = game.token_program)]
     pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
+
+    /// The lobby the game is listed in, if any.
+    #[account(mut)]
+    pub lobby: Option<Box<Account<'info, Lobby>>>,
+
+    /// The season the game counts towards, if any.
+    #[account(mut)]
+    pub season: Option<Box<Account<'info, Season>>>,
+    /// The player's entry in `season`.
+    pub season_entry: Option<Box<Account<'info, SeasonEntry>>>,
+
+    /// Rewards of the player's referrer, required when `referral` exists.
+    #[account(mut)]
+    pub referrer_rewards: Option<Box<Account<'info, ReferrerRewards>>>,
+    /// Token account of `referrer_rewards` in the mint, paid its share of
+    /// the player's rake, required along with it.
+    pub referrer_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 }
 
-pub fn processor(
-    ctx: Context<JoinGame>,
-    hash: [u8; 32], // Choice + Salt
+pub fn processor<'info>(
+    ctx: Context<'_, '_, '_, 'info, JoinGame<'info>>,
+    hash: [u8; 32], // Commitment, see `CommitmentScheme`
+    proof: Vec<[u8; 32]>,
 ) -> Result<()> {
+    let clock = Clock::get()?;
     let game = &mut ctx.accounts.game;
+    Lobby::unlist(ctx.accounts.lobby.as_deref_mut(), game.lobby, &game.key())?;
     let settings = &ctx.accounts.settings;
+    let player_fee_lamports = game.player_fee_lamports(&ctx.accounts.mint_config, settings);
     let player = &ctx.accounts.player;
     let player_token_account = &mut ctx.accounts.player_token_account;
     let player_escrow_token_account = &mut ctx.accounts.player_escrow_token_account;
@@ -77,6 +132,37 @@ pub fn processor(
 
     let _hash = anchor_lang::solana_program::hash::Hash::new_from_array(hash);
 
+    require!(
+        !game.is_past_join_deadline(clock.unix_timestamp),
+        RockPaperScissorsError::JoinDeadlinePassed
+    );
+    let season_fee_lamports = Season::fee_share_on_join(
+        ctx.accounts.season.as_deref(),
+        ctx.accounts.season_entry.as_deref(),
+        game.season,
+        &player.key(),
+        player_fee_lamports,
+        clock.unix_timestamp,
+    )?;
+    let referrer = Referral::referrer_of(&ctx.accounts.referral)?;
+    let mut referral_fee_lamports = 0;
+    let mut game_referral = None;
+    if let Some(rewards) =
+        ReferrerRewards::of_referrer(ctx.accounts.referrer_rewards.as_deref_mut(), referrer)?
+    {
+        referral_fee_lamports =
+            settings.referral_share(player_fee_lamports - season_fee_lamports)?;
+        rewards.record_earned(referral_fee_lamports);
+        game_referral = Some(GameReferral {
+            referrer: rewards.referrer,
+            vault: Some(GameReferral::check_vault(
+                ctx.accounts.referrer_vault.as_deref(),
+                &rewards.key(),
+                &mint.key(),
+            )?),
+        });
+    }
+
     transfer_spl_compatible(
         token_program,
         player_token_account,
@@ -85,17 +171,76 @@ pub fn processor(
         mint,
         game.amount_to_match,
         None,
+        ctx.remaining_accounts,
+    )?;
+    let mut fee_shares = vec![];
+    if let Some(season) = &ctx.accounts.season {
+        fee_shares.push((season.to_account_info(), season_fee_lamports));
+    }
+    if let Some(rewards) = &ctx.accounts.referrer_rewards {
+        fee_shares.push((rewards.to_account_info(), referral_fee_lamports));
+    }
+    transfer_player_fee(
+        &player.to_account_info(),
+        &ctx.accounts.treasury.to_account_info(),
+        &fee_shares,
+        &ctx.accounts.system_program.to_account_info(),
+        player_fee_lamports,
     )?;
-    transfer_lamports(
-        TransferLamports {
-            from: player.to_account_info(),
-            to: ctx.accounts.treasury.to_account_info(),
-            system_program: ctx.accounts.system_program.to_account_info(),
-        },
-        settings.player_fee_lamports,
+
+    let player_rating = &mut ctx.accounts.player_rating;
+    if !player_rating.is_initialized() {
+        player_rating.set_inner(PlayerRating::new(
+            ctx.bumps.player_rating,
+            ctx.accounts.player.key(),
+            clock.unix_timestamp,
+        ));
+    }
+    if let Some(rating_band) = &game.rating_band {
+        require!(
+            rating_band.contains(player_rating.rating),
+            RockPaperScissorsError::RatingOutOfBand
+        );
+    }
+    game.join_game(
+        player.key(),
+        hash,
+        player_escrow_token_account.key(),
+        clock.unix_timestamp,
+        &proof,
     )?;
+    player_escrow_token_account.reload()?;
+    game.set_deposit(&Player::Second, player_escrow_token_account.amount);
+    game.second_player_referral = game_referral;
+
+    let player_profile = &mut ctx.accounts.player_profile;
+    if !player_profile.is_initialized() {
+        player_profile.set_inner(PlayerProfile::new(
+            ctx.bumps.player_profile,
+            ctx.accounts.player.key(),
+            clock.unix_timestamp,
+        ));
+    }
+
+    emit_cpi!(GameJoined {
+        game: game.key(),
+        first_player: game.first_player,
+        second_player: player.key(),
+        mint: game.mint,
+        amount_to_match: game.amount_to_match,
+        player_fee_lamports,
+        started_at: clock.unix_timestamp,
+    });
 
-    game.join_game(player.key(), hash, player_escrow_token_account.key());
+    if let Some(referrer) = referrer.filter(|_| referral_fee_lamports > 0) {
+        emit_cpi!(ReferralRewarded {
+            referrer,
+            player: player.key(),
+            game: game.key(),
+            mint: Pubkey::default(),
+            amount: referral_fee_lamports,
+        });
+    }
 
     Ok(())
 }
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RockPaperScissorsError, transfer_lamports, Game, GameMigrated, TransferLamports,
+    MAX_BEST_OF,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MigrateGame<'info> {
+    /// CHECK: Deserialized by hand, since games created before the latest
+    /// fields were appended are too short for `Account<Game>`. Only this
+    /// program can write the `Game` discriminator checked on decoding.
+    #[account(
+        mut,
+        owner = crate::ID,
+    )]
+    pub game: UncheckedAccount<'info>,
+    /// Anyone, usually whoever wants to settle, unwind or cancel the game,
+    /// pays for the extra rent.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn processor(ctx: Context<MigrateGame>) -> Result<()> {
+    let game = &ctx.accounts.game;
+    let signer = &ctx.accounts.signer;
+    let previous_size = game.data_len();
+
+    // `best_of` sizes the rounds, but is only known once decoded, so grow to
+    // fit any match first. Appended fields are zeroed, which decodes to their
+    // defaults.
+    game.realloc(Game::size(MAX_BEST_OF).max(previous_size), true)?;
+    let mut migrated = Game::try_deserialize(&mut &game.try_borrow_data()?[..])?;
+    let size = Game::size(migrated.best_of);
+    require!(
+        previous_size < size,
+        RockPaperScissorsError::GameAlreadyMigrated
+    );
+    game.realloc(size, false)?;
+    migrated.resolve_legacy_round();
+    migrated.try_serialize(&mut &mut game.try_borrow_mut_data()?[..])?;
+
+    // Native games hold the wagers on top of their rent, so only the rent of
+    // the added bytes is topped up.
+    let rent = Rent::get()?;
+    let rent = rent
+        .minimum_balance(size)
+        .saturating_sub(rent.minimum_balance(previous_size));
+    if rent > 0 {
+        transfer_lamports(
+            TransferLamports {
+                from: signer.to_account_info(),
+                to: game.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            },
+            rent,
+        )?;
+    }
+
+    emit_cpi!(GameMigrated {
+        game: game.key(),
+        previous_size: previous_size as u32,
+        size: size as u32,
+    });
+
+    Ok(())
+}
@@ -1,13 +1,35 @@
 pub mod cancel_game;
+pub mod cancel_native_game;
+pub mod close_game;
+pub mod commit_choice;
+pub mod expire_game;
+pub mod expire_native_game;
 pub mod initialize_game;
+pub mod initialize_native_game;
+pub mod initialize_player_profile;
 pub mod join_game;
+pub mod join_native_game;
+pub mod migrate_game;
 pub mod reveal_choice;
 pub mod settle_game;
+pub mod settle_native_game;
 pub mod unwind_game;
+pub mod unwind_native_game;
 
 pub use cancel_game::*;
+pub use cancel_native_game::*;
+pub use close_game::*;
+pub use commit_choice::*;
+pub use expire_game::*;
+pub use expire_native_game::*;
 pub use initialize_game::*;
+pub use initialize_native_game::*;
+pub use initialize_player_profile::*;
 pub use join_game::*;
+pub use join_native_game::*;
+pub use migrate_game::*;
 pub use reveal_choice::*;
 pub use settle_game::*;
+pub use settle_native_game::*;
 pub use unwind_game::*;
+pub use unwind_native_game::*;
@@ -1,12 +1,11 @@
-use anchor_lang::{
-    prelude::*,
-    solana_program::hash::{hashv, Hash},
-};
+use anchor_lang::{prelude::*, solana_program::hash::Hash};
 
 use crate::{
-    error::RockPaperScissorsError, vec_to_arr_of_n, Choice, Game, GameState, Player, GAME,
+    error::RockPaperScissorsError, Choice, ChoiceRevealed, Game, GameState, Player, RoundResolved,
+    GAME,
 };
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct RevealChoice<'info> {
     #[account(
@@ -42,17 +41,26 @@ fn require_player_did_not_reveal(player: &Player, game: &Game) -> Result<()> {
 
 pub fn processor(ctx: Context<RevealChoice>, choice: Choice, salt: [u8; 32]) -> Result<()> {
     let clock = Clock::get()?;
+    let game_key = ctx.accounts.game.key();
     let game = &mut ctx.accounts.game;
 
-    let choice_bytes = choice.clone() as u8;
     let player_key = ctx.accounts.player.key();
 
     let player = game.to_player(&player_key)?;
 
+    require!(
+        game.is_round_committed(),
+        RockPaperScissorsError::AwaitingCommitments
+    );
     require_player_did_not_reveal(&player, game)?;
+    require!(
+        game.variant.rules().allows(&choice),
+        RockPaperScissorsError::InvalidChoice
+    );
 
-    let val_to_hash = vec_to_arr_of_n::<u8, 33>([&[choice_bytes], &salt[..]].concat());
-    let created_hash = hashv(&[&val_to_hash]);
+    let created_hash = game
+        .commitment_scheme
+        .hash(&game_key, &player_key, &choice, &salt);
     let stored_hash = Hash::new_from_array(match player {
         Player::First => game.first_player_hash,
         Player::Second => game.second_player_hash.unwrap(),
@@ -64,7 +72,27 @@ pub fn processor(ctx: Context<RevealChoice>, choice: Choice, salt: [u8; 32]) ->
         RockPaperScissorsError::InvalidHash
     );
 
+    let round = game.rounds.len() as u8;
+    emit_cpi!(ChoiceRevealed {
+        game: game_key,
+        player: player_key,
+        round,
+        choice: choice.clone(),
+        revealed_at: clock.unix_timestamp,
+    });
+
     game.set_player_choice(player, choice, clock.unix_timestamp);
+    game.resolve_round();
+
+    if let Some(result) = game.rounds.get(round as usize) {
+        emit_cpi!(RoundResolved {
+            game: game_key,
+            round,
+            result: result.clone(),
+            first_player_wins: game.first_player_wins,
+            second_player_wins: game.second_player_wins,
+        });
+    }
 
     Ok(())
 }
@@ -1,13 +1,15 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token_interface::{
-    close_account, CloseAccount, Mint, TokenAccount, TokenInterface,
-};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::{
-    error::RockPaperScissorsError, transfer_spl_compatible, Game, GameState, Player, Settings,
-    GAME, GAME_ESCROW, SETTINGS,
+    close_escrow_spl_compatible, error::RockPaperScissorsError, transfer_spl_compatible,
+    EscrowPayout, Game, GameReferral, GameSettled, GameState, Player, PlayerProfile, PlayerRating,
+    RatingsUpdated, ReferralRewarded, Season, SeasonEntry, SeasonStandingsUpdated, Settings,
+    Settlement, SideBetOutcome, SidePool, SidePoolResolved, SidePoolResult, WagerMode, GAME,
+    GAME_ESCROW, PLAYER_PROFILE, PLAYER_RATING, SETTINGS,
 };
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct SettleGame<'info> {
     #[account(
@@ -19,6 +21,7 @@ pub struct SettleGame<'info> {
         ],
         bump = game.bump,
         constraint = game.state == GameState::Started @ RockPaperScissorsError::InvalidGameState,
+        constraint = game.wager_mode == WagerMode::Token @ RockPaperScissorsError::InvalidWagerMode,
     )]
     pub game: Box<Account<'info, Game>>,
     #[account(
@@ -84,18 +87,80 @@ pub struct SettleGame<'info> {
     )]
     pub second_player: AccountInfo<'info>,
     #[account(
+        mut,
         mint::token_program = game.token_program,
         address = game.mint
     )]
     pub mint: Box<InterfaceAccount<'info, Mint>>,
 
+    /// Receives the rake. Anyone can create the treasury's associated token
+    /// account for the mint ahead of settlement.
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = settings.treasury,
+        token::token_program = token_program
+    )]
+    pub treasury_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Rated along with the second player's if both exist, players
+    /// of games created before ratings might not have one.
+    #[account(
+        mut,
+        seeds = [PLAYER_RATING.as_ref(), first_player.key().as_ref()],
+        bump,
+    )]
+    pub first_player_rating: UncheckedAccount<'info>,
+    /// CHECK: As above.
+    #[account(
+        mut,
+        seeds = [PLAYER_RATING.as_ref(), second_player.key().as_ref()],
+        bump,
+    )]
+    pub second_player_rating: UncheckedAccount<'info>,
+
+    /// CHECK: Recorded if it exists, players of games created before
+    /// profiles might not have one.
+    #[account(
+        mut,
+        seeds = [PLAYER_PROFILE.as_ref(), first_player.key().as_ref()],
+        bump,
+    )]
+    pub first_player_profile: UncheckedAccount<'info>,
+    /// CHECK: Recorded if it exists, as above.
+    #[account(
+        mut,
+        seeds = [PLAYER_PROFILE.as_ref(), second_player.key().as_ref()],
+        bump,
+    )]
+    pub second_player_profile: UncheckedAccount<'info>,
+
     pub signer: Signer<'info>,
 
     #[account(address = game.token_program)]
     pub token_program: Interface<'info, TokenInterface>,
+
+    /// The season the game counts towards, if any.
+    #[account(mut)]
+    pub season: Option<Box<Account<'info, Season>>>,
+    #[account(mut)]
+    pub first_player_season_entry: Option<Box<Account<'info, SeasonEntry>>>,
+    #[account(mut)]
+    pub second_player_season_entry: Option<Box<Account<'info, SeasonEntry>>>,
+
+    /// Vaults of the referrers snapshotted by the game, paid their share of
+    /// the rake of their player's escrow.
+    #[account(mut)]
+    pub first_player_referrer_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(mut)]
+    pub second_player_referrer_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// The game's `SidePool`, required once one is open.
+    #[account(mut)]
+    pub side_pool: Option<Box<Account<'info, SidePool>>>,
 }
 
-pub fn processor(ctx: Context<SettleGame>) -> Result<()> {
+pub fn processor<'info>(ctx: Context<'_, '_, '_, 'info, SettleGame<'info>>) -> Result<()> {
     let clock = Clock::get()?;
     let game = &mut ctx.accounts.game;
     let settings = &ctx.accounts.settings;
@@ -106,10 +171,57 @@ pub fn processor(ctx: Context<SettleGame>) -> Result<()> {
     let first_player_token_account = &mut ctx.accounts.first_player_token_account;
     let second_player_escrow_token_account = &mut ctx.accounts.second_player_escrow_token_account;
     let second_player_token_account = &mut ctx.accounts.second_player_token_account;
+    let treasury_token_account = &mut ctx.accounts.treasury_token_account;
+    let first_player_referrer_vault = GameReferral::vault_at_settlement(
+        game.first_player_referral.as_ref(),
+        ctx.accounts.first_player_referrer_vault.as_mut(),
+    )?;
+    let second_player_referrer_vault = GameReferral::vault_at_settlement(
+        game.second_player_referral.as_ref(),
+        ctx.accounts.second_player_referrer_vault.as_mut(),
+    )?;
 
     let first_player_key = ctx.accounts.first_player.key();
     let now = clock.unix_timestamp;
 
+    let forfeiter = game.forfeiter(now, settings);
+    let settlement = game.settle(
+        now,
+        settings,
+        first_player_escrow_token_account.amount,
+        second_player_escrow_token_account.amount,
+    )?;
+    let rake = settlement.rake()?;
+    let rake_terms = game.rake_terms(settings);
+    PlayerProfile::record_settlement(
+        &ctx.accounts.first_player_profile,
+        &ctx.accounts.second_player_profile,
+        game,
+        &settlement,
+        &forfeiter,
+        now,
+    )?;
+    let season_scores = Season::record_game(
+        ctx.accounts.season.as_deref_mut(),
+        ctx.accounts.first_player_season_entry.as_deref_mut(),
+        ctx.accounts.second_player_season_entry.as_deref_mut(),
+        game,
+        &settlement,
+        now,
+    )?;
+    let ratings = PlayerRating::record_settlement(
+        &ctx.accounts.first_player_rating,
+        &ctx.accounts.second_player_rating,
+        &settlement.winner,
+        now,
+    )?;
+    let side_pool_result = SidePoolResult::Settled(SideBetOutcome::from_winner(&settlement.winner));
+    let Settlement {
+        winner,
+        first_player: first_player_payout,
+        second_player: second_player_payout,
+    } = settlement;
+
     let game_seeds = &[
         GAME.as_ref(),
         first_player_key.as_ref(),
@@ -118,106 +230,254 @@ pub fn processor(ctx: Context<SettleGame>) -> Result<()> {
     ];
     let game_signer = &[&game_seeds[..]];
 
-    let winner = game.get_winner(now, settings)?;
+    let first_player_referral_fee = match first_player_referrer_vault {
+        Some(_) => rake_terms.referral_share(first_player_payout.rake)?,
+        None => 0,
+    };
+    let second_player_referral_fee = match second_player_referrer_vault {
+        Some(_) => rake_terms.referral_share(second_player_payout.rake)?,
+        None => 0,
+    };
 
-    if winner.is_none() {
-        transfer_spl_compatible(
-            token_program,
-            first_player_escrow_token_account,
-            first_player_token_account,
-            &game.to_account_info(),
-            mint,
-            game.amount_to_match,
-            Some(game_signer),
-        )?;
-        transfer_spl_compatible(
-            token_program,
-            second_player_escrow_token_account,
-            second_player_token_account,
-            &game.to_account_info(),
-            mint,
-            game.amount_to_match,
-            Some(game_signer),
-        )?;
-    } else {
-        let winner = winner.as_ref().unwrap();
-        let (
-            winner_escrow_token_account,
-            winner_token_account,
-            loser_escrow_token_account,
-            _loser_token_account,
-        ) = match winner {
-            Player::First => (
+    match &winner {
+        None => {
+            pay_out_escrow(
+                token_program,
                 first_player_escrow_token_account,
                 first_player_token_account,
+                treasury_token_account,
+                first_player_referrer_vault,
+                first_player_referral_fee,
+                &game.to_account_info(),
+                mint,
+                &first_player_payout,
+                game_signer,
+                ctx.remaining_accounts,
+            )?;
+            pay_out_escrow(
+                token_program,
                 second_player_escrow_token_account,
                 second_player_token_account,
-            ),
-            Player::Second => (
-                second_player_escrow_token_account,
-                second_player_token_account,
-                first_player_escrow_token_account,
-                first_player_token_account,
-            ),
-        };
+                treasury_token_account,
+                second_player_referrer_vault,
+                second_player_referral_fee,
+                &game.to_account_info(),
+                mint,
+                &second_player_payout,
+                game_signer,
+                ctx.remaining_accounts,
+            )?;
+        }
+        Some(winner) => {
+            let (
+                winner_escrow_token_account,
+                winner_payout,
+                winner_token_account,
+                winner_referrer_vault,
+                winner_referral_fee,
+                loser_escrow_token_account,
+                loser_payout,
+                loser_referrer_vault,
+                loser_referral_fee,
+            ) = match winner {
+                Player::First => (
+                    first_player_escrow_token_account,
+                    &first_player_payout,
+                    first_player_token_account,
+                    first_player_referrer_vault,
+                    first_player_referral_fee,
+                    second_player_escrow_token_account,
+                    &second_player_payout,
+                    second_player_referrer_vault,
+                    second_player_referral_fee,
+                ),
+                Player::Second => (
+                    second_player_escrow_token_account,
+                    &second_player_payout,
+                    second_player_token_account,
+                    second_player_referrer_vault,
+                    second_player_referral_fee,
+                    first_player_escrow_token_account,
+                    &first_player_payout,
+                    first_player_referrer_vault,
+                    first_player_referral_fee,
+                ),
+            };
+            pay_out_escrow(
+                token_program,
+                winner_escrow_token_account,
+                winner_token_account,
+                treasury_token_account,
+                winner_referrer_vault,
+                winner_referral_fee,
+                &game.to_account_info(),
+                mint,
+                winner_payout,
+                game_signer,
+                ctx.remaining_accounts,
+            )?;
+            pay_out_escrow(
+                token_program,
+                loser_escrow_token_account,
+                winner_token_account,
+                treasury_token_account,
+                loser_referrer_vault,
+                loser_referral_fee,
+                &game.to_account_info(),
+                mint,
+                loser_payout,
+                game_signer,
+                ctx.remaining_accounts,
+            )?;
+        }
+    }
+
+    close_escrow_spl_compatible(
+        token_program,
+        &ctx.accounts.first_player_escrow_token_account,
+        mint,
+        &ctx.accounts.first_player,
+        &game.to_account_info(),
+        game_signer,
+    )?;
+    close_escrow_spl_compatible(
+        token_program,
+        &ctx.accounts.second_player_escrow_token_account,
+        mint,
+        &ctx.accounts.second_player,
+        &game.to_account_info(),
+        game_signer,
+    )?;
+
+    emit_cpi!(GameSettled {
+        game: game.key(),
+        first_player: first_player_key,
+        second_player: ctx.accounts.second_player.key(),
+        mint: game.mint,
+        amount_to_match: game.amount_to_match,
+        winner,
+        amount_won: game.amount_won,
+        rake,
+        settled_at: now,
+    });
+
+    if let Some(
+        [(first_player_old_rating, first_player_new_rating), (second_player_old_rating, second_player_new_rating)],
+    ) = ratings
+    {
+        emit_cpi!(RatingsUpdated {
+            game: game.key(),
+            first_player: game.first_player,
+            first_player_old_rating,
+            first_player_new_rating,
+            second_player: ctx.accounts.second_player.key(),
+            second_player_old_rating,
+            second_player_new_rating,
+        });
+    }
+
+    if let (Some(season), Some((first_player_score, second_player_score))) =
+        (&ctx.accounts.season, season_scores)
+    {
+        emit_cpi!(SeasonStandingsUpdated {
+            season: season.key(),
+            game: game.key(),
+            first_player: game.first_player,
+            first_player_score,
+            second_player: ctx.accounts.second_player.key(),
+            second_player_score,
+            leaderboard: season.leaderboard.clone(),
+        });
+    }
+
+    if let Some(side_pool) = SidePool::resolve(
+        ctx.accounts.side_pool.as_deref_mut(),
+        game,
+        side_pool_result.clone(),
+    )? {
+        emit_cpi!(SidePoolResolved {
+            side_pool: side_pool.key(),
+            game: game.key(),
+            result: side_pool_result,
+            total: side_pool.total()?,
+        });
+    }
+
+    for (player, referral, amount) in [
+        (
+            game.first_player,
+            &game.first_player_referral,
+            first_player_referral_fee,
+        ),
+        (
+            ctx.accounts.second_player.key(),
+            &game.second_player_referral,
+            second_player_referral_fee,
+        ),
+    ] {
+        if let Some(referral) = referral.as_ref().filter(|_| amount > 0) {
+            emit_cpi!(ReferralRewarded {
+                referrer: referral.referrer,
+                player,
+                game: game.key(),
+                mint: game.mint,
+                amount,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Empties an escrow, the payout to `to`, `referral_fee` of the rake to the
+/// referrer's vault and the rest to the treasury.
+#[allow(clippy::too_many_arguments)]
+fn pay_out_escrow<'info>(
+    token_program: &Interface<'info, TokenInterface>,
+    escrow_token_account: &mut Box<InterfaceAccount<'info, TokenAccount>>,
+    to_token_account: &mut Box<InterfaceAccount<'info, TokenAccount>>,
+    treasury_token_account: &mut Box<InterfaceAccount<'info, TokenAccount>>,
+    referrer_vault: Option<&mut Box<InterfaceAccount<'info, TokenAccount>>>,
+    referral_fee: u64,
+    authority: &AccountInfo<'info>,
+    mint: &InterfaceAccount<'info, Mint>,
+    &EscrowPayout { payout, rake }: &EscrowPayout,
+    signer_seeds: &[&[&[u8]]],
+    remaining_accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+    transfer_spl_compatible(
+        token_program,
+        escrow_token_account,
+        to_token_account,
+        authority,
+        mint,
+        payout,
+        Some(signer_seeds),
+        remaining_accounts,
+    )?;
+    if let Some(referrer_vault) = referrer_vault.filter(|_| referral_fee > 0) {
         transfer_spl_compatible(
             token_program,
-            winner_escrow_token_account,
-            winner_token_account,
-            &game.to_account_info(),
+            escrow_token_account,
+            referrer_vault,
+            authority,
             mint,
-            game.amount_to_match,
-            Some(game_signer),
+            referral_fee,
+            Some(signer_seeds),
+            remaining_accounts,
         )?;
+    }
+    if rake > referral_fee {
         transfer_spl_compatible(
             token_program,
-            loser_escrow_token_account,
-            winner_token_account,
-            &game.to_account_info(),
+            escrow_token_account,
+            treasury_token_account,
+            authority,
             mint,
-            game.amount_to_match,
-            Some(game_signer),
+            rake - referral_fee,
+            Some(signer_seeds),
+            remaining_accounts,
         )?;
     }
-
-    close_account(
-        CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            CloseAccount {
-                account: ctx
-                    .accounts
-                    .first_player_escrow_token_account
-                    .to_account_info(),
-                destination: ctx.accounts.first_player.to_account_info(),
-                authority: game.to_account_info(),
-            },
-        )
-        .with_signer(game_signer),
-    )?;
-    close_account(
-        CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            CloseAccount {
-                account: ctx
-                    .accounts
-                    .second_player_escrow_token_account
-                    .to_account_info(),
-                destination: ctx.accounts.second_player.to_account_info(),
-                authority: game.to_account_info(),
-            },
-        )
-        .with_signer(game_signer),
-    )?;
-
-    let amount_won: u64 = match winner {
-        Some(_) => match game.amount_to_match.checked_mul(2) {
-            Some(value) => value,
-            None => return Err(RockPaperScissorsError::NumericOverflow.into()),
-        },
-        None => 0,
-    };
-
-    game.set_claimed(&winner, amount_won, now);
-
     Ok(())
 }
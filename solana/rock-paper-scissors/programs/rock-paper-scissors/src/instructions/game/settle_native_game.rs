@@ -0,0 +1,282 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RockPaperScissorsError, withdraw_lamports, Game, GameReferral, GameSettled, GameState,
+    Player, PlayerProfile, PlayerRating, RatingsUpdated, ReferralRewarded, ReferrerRewards, Season,
+    SeasonEntry, SeasonStandingsUpdated, Settings, Settlement, SideBetOutcome, SidePool,
+    SidePoolResolved, SidePoolResult, WagerMode, GAME, PLAYER_PROFILE, PLAYER_RATING, SETTINGS,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SettleNativeGame<'info> {
+    #[account(
+        mut,
+        seeds = [
+            GAME.as_ref(),
+            game.first_player.as_ref(),
+            game.game_id.as_bytes()
+        ],
+        bump = game.bump,
+        constraint = game.state == GameState::Started @ RockPaperScissorsError::InvalidGameState,
+        constraint = game.wager_mode == WagerMode::Native @ RockPaperScissorsError::InvalidWagerMode,
+    )]
+    pub game: Box<Account<'info, Game>>,
+    #[account(
+        seeds = [SETTINGS.as_ref()],
+        bump = settings.bump,
+    )]
+    pub settings: Account<'info, Settings>,
+
+    /// CHECK: No check needed.
+    #[account(
+        mut,
+        address = game.first_player
+    )]
+    pub first_player: AccountInfo<'info>,
+    /// CHECK: No check needed.
+    #[account(
+        mut,
+        // Unwrapping here is fine since the game is in the started state.
+        address = game.second_player.unwrap()
+    )]
+    pub second_player: AccountInfo<'info>,
+
+    /// CHECK: No check needed, receives the rake.
+    #[account(
+        mut,
+        address = settings.treasury
+    )]
+    pub treasury: AccountInfo<'info>,
+
+    /// CHECK: Rated along with the second player's if both exist, players
+    /// of games created before ratings might not have one.
+    #[account(
+        mut,
+        seeds = [PLAYER_RATING.as_ref(), first_player.key().as_ref()],
+        bump,
+    )]
+    pub first_player_rating: UncheckedAccount<'info>,
+    /// CHECK: As above.
+    #[account(
+        mut,
+        seeds = [PLAYER_RATING.as_ref(), second_player.key().as_ref()],
+        bump,
+    )]
+    pub second_player_rating: UncheckedAccount<'info>,
+
+    /// CHECK: Recorded if it exists, players of games created before
+    /// profiles might not have one.
+    #[account(
+        mut,
+        seeds = [PLAYER_PROFILE.as_ref(), first_player.key().as_ref()],
+        bump,
+    )]
+    pub first_player_profile: UncheckedAccount<'info>,
+    /// CHECK: Recorded if it exists, as above.
+    #[account(
+        mut,
+        seeds = [PLAYER_PROFILE.as_ref(), second_player.key().as_ref()],
+        bump,
+    )]
+    pub second_player_profile: UncheckedAccount<'info>,
+
+    /// Not checked, so this call is permissionless.
+    pub signer: Signer<'info>,
+
+    /// The season the game counts towards, if any.
+    #[account(mut)]
+    pub season: Option<Box<Account<'info, Season>>>,
+    #[account(mut)]
+    pub first_player_season_entry: Option<Box<Account<'info, SeasonEntry>>>,
+    #[account(mut)]
+    pub second_player_season_entry: Option<Box<Account<'info, SeasonEntry>>>,
+
+    /// Rewards of the referrers snapshotted by the game, paid their share of
+    /// the rake of their player's escrow.
+    #[account(mut)]
+    pub first_player_referrer_rewards: Option<Box<Account<'info, ReferrerRewards>>>,
+    #[account(mut)]
+    pub second_player_referrer_rewards: Option<Box<Account<'info, ReferrerRewards>>>,
+
+    /// The game's `SidePool`, required once one is open.
+    #[account(mut)]
+    pub side_pool: Option<Box<Account<'info, SidePool>>>,
+}
+
+/// Pays the stakes out of the game account, leaving its rent for `close_game`.
+pub fn processor(ctx: Context<SettleNativeGame>) -> Result<()> {
+    let clock = Clock::get()?;
+    let game = &mut ctx.accounts.game;
+    let settings = &ctx.accounts.settings;
+    let now = clock.unix_timestamp;
+
+    let first_player_deposit = game.deposit_of(&Player::First);
+    let second_player_deposit = game.deposit_of(&Player::Second);
+    let forfeiter = game.forfeiter(now, settings);
+    let settlement = game.settle(now, settings, first_player_deposit, second_player_deposit)?;
+    let rake = settlement.rake()?;
+    let rake_terms = game.rake_terms(settings);
+    PlayerProfile::record_settlement(
+        &ctx.accounts.first_player_profile,
+        &ctx.accounts.second_player_profile,
+        game,
+        &settlement,
+        &forfeiter,
+        now,
+    )?;
+    let season_scores = Season::record_game(
+        ctx.accounts.season.as_deref_mut(),
+        ctx.accounts.first_player_season_entry.as_deref_mut(),
+        ctx.accounts.second_player_season_entry.as_deref_mut(),
+        game,
+        &settlement,
+        now,
+    )?;
+    let ratings = PlayerRating::record_settlement(
+        &ctx.accounts.first_player_rating,
+        &ctx.accounts.second_player_rating,
+        &settlement.winner,
+        now,
+    )?;
+    let side_pool_result = SidePoolResult::Settled(SideBetOutcome::from_winner(&settlement.winner));
+    let Settlement {
+        winner,
+        first_player: first_player_payout,
+        second_player: second_player_payout,
+    } = settlement;
+
+    let game_info = game.to_account_info();
+    match &winner {
+        None => {
+            withdraw_lamports(
+                &game_info,
+                &ctx.accounts.first_player,
+                first_player_payout.payout,
+            )?;
+            withdraw_lamports(
+                &game_info,
+                &ctx.accounts.second_player,
+                second_player_payout.payout,
+            )?;
+        }
+        Some(winner) => {
+            let winner_info = match winner {
+                Player::First => &ctx.accounts.first_player,
+                Player::Second => &ctx.accounts.second_player,
+            };
+            // Set by `settle`, both escrows' payouts.
+            withdraw_lamports(&game_info, winner_info, game.amount_won.unwrap_or_default())?;
+        }
+    }
+    let first_player_referral = pay_referrer(
+        &game_info,
+        GameReferral::rewards_at_settlement(
+            game.first_player_referral.as_ref(),
+            ctx.accounts.first_player_referrer_rewards.as_deref_mut(),
+        )?,
+        rake_terms.referral_share(first_player_payout.rake)?,
+    )?;
+    let second_player_referral = pay_referrer(
+        &game_info,
+        GameReferral::rewards_at_settlement(
+            game.second_player_referral.as_ref(),
+            ctx.accounts.second_player_referrer_rewards.as_deref_mut(),
+        )?,
+        rake_terms.referral_share(second_player_payout.rake)?,
+    )?;
+    let treasury_rake = rake
+        - first_player_referral.map_or(0, |(_, amount)| amount)
+        - second_player_referral.map_or(0, |(_, amount)| amount);
+    if treasury_rake > 0 {
+        withdraw_lamports(&game_info, &ctx.accounts.treasury, treasury_rake)?;
+    }
+
+    emit_cpi!(GameSettled {
+        game: game.key(),
+        first_player: game.first_player,
+        second_player: ctx.accounts.second_player.key(),
+        mint: game.mint,
+        amount_to_match: game.amount_to_match,
+        winner,
+        amount_won: game.amount_won,
+        rake,
+        settled_at: now,
+    });
+
+    if let Some(
+        [(first_player_old_rating, first_player_new_rating), (second_player_old_rating, second_player_new_rating)],
+    ) = ratings
+    {
+        emit_cpi!(RatingsUpdated {
+            game: game.key(),
+            first_player: game.first_player,
+            first_player_old_rating,
+            first_player_new_rating,
+            second_player: ctx.accounts.second_player.key(),
+            second_player_old_rating,
+            second_player_new_rating,
+        });
+    }
+
+    if let (Some(season), Some((first_player_score, second_player_score))) =
+        (&ctx.accounts.season, season_scores)
+    {
+        emit_cpi!(SeasonStandingsUpdated {
+            season: season.key(),
+            game: game.key(),
+            first_player: game.first_player,
+            first_player_score,
+            second_player: ctx.accounts.second_player.key(),
+            second_player_score,
+            leaderboard: season.leaderboard.clone(),
+        });
+    }
+
+    if let Some(side_pool) = SidePool::resolve(
+        ctx.accounts.side_pool.as_deref_mut(),
+        game,
+        side_pool_result.clone(),
+    )? {
+        emit_cpi!(SidePoolResolved {
+            side_pool: side_pool.key(),
+            game: game.key(),
+            result: side_pool_result,
+            total: side_pool.total()?,
+        });
+    }
+
+    for (player, referral) in [
+        (game.first_player, first_player_referral),
+        (ctx.accounts.second_player.key(), second_player_referral),
+    ] {
+        if let Some((referrer, amount)) = referral.filter(|&(_, amount)| amount > 0) {
+            emit_cpi!(ReferralRewarded {
+                referrer,
+                player,
+                game: game.key(),
+                mint: Pubkey::default(),
+                amount,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Moves a referrer's share of the rake from the game to its rewards,
+/// returning the referrer and the amount paid.
+fn pay_referrer<'info>(
+    game_info: &AccountInfo<'info>,
+    rewards: Option<&mut Account<'info, ReferrerRewards>>,
+    amount: u64,
+) -> Result<Option<(Pubkey, u64)>> {
+    let Some(rewards) = rewards else {
+        return Ok(None);
+    };
+    if amount > 0 {
+        withdraw_lamports(game_info, &rewards.to_account_info(), amount)?;
+        rewards.record_earned(amount);
+    }
+    Ok(Some((rewards.referrer, amount)))
+}
@@ -1,13 +1,13 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token_interface::{
-    close_account, CloseAccount, Mint, TokenAccount, TokenInterface,
-};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::{
-    error::RockPaperScissorsError, transfer_spl_compatible, Game, GameState, Settings, GAME,
+    close_escrow_spl_compatible, error::RockPaperScissorsError, transfer_spl_compatible, Game,
+    GameState, GameUnwound, Settings, SidePool, SidePoolResolved, SidePoolResult, WagerMode, GAME,
     GAME_ESCROW, SETTINGS,
 };
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct UnwindGame<'info> {
     #[account(
@@ -16,6 +16,7 @@ pub struct UnwindGame<'info> {
         seeds = [GAME.as_ref(), first_player.key().as_ref(), game.game_id.as_bytes()],
         bump = game.bump,
         constraint = game.state == GameState::Started @ RockPaperScissorsError::InvalidGameState,
+        constraint = game.wager_mode == WagerMode::Token @ RockPaperScissorsError::InvalidWagerMode,
     )]
     pub game: Account<'info, Game>,
 
@@ -77,6 +78,7 @@ pub struct UnwindGame<'info> {
     pub second_player: AccountInfo<'info>,
 
     #[account(
+        mut,
         mint::token_program = game.token_program,
         address = game.mint
     )]
@@ -92,9 +94,13 @@ pub struct UnwindGame<'info> {
 
     #[account(address = game.token_program)]
     pub token_program: Interface<'info, TokenInterface>,
+
+    /// The game's `SidePool`, required once one is open.
+    #[account(mut)]
+    pub side_pool: Option<Box<Account<'info, SidePool>>>,
 }
 
-pub fn processor(ctx: Context<UnwindGame>) -> Result<()> {
+pub fn processor<'info>(ctx: Context<'_, '_, '_, 'info, UnwindGame<'info>>) -> Result<()> {
     let clock = Clock::get()?;
     let game = &mut ctx.accounts.game;
     let settings = &ctx.accounts.settings;
@@ -106,7 +112,7 @@ pub fn processor(ctx: Context<UnwindGame>) -> Result<()> {
     let mint = &ctx.accounts.mint;
 
     require!(
-        game.is_stale(clock.unix_timestamp, settings.time_for_stale),
+        game.is_stale(clock.unix_timestamp, game.time_for_stale(settings)),
         RockPaperScissorsError::GameIsNotStale
     );
 
@@ -118,52 +124,67 @@ pub fn processor(ctx: Context<UnwindGame>) -> Result<()> {
     ];
     let game_signer = &[&game_seeds[..]];
 
+    let first_player_balance = first_player_escrow_token_account.amount;
     transfer_spl_compatible(
         &ctx.accounts.token_program,
         first_player_escrow_token_account,
         first_player_token_account,
         &game.to_account_info(),
         mint,
-        game.amount_to_match,
+        first_player_balance,
         Some(game_signer),
+        ctx.remaining_accounts,
     )?;
+    let second_player_balance = second_player_escrow_token_account.amount;
     transfer_spl_compatible(
         &ctx.accounts.token_program,
         second_player_escrow_token_account,
         second_player_token_account,
         &game.to_account_info(),
         mint,
-        game.amount_to_match,
+        second_player_balance,
         Some(game_signer),
+        ctx.remaining_accounts,
     )?;
-    close_account(
-        CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            CloseAccount {
-                account: ctx
-                    .accounts
-                    .first_player_escrow_token_account
-                    .to_account_info(),
-                destination: ctx.accounts.first_player.to_account_info(),
-                authority: game.to_account_info(),
-            },
-        )
-        .with_signer(game_signer),
+    close_escrow_spl_compatible(
+        &ctx.accounts.token_program,
+        first_player_escrow_token_account,
+        mint,
+        &ctx.accounts.first_player,
+        &game.to_account_info(),
+        game_signer,
     )?;
-    close_account(
-        CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            CloseAccount {
-                account: ctx
-                    .accounts
-                    .second_player_escrow_token_account
-                    .to_account_info(),
-                destination: ctx.accounts.second_player.to_account_info(),
-                authority: game.to_account_info(),
-            },
-        )
-        .with_signer(game_signer),
+    close_escrow_spl_compatible(
+        &ctx.accounts.token_program,
+        second_player_escrow_token_account,
+        mint,
+        &ctx.accounts.second_player,
+        &game.to_account_info(),
+        game_signer,
     )?;
 
+    let side_pool_result = SidePoolResult::Refunded;
+    if let Some(side_pool) = SidePool::resolve(
+        ctx.accounts.side_pool.as_deref_mut(),
+        game,
+        side_pool_result.clone(),
+    )? {
+        emit_cpi!(SidePoolResolved {
+            side_pool: side_pool.key(),
+            game: game.key(),
+            result: side_pool_result,
+            total: side_pool.total()?,
+        });
+    }
+
+    emit_cpi!(GameUnwound {
+        game: game.key(),
+        first_player: first_player_key,
+        second_player: ctx.accounts.second_player.key(),
+        mint: game.mint,
+        amount_to_match: game.amount_to_match,
+        unwound_at: clock.unix_timestamp,
+    });
+
     Ok(())
 }
@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RockPaperScissorsError, withdraw_lamports, Game, GameState, GameUnwound, Player,
+    Settings, SidePool, SidePoolResolved, SidePoolResult, WagerMode, GAME, SETTINGS,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UnwindNativeGame<'info> {
+    #[account(
+        mut,
+        // Refunds the first player's stake along with the rent.
+        close = first_player,
+        seeds = [GAME.as_ref(), first_player.key().as_ref(), game.game_id.as_bytes()],
+        bump = game.bump,
+        constraint = game.state == GameState::Started @ RockPaperScissorsError::InvalidGameState,
+        constraint = game.wager_mode == WagerMode::Native @ RockPaperScissorsError::InvalidWagerMode,
+    )]
+    pub game: Account<'info, Game>,
+    /// CHECK: No check needed.
+    #[account(
+        mut,
+        address = game.first_player
+    )]
+    pub first_player: AccountInfo<'info>,
+    /// CHECK: No check needed.
+    #[account(
+        mut,
+        // Unwrapping here is fine since the game is in the started state.
+        address = game.second_player.unwrap()
+    )]
+    pub second_player: AccountInfo<'info>,
+
+    /// Not checked, so this call is permissionless.
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [SETTINGS.as_ref()],
+        bump = settings.bump,
+    )]
+    pub settings: Account<'info, Settings>,
+
+    /// The game's `SidePool`, required once one is open.
+    #[account(mut)]
+    pub side_pool: Option<Box<Account<'info, SidePool>>>,
+}
+
+pub fn processor(ctx: Context<UnwindNativeGame>) -> Result<()> {
+    let clock = Clock::get()?;
+    let game = &ctx.accounts.game;
+    let settings = &ctx.accounts.settings;
+
+    require!(
+        game.is_stale(clock.unix_timestamp, game.time_for_stale(settings)),
+        RockPaperScissorsError::GameIsNotStale
+    );
+
+    withdraw_lamports(
+        &game.to_account_info(),
+        &ctx.accounts.second_player,
+        game.deposit_of(&Player::Second),
+    )?;
+
+    let side_pool_result = SidePoolResult::Refunded;
+    if let Some(side_pool) = SidePool::resolve(
+        ctx.accounts.side_pool.as_deref_mut(),
+        game,
+        side_pool_result.clone(),
+    )? {
+        emit_cpi!(SidePoolResolved {
+            side_pool: side_pool.key(),
+            game: game.key(),
+            result: side_pool_result,
+            total: side_pool.total()?,
+        });
+    }
+
+    emit_cpi!(GameUnwound {
+        game: game.key(),
+        first_player: game.first_player,
+        second_player: ctx.accounts.second_player.key(),
+        mint: game.mint,
+        amount_to_match: game.amount_to_match,
+        unwound_at: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -1,5 +1,13 @@
 pub mod game;
+pub mod referral;
+pub mod season;
 pub mod settings;
+pub mod side_pool;
+pub mod tournament;
 
 pub use game::*;
+pub use referral::*;
+pub use season::*;
 pub use settings::*;
+pub use side_pool::*;
+pub use tournament::*;
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RockPaperScissorsError, withdraw_lamports, ReferralRewardsClaimed, ReferrerRewards,
+    REFERRER_REWARDS,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimReferralRewards<'info> {
+    #[account(
+        mut,
+        seeds = [REFERRER_REWARDS.as_ref(), referrer.key().as_ref()],
+        bump = referrer_rewards.bump,
+    )]
+    pub referrer_rewards: Box<Account<'info, ReferrerRewards>>,
+
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+}
+
+/// Pays out the SOL shares, everything the rewards account holds above its
+/// rent.
+pub fn processor(ctx: Context<ClaimReferralRewards>) -> Result<()> {
+    let referrer_rewards = &mut ctx.accounts.referrer_rewards;
+    let referrer = &ctx.accounts.referrer;
+
+    let rent = Rent::get()?.minimum_balance(referrer_rewards.to_account_info().data_len());
+    let amount = referrer_rewards
+        .to_account_info()
+        .lamports()
+        .saturating_sub(rent);
+    require!(amount > 0, RockPaperScissorsError::NoReferralRewards);
+    referrer_rewards.claimed_lamports = referrer_rewards.claimed_lamports.saturating_add(amount);
+    withdraw_lamports(
+        &referrer_rewards.to_account_info(),
+        &referrer.to_account_info(),
+        amount,
+    )?;
+
+    emit_cpi!(ReferralRewardsClaimed {
+        referrer: referrer.key(),
+        mint: Pubkey::default(),
+        amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    error::RockPaperScissorsError, transfer_spl_compatible, ReferralRewardsClaimed,
+    ReferrerRewards, REFERRER_REWARDS,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimReferralTokenRewards<'info> {
+    #[account(
+        seeds = [REFERRER_REWARDS.as_ref(), referrer.key().as_ref()],
+        bump = referrer_rewards.bump,
+    )]
+    pub referrer_rewards: Box<Account<'info, ReferrerRewards>>,
+    /// Any token account of the rewards in `mint`.
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = referrer_rewards,
+        token::token_program = token_program
+    )]
+    pub referrer_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = referrer,
+        token::token_program = token_program
+    )]
+    pub referrer_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mint::token_program = token_program)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub referrer: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Empties a token account holding the referrer's token shares.
+pub fn processor<'info>(
+    ctx: Context<'_, '_, '_, 'info, ClaimReferralTokenRewards<'info>>,
+) -> Result<()> {
+    let referrer_rewards = &ctx.accounts.referrer_rewards;
+    let referrer_key = ctx.accounts.referrer.key();
+    let amount = ctx.accounts.referrer_vault.amount;
+    require!(amount > 0, RockPaperScissorsError::NoReferralRewards);
+
+    let rewards_seeds = &[
+        REFERRER_REWARDS.as_ref(),
+        referrer_key.as_ref(),
+        &[referrer_rewards.bump],
+    ];
+    transfer_spl_compatible(
+        &ctx.accounts.token_program,
+        &mut ctx.accounts.referrer_vault,
+        &mut ctx.accounts.referrer_token_account,
+        &referrer_rewards.to_account_info(),
+        &ctx.accounts.mint,
+        amount,
+        Some(&[&rewards_seeds[..]]),
+        ctx.remaining_accounts,
+    )?;
+
+    emit_cpi!(ReferralRewardsClaimed {
+        referrer: referrer_key,
+        mint: ctx.accounts.mint.key(),
+        amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,7 @@
+pub mod claim_referral_rewards;
+pub mod claim_referral_token_rewards;
+pub mod register_referrer;
+
+pub use claim_referral_rewards::*;
+pub use claim_referral_token_rewards::*;
+pub use register_referrer::*;
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RockPaperScissorsError, Referral, ReferrerRegistered, ReferrerRewards, REFERRAL,
+    REFERRER_REWARDS,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RegisterReferrer<'info> {
+    #[account(
+        init,
+        payer = player,
+        space = Referral::size(),
+        seeds = [REFERRAL.as_ref(), player.key().as_ref()],
+        bump,
+    )]
+    pub referral: Box<Account<'info, Referral>>,
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = ReferrerRewards::size(),
+        seeds = [REFERRER_REWARDS.as_ref(), referrer.key().as_ref()],
+        bump,
+    )]
+    pub referrer_rewards: Box<Account<'info, ReferrerRewards>>,
+
+    /// CHECK: Any wallet but the player's.
+    #[account(
+        constraint = referrer.key() != player.key() @ RockPaperScissorsError::SelfReferral
+    )]
+    pub referrer: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Once registered, a share of every fee and rake the player pays goes to
+/// the referrer, see `Settings::referral_share_bps`.
+pub fn processor(ctx: Context<RegisterReferrer>) -> Result<()> {
+    let clock = Clock::get()?;
+    let player = &ctx.accounts.player;
+    let referrer = &ctx.accounts.referrer;
+
+    let referrer_rewards = &mut ctx.accounts.referrer_rewards;
+    if !referrer_rewards.is_initialized() {
+        referrer_rewards.set_inner(ReferrerRewards::new(
+            ctx.bumps.referrer_rewards,
+            referrer.key(),
+            clock.unix_timestamp,
+        ));
+    }
+    referrer_rewards.referred_players = referrer_rewards.referred_players.saturating_add(1);
+
+    ctx.accounts.referral.set_inner(Referral {
+        bump: ctx.bumps.referral,
+        player: player.key(),
+        referrer: referrer.key(),
+        created_at: clock.unix_timestamp,
+    });
+
+    emit_cpi!(ReferrerRegistered {
+        player: player.key(),
+        referrer: referrer.key(),
+    });
+
+    Ok(())
+}
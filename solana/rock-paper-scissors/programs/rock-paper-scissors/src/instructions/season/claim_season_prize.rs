@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::RockPaperScissorsError, withdraw_lamports, Season, SeasonPrizeClaimed, SEASON};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimSeasonPrize<'info> {
+    #[account(
+        mut,
+        seeds = [SEASON.as_ref(), season.season_id.to_le_bytes().as_ref()],
+        bump = season.bump,
+    )]
+    pub season: Box<Account<'info, Season>>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+}
+
+/// Pays a finished season's leaderboard place its share of the prize vault,
+/// everything the season account holds above its rent, once standings are
+/// final and until claims are over.
+pub fn processor(ctx: Context<ClaimSeasonPrize>) -> Result<()> {
+    let clock = Clock::get()?;
+    let season = &mut ctx.accounts.season;
+    let player = &ctx.accounts.player;
+
+    require!(
+        clock.unix_timestamp >= season.claims_open_at(),
+        RockPaperScissorsError::SeasonNotEnded
+    );
+    require!(
+        clock.unix_timestamp < season.claims_close_at(),
+        RockPaperScissorsError::SeasonClaimsClosed
+    );
+    let place = season
+        .place_of(&player.key())
+        .ok_or(RockPaperScissorsError::NoPrizeToClaim)?;
+    let rent = Rent::get()?.minimum_balance(season.to_account_info().data_len());
+    let vault_balance = season.to_account_info().lamports().saturating_sub(rent);
+    season.lock_prize_pool(vault_balance);
+    let amount = season.prize_for(place)?;
+    require!(
+        !season.is_prize_claimed(place),
+        RockPaperScissorsError::PrizeAlreadyClaimed
+    );
+    season.set_prize_claimed(place);
+    withdraw_lamports(&season.to_account_info(), &player.to_account_info(), amount)?;
+
+    emit_cpi!(SeasonPrizeClaimed {
+        season: season.key(),
+        player: player.key(),
+        place: place as u8,
+        amount,
+    });
+
+    Ok(())
+}
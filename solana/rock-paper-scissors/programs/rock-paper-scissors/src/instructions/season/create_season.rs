@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RockPaperScissorsError, Season, SeasonCreated, SeasonRanking, Settings, MAX_BPS, SEASON,
+    SETTINGS,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(season_id: u32, leaderboard_size: u8)]
+pub struct CreateSeason<'info> {
+    #[account(
+        init,
+        payer = signer,
+        space = Season::size(leaderboard_size),
+        seeds = [SEASON.as_ref(), season_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub season: Box<Account<'info, Season>>,
+    #[account(
+        seeds = [SETTINGS.as_ref()],
+        bump = settings.bump,
+    )]
+    pub settings: Box<Account<'info, Settings>>,
+
+    #[account(mut, address = settings.admin)]
+    pub signer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn processor(
+    ctx: Context<CreateSeason>,
+    season_id: u32,
+    leaderboard_size: u8,
+    start_at: i64,
+    end_at: i64,
+    ranking: SeasonRanking,
+    fee_share_bps: u16, // Share of season games' player fees funding the prizes
+    prize_split_bps: Vec<u16>, // First place first, adds up to `MAX_BPS`
+) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(
+        start_at < end_at && end_at > clock.unix_timestamp,
+        RockPaperScissorsError::InvalidSeasonWindow
+    );
+    require!(
+        Season::is_valid_leaderboard_size(leaderboard_size),
+        RockPaperScissorsError::InvalidLeaderboardSize
+    );
+    require!(
+        Season::is_valid_prize_split(&prize_split_bps, leaderboard_size),
+        RockPaperScissorsError::InvalidPrizeSplit
+    );
+    require!(
+        fee_share_bps <= MAX_BPS,
+        RockPaperScissorsError::InvalidFeeShare
+    );
+
+    let season = &mut ctx.accounts.season;
+    season.set_inner(Season::new(
+        ctx.bumps.season,
+        season_id,
+        start_at,
+        end_at,
+        ranking,
+        fee_share_bps,
+        prize_split_bps,
+        leaderboard_size,
+        clock.unix_timestamp,
+    ));
+
+    emit_cpi!(SeasonCreated {
+        season: season.key(),
+        season_id,
+        start_at,
+        end_at,
+        ranking: season.ranking.clone(),
+        fee_share_bps,
+        prize_split_bps: season.prize_split_bps.clone(),
+        leaderboard_size,
+    });
+
+    Ok(())
+}
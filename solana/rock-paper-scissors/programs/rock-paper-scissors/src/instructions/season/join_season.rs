@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RockPaperScissorsError, Season, SeasonEntry, SeasonJoined, SEASON, SEASON_ENTRY,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct JoinSeason<'info> {
+    #[account(
+        seeds = [SEASON.as_ref(), season.season_id.to_le_bytes().as_ref()],
+        bump = season.bump,
+    )]
+    pub season: Box<Account<'info, Season>>,
+    #[account(
+        init,
+        payer = player,
+        space = SeasonEntry::size(),
+        seeds = [SEASON_ENTRY.as_ref(), season.key().as_ref(), player.key().as_ref()],
+        bump,
+    )]
+    pub season_entry: Box<Account<'info, SeasonEntry>>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Needed before creating or joining games that count towards the season.
+pub fn processor(ctx: Context<JoinSeason>) -> Result<()> {
+    let clock = Clock::get()?;
+    let season = &ctx.accounts.season;
+    require!(
+        !season.has_ended(clock.unix_timestamp),
+        RockPaperScissorsError::SeasonNotActive
+    );
+
+    let season_entry = &mut ctx.accounts.season_entry;
+    season_entry.set_inner(SeasonEntry::new(
+        ctx.bumps.season_entry,
+        season.key(),
+        ctx.accounts.player.key(),
+        clock.unix_timestamp,
+    ));
+
+    emit_cpi!(SeasonJoined {
+        season: season.key(),
+        player: season_entry.player,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,11 @@
+pub mod claim_season_prize;
+pub mod create_season;
+pub mod join_season;
+pub mod rank_season_entry;
+pub mod sweep_season_prizes;
+
+pub use claim_season_prize::*;
+pub use create_season::*;
+pub use join_season::*;
+pub use rank_season_entry::*;
+pub use sweep_season_prizes::*;
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::{Season, SeasonEntry, SeasonEntryRanked, SEASON, SEASON_ENTRY};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RankSeasonEntry<'info> {
+    #[account(
+        mut,
+        seeds = [SEASON.as_ref(), season.season_id.to_le_bytes().as_ref()],
+        bump = season.bump,
+    )]
+    pub season: Box<Account<'info, Season>>,
+    #[account(
+        seeds = [SEASON_ENTRY.as_ref(), season.key().as_ref(), season_entry.player.as_ref()],
+        bump = season_entry.bump,
+    )]
+    pub season_entry: Box<Account<'info, SeasonEntry>>,
+
+    /// Not checked, so this call is permissionless.
+    pub signer: Signer<'info>,
+}
+
+/// Puts an entry the leaderboard left behind back on it, when its current
+/// score earns a place.
+pub fn processor(ctx: Context<RankSeasonEntry>) -> Result<()> {
+    let clock = Clock::get()?;
+    let season = &mut ctx.accounts.season;
+    let season_entry = &ctx.accounts.season_entry;
+
+    let score = season.rank_entry(season_entry, clock.unix_timestamp)?;
+
+    emit_cpi!(SeasonEntryRanked {
+        season: season.key(),
+        player: season_entry.player,
+        score,
+        leaderboard: season.leaderboard.clone(),
+    });
+
+    Ok(())
+}
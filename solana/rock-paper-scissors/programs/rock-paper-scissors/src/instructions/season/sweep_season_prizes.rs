@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RockPaperScissorsError, withdraw_lamports, Season, SeasonPrizesSwept, Settings, SEASON,
+    SETTINGS,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SweepSeasonPrizes<'info> {
+    #[account(
+        mut,
+        seeds = [SEASON.as_ref(), season.season_id.to_le_bytes().as_ref()],
+        bump = season.bump,
+    )]
+    pub season: Box<Account<'info, Season>>,
+    #[account(
+        seeds = [SETTINGS.as_ref()],
+        bump = settings.bump,
+    )]
+    pub settings: Box<Account<'info, Settings>>,
+
+    /// CHECK: Receives the unclaimed prizes.
+    #[account(
+        mut,
+        address = settings.treasury
+    )]
+    pub treasury: AccountInfo<'info>,
+
+    #[account(address = settings.admin)]
+    pub signer: Signer<'info>,
+}
+
+/// Moves what the prize vault still holds once claims are over to the
+/// treasury: prizes nobody claimed, and the shares of places the
+/// leaderboard never filled. The season account stays, games still settling
+/// towards it need it.
+pub fn processor(ctx: Context<SweepSeasonPrizes>) -> Result<()> {
+    let clock = Clock::get()?;
+    let season = &mut ctx.accounts.season;
+    let treasury = &ctx.accounts.treasury;
+
+    require!(
+        clock.unix_timestamp >= season.claims_close_at(),
+        RockPaperScissorsError::SeasonClaimsNotOver
+    );
+    let rent = Rent::get()?.minimum_balance(season.to_account_info().data_len());
+    let amount = season.to_account_info().lamports().saturating_sub(rent);
+    require!(amount > 0, RockPaperScissorsError::NoPrizeToClaim);
+    withdraw_lamports(&season.to_account_info(), treasury, amount)?;
+
+    emit_cpi!(SeasonPrizesSwept {
+        season: season.key(),
+        treasury: treasury.key(),
+        amount,
+    });
+
+    Ok(())
+}
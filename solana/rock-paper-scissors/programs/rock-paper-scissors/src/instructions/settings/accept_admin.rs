@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::RockPaperScissorsError, AdminAccepted, Settings, SETTINGS};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [SETTINGS.as_ref()],
+        bump = settings.bump,
+        constraint = settings.pending_admin == Some(signer.key()) @ RockPaperScissorsError::NoPendingAdmin,
+    )]
+    pub settings: Account<'info, Settings>,
+    pub signer: Signer<'info>,
+}
+
+pub fn processor(ctx: Context<AcceptAdmin>) -> Result<()> {
+    let settings = &mut ctx.accounts.settings;
+    let old_admin = settings.admin;
+    settings.admin = ctx.accounts.signer.key();
+    settings.pending_admin = None;
+
+    emit_cpi!(AdminAccepted {
+        settings: settings.key(),
+        old_admin,
+        new_admin: settings.admin,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RockPaperScissorsError, MintConfig, MintConfigInitialized, MintConfigValues, Settings,
+    MINT_CONFIG, SETTINGS,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(mint: Pubkey)]
+pub struct InitializeMintConfig<'info> {
+    #[account(
+        init,
+        payer = signer,
+        space = MintConfig::size(),
+        seeds = [MINT_CONFIG.as_ref(), settings.key().as_ref(), mint.as_ref()],
+        bump,
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+    #[account(
+        seeds = [SETTINGS.as_ref()],
+        bump = settings.bump,
+    )]
+    pub settings: Account<'info, Settings>,
+    #[account(
+        mut,
+        address = settings.admin,
+    )]
+    pub signer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn processor(
+    ctx: Context<InitializeMintConfig>,
+    mint: Pubkey, // `Pubkey::default()` for native SOL
+    enabled: bool,
+    min_amount: u64,
+    max_amount: u64,
+    player_fee_lamports: Option<u64>,
+) -> Result<()> {
+    require!(
+        MintConfig::is_valid_limits(min_amount, max_amount),
+        RockPaperScissorsError::InvalidAmountLimits
+    );
+
+    let mint_config = &mut ctx.accounts.mint_config;
+    mint_config.set_inner(MintConfig::new(
+        ctx.bumps.mint_config,
+        ctx.accounts.settings.key(),
+        mint,
+        enabled,
+        min_amount,
+        max_amount,
+        player_fee_lamports,
+    ));
+
+    emit_cpi!(MintConfigInitialized {
+        mint_config: mint_config.key(),
+        mint,
+        values: MintConfigValues::from(&**mint_config),
+    });
+
+    Ok(())
+}
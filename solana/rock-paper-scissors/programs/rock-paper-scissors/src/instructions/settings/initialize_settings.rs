@@ -1,7 +1,10 @@
-use anchor_lang::{prelude::*, solana_program::pubkey};
+use anchor_lang::{prelude::*, solana_program::bpf_loader_upgradeable};
 
-use crate::{Settings, SETTINGS};
+use crate::{
+    error::RockPaperScissorsError, Settings, SettingsInitialized, SettingsValues, SETTINGS,
+};
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct InitializeSettings<'info> {
     #[account(
@@ -12,20 +15,35 @@ pub struct InitializeSettings<'info> {
         bump,
     )]
     pub settings: Account<'info, Settings>,
+    /// Only the program's upgrade authority can initialize, and becomes admin.
+    #[account(mut)]
+    pub signer: Signer<'info>,
     #[account(
-        mut,
-        address = pubkey!("rpstBRQkjzHZhsJKL6ENJXicc6nq2x49pDq7iTSRmuD")
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(signer.key()),
     )]
-    pub signer: Signer<'info>,
+    pub program_data: Account<'info, ProgramData>,
     pub system_program: Program<'info, System>,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn processor(
     ctx: Context<InitializeSettings>,
     time_for_penalization: i64,
     time_for_stale: i64,
     game_fee_lamports: u64,
+    rake_bps: u16,
+    rake_draws: bool,
+    game_retention_period: i64,
+    treasury: Pubkey,
 ) -> Result<()> {
+    require!(
+        Settings::is_valid_rake(rake_bps),
+        RockPaperScissorsError::InvalidRake
+    );
+
     let settings = &mut ctx.accounts.settings;
     let signer = &ctx.accounts.signer;
     let bump = ctx.bumps.settings;
@@ -33,8 +51,18 @@ pub fn processor(
         bump,
         time_for_penalization,
         time_for_stale,
-        signer.key(),
+        treasury,
         game_fee_lamports,
+        rake_bps,
+        rake_draws,
+        game_retention_period,
+        signer.key(),
     ));
+
+    emit_cpi!(SettingsInitialized {
+        settings: settings.key(),
+        admin: settings.admin,
+        values: SettingsValues::from(&**settings),
+    });
     Ok(())
 }
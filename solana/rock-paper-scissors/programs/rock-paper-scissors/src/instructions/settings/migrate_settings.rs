@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RockPaperScissorsError, transfer_lamports, Settings, SettingsMigrated, SettingsValues,
+    TransferLamports, SETTINGS,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MigrateSettings<'info> {
+    /// CHECK: Deserialized by hand, since accounts created before the latest
+    /// fields were appended are too short for `Account<Settings>`.
+    #[account(
+        mut,
+        seeds = [SETTINGS.as_ref()],
+        bump,
+        owner = crate::ID,
+    )]
+    pub settings: UncheckedAccount<'info>,
+    /// The admin, or the treasury on accounts from before admin rights were
+    /// split out of it.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn processor(ctx: Context<MigrateSettings>) -> Result<()> {
+    let settings = &ctx.accounts.settings;
+    let signer = &ctx.accounts.signer;
+    require!(
+        settings.data_len() < Settings::size(),
+        RockPaperScissorsError::SettingsAlreadyMigrated
+    );
+
+    let rent = Rent::get()?
+        .minimum_balance(Settings::size())
+        .saturating_sub(settings.lamports());
+    if rent > 0 {
+        transfer_lamports(
+            TransferLamports {
+                from: signer.to_account_info(),
+                to: settings.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            },
+            rent,
+        )?;
+    }
+    // Appended fields are zeroed, which decodes to their defaults.
+    settings.realloc(Settings::size(), true)?;
+
+    let mut migrated = Settings::try_deserialize(&mut &settings.try_borrow_data()?[..])?;
+    let admin = if migrated.admin == Pubkey::default() {
+        migrated.treasury
+    } else {
+        migrated.admin
+    };
+    require_keys_eq!(
+        signer.key(),
+        admin,
+        anchor_lang::error::ErrorCode::ConstraintAddress
+    );
+    migrated.admin = admin;
+    migrated.try_serialize(&mut &mut settings.try_borrow_mut_data()?[..])?;
+
+    emit_cpi!(SettingsMigrated {
+        settings: settings.key(),
+        admin: migrated.admin,
+        values: SettingsValues::from(&migrated),
+    });
+
+    Ok(())
+}
@@ -1,5 +1,21 @@
+pub mod accept_admin;
+pub mod initialize_mint_config;
 pub mod initialize_settings;
+pub mod migrate_settings;
+pub mod propose_admin;
+pub mod set_paused;
+pub mod set_referral_share;
+pub mod update_mint_config;
 pub mod update_settings;
+pub mod update_window_bounds;
 
+pub use accept_admin::*;
+pub use initialize_mint_config::*;
 pub use initialize_settings::*;
+pub use migrate_settings::*;
+pub use propose_admin::*;
+pub use set_paused::*;
+pub use set_referral_share::*;
+pub use update_mint_config::*;
 pub use update_settings::*;
+pub use update_window_bounds::*;
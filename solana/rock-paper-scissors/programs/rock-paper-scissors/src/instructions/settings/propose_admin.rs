@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::{AdminProposed, Settings, SETTINGS};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [SETTINGS.as_ref()],
+        bump = settings.bump,
+    )]
+    pub settings: Account<'info, Settings>,
+    #[account(address = settings.admin)]
+    pub signer: Signer<'info>,
+}
+
+pub fn processor(ctx: Context<ProposeAdmin>, pending_admin: Option<Pubkey>) -> Result<()> {
+    let settings = &mut ctx.accounts.settings;
+    settings.pending_admin = pending_admin;
+
+    emit_cpi!(AdminProposed {
+        settings: settings.key(),
+        admin: settings.admin,
+        pending_admin,
+    });
+
+    Ok(())
+}
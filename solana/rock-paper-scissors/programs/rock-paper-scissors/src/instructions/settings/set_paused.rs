@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::RockPaperScissorsError, PauseUpdated, Settings, SETTINGS};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    #[account(
+        mut,
+        seeds = [SETTINGS.as_ref()],
+        bump = settings.bump,
+    )]
+    pub settings: Account<'info, Settings>,
+    #[account(address = settings.admin)]
+    pub signer: Signer<'info>,
+}
+
+pub fn processor(ctx: Context<SetPaused>, paused: u8) -> Result<()> {
+    require!(
+        Settings::is_valid_pause(paused),
+        RockPaperScissorsError::InvalidPauseFlags
+    );
+
+    let settings = &mut ctx.accounts.settings;
+    let old_paused = settings.paused;
+    settings.paused = paused;
+
+    emit_cpi!(PauseUpdated {
+        settings: settings.key(),
+        old_paused,
+        new_paused: paused,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::RockPaperScissorsError, ReferralShareUpdated, Settings, MAX_BPS, SETTINGS};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetReferralShare<'info> {
+    #[account(
+        mut,
+        seeds = [SETTINGS.as_ref()],
+        bump = settings.bump,
+    )]
+    pub settings: Account<'info, Settings>,
+    #[account(address = settings.admin)]
+    pub signer: Signer<'info>,
+}
+
+pub fn processor(ctx: Context<SetReferralShare>, referral_share_bps: u16) -> Result<()> {
+    require!(
+        referral_share_bps <= MAX_BPS,
+        RockPaperScissorsError::InvalidFeeShare
+    );
+
+    let settings = &mut ctx.accounts.settings;
+    let old_referral_share_bps = settings.referral_share_bps;
+    settings.referral_share_bps = referral_share_bps;
+
+    emit_cpi!(ReferralShareUpdated {
+        settings: settings.key(),
+        old_referral_share_bps,
+        new_referral_share_bps: referral_share_bps,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RockPaperScissorsError, MintConfig, MintConfigUpdated, MintConfigValues, Settings,
+    MINT_CONFIG, SETTINGS,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateMintConfig<'info> {
+    #[account(
+        mut,
+        seeds = [MINT_CONFIG.as_ref(), settings.key().as_ref(), mint_config.mint.as_ref()],
+        bump = mint_config.bump,
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+    #[account(
+        seeds = [SETTINGS.as_ref()],
+        bump = settings.bump,
+    )]
+    pub settings: Account<'info, Settings>,
+    #[account(address = settings.admin)]
+    pub signer: Signer<'info>,
+}
+
+pub fn processor(
+    ctx: Context<UpdateMintConfig>,
+    enabled: bool,
+    min_amount: u64,
+    max_amount: u64,
+    player_fee_lamports: Option<u64>,
+) -> Result<()> {
+    require!(
+        MintConfig::is_valid_limits(min_amount, max_amount),
+        RockPaperScissorsError::InvalidAmountLimits
+    );
+
+    let mint_config = &mut ctx.accounts.mint_config;
+    let old_values = MintConfigValues::from(&**mint_config);
+    mint_config.enabled = enabled;
+    mint_config.min_amount = min_amount;
+    mint_config.max_amount = max_amount;
+    mint_config.player_fee_lamports = player_fee_lamports;
+
+    emit_cpi!(MintConfigUpdated {
+        mint_config: mint_config.key(),
+        mint: mint_config.mint,
+        old_values,
+        new_values: MintConfigValues::from(&**mint_config),
+    });
+
+    Ok(())
+}
@@ -1,7 +1,8 @@
 use anchor_lang::prelude::*;
 
-use crate::{Settings, SETTINGS};
+use crate::{error::RockPaperScissorsError, Settings, SettingsUpdated, SettingsValues, SETTINGS};
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct UpdateSettings<'info> {
     #[account(
@@ -12,20 +13,42 @@ pub struct UpdateSettings<'info> {
     pub settings: Account<'info, Settings>,
     #[account(
         mut,
-        address = settings.treasury,
+        address = settings.admin,
     )]
     pub signer: Signer<'info>,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn processor(
     ctx: Context<UpdateSettings>,
     time_for_penalization: i64,
     time_for_stale: i64,
     player_fee_lamports: u64,
+    rake_bps: u16,
+    rake_draws: bool,
+    game_retention_period: i64,
+    treasury: Pubkey,
 ) -> Result<()> {
+    require!(
+        Settings::is_valid_rake(rake_bps),
+        RockPaperScissorsError::InvalidRake
+    );
+
     let settings = &mut ctx.accounts.settings;
+    let old_values = SettingsValues::from(&**settings);
     settings.time_for_stale = time_for_stale;
     settings.time_for_penalization = time_for_penalization;
     settings.player_fee_lamports = player_fee_lamports;
+    settings.rake_bps = rake_bps;
+    settings.rake_draws = rake_draws;
+    settings.game_retention_period = game_retention_period;
+    settings.treasury = treasury;
+
+    emit_cpi!(SettingsUpdated {
+        settings: settings.key(),
+        old_values,
+        new_values: SettingsValues::from(&**settings),
+    });
+
     Ok(())
 }
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RockPaperScissorsError, Settings, WindowBoundsUpdated, WindowBoundsValues, SETTINGS,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateWindowBounds<'info> {
+    #[account(
+        mut,
+        seeds = [SETTINGS.as_ref()],
+        bump = settings.bump,
+    )]
+    pub settings: Account<'info, Settings>,
+    #[account(address = settings.admin)]
+    pub signer: Signer<'info>,
+}
+
+pub fn processor(
+    ctx: Context<UpdateWindowBounds>,
+    min_time_for_penalization: i64,
+    max_time_for_penalization: i64,
+    min_time_for_stale: i64,
+    max_time_for_stale: i64,
+) -> Result<()> {
+    require!(
+        Settings::is_valid_window_bounds(min_time_for_penalization, max_time_for_penalization)
+            && Settings::is_valid_window_bounds(min_time_for_stale, max_time_for_stale),
+        RockPaperScissorsError::InvalidWindowBounds
+    );
+
+    let settings = &mut ctx.accounts.settings;
+    let old_values = WindowBoundsValues::from(&**settings);
+    settings.min_time_for_penalization = min_time_for_penalization;
+    settings.max_time_for_penalization = max_time_for_penalization;
+    settings.min_time_for_stale = min_time_for_stale;
+    settings.max_time_for_stale = max_time_for_stale;
+
+    emit_cpi!(WindowBoundsUpdated {
+        settings: settings.key(),
+        old_values,
+        new_values: WindowBoundsValues::from(&**settings),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use crate::{withdraw_lamports, SideBet, SideBetClaimed, SidePool, SIDE_BET, SIDE_POOL};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimSideBet<'info> {
+    #[account(
+        mut,
+        seeds = [SIDE_POOL.as_ref(), side_pool.game.as_ref()],
+        bump = side_pool.bump,
+    )]
+    pub side_pool: Box<Account<'info, SidePool>>,
+    #[account(
+        mut,
+        close = bettor,
+        seeds = [SIDE_BET.as_ref(), side_pool.key().as_ref(), bettor.key().as_ref()],
+        bump = side_bet.bump,
+    )]
+    pub side_bet: Box<Account<'info, SideBet>>,
+
+    #[account(mut)]
+    pub bettor: Signer<'info>,
+}
+
+/// Pays out a bet once its pool is resolved, closing it either way.
+pub fn processor(ctx: Context<ClaimSideBet>) -> Result<()> {
+    let side_pool = &mut ctx.accounts.side_pool;
+    let side_bet = &ctx.accounts.side_bet;
+
+    let payout = side_pool.payout_for(side_bet)?;
+    if payout > 0 {
+        withdraw_lamports(
+            &side_pool.to_account_info(),
+            &ctx.accounts.bettor.to_account_info(),
+            payout,
+        )?;
+    }
+    side_pool.open_bets -= 1;
+
+    emit_cpi!(SideBetClaimed {
+        side_pool: side_pool.key(),
+        bettor: side_bet.bettor,
+        outcome: side_bet.outcome.clone(),
+        amount: side_bet.amount,
+        payout,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::RockPaperScissorsError, SidePool, SidePoolClosed, SIDE_POOL};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseSidePool<'info> {
+    #[account(
+        mut,
+        // Refunds the rent along with the rounding dust of payouts.
+        close = creator,
+        seeds = [SIDE_POOL.as_ref(), side_pool.game.as_ref()],
+        bump = side_pool.bump,
+        constraint = side_pool.result.is_some() @ RockPaperScissorsError::SidePoolNotResolved,
+        constraint = side_pool.open_bets == 0 @ RockPaperScissorsError::SidePoolHasOpenBets,
+    )]
+    pub side_pool: Box<Account<'info, SidePool>>,
+    /// CHECK: No check needed.
+    #[account(
+        mut,
+        address = side_pool.creator
+    )]
+    pub creator: AccountInfo<'info>,
+
+    /// Not checked, so this call is permissionless.
+    pub signer: Signer<'info>,
+}
+
+/// Permissionless, closes a side pool once every bet is claimed.
+pub fn processor(ctx: Context<CloseSidePool>) -> Result<()> {
+    let side_pool = &ctx.accounts.side_pool;
+
+    emit_cpi!(SidePoolClosed {
+        side_pool: side_pool.key(),
+        game: side_pool.game,
+        creator: side_pool.creator,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,9 @@
+pub mod claim_side_bet;
+pub mod close_side_pool;
+pub mod open_side_pool;
+pub mod place_side_bet;
+
+pub use claim_side_bet::*;
+pub use close_side_pool::*;
+pub use open_side_pool::*;
+pub use place_side_bet::*;
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RockPaperScissorsError, Game, GameState, SidePool, SidePoolOpened, WagerMode, GAME,
+    SIDE_POOL,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct OpenSidePool<'info> {
+    #[account(
+        mut,
+        seeds = [
+            GAME.as_ref(),
+            game.first_player.as_ref(),
+            game.game_id.as_bytes()
+        ],
+        bump = game.bump,
+        constraint = game.state == GameState::Started @ RockPaperScissorsError::InvalidGameState,
+        // Tournament games settle through their tournament, which doesn't
+        // resolve side pools.
+        constraint = game.wager_mode != WagerMode::Tournament @ RockPaperScissorsError::InvalidWagerMode,
+    )]
+    pub game: Box<Account<'info, Game>>,
+    #[account(
+        init,
+        payer = creator,
+        space = SidePool::size(),
+        seeds = [SIDE_POOL.as_ref(), game.key().as_ref()],
+        bump,
+    )]
+    pub side_pool: Box<Account<'info, SidePool>>,
+
+    /// Not checked, so anyone can open a game's pool.
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn processor(ctx: Context<OpenSidePool>) -> Result<()> {
+    let clock = Clock::get()?;
+    let game = &mut ctx.accounts.game;
+    require!(!game.has_revealed(), RockPaperScissorsError::SideBetsClosed);
+
+    let side_pool = &mut ctx.accounts.side_pool;
+    side_pool.set_inner(SidePool::new(
+        ctx.bumps.side_pool,
+        game.key(),
+        ctx.accounts.creator.key(),
+        clock.unix_timestamp,
+    ));
+    game.side_pool = Some(side_pool.key());
+
+    emit_cpi!(SidePoolOpened {
+        side_pool: side_pool.key(),
+        game: game.key(),
+        creator: side_pool.creator,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RockPaperScissorsError, transfer_lamports, Game, SideBet, SideBetOutcome, SideBetPlaced,
+    SidePool, TransferLamports, GAME, SIDE_BET, SIDE_POOL,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct PlaceSideBet<'info> {
+    #[account(
+        seeds = [
+            GAME.as_ref(),
+            game.first_player.as_ref(),
+            game.game_id.as_bytes()
+        ],
+        bump = game.bump,
+    )]
+    pub game: Box<Account<'info, Game>>,
+    #[account(
+        mut,
+        seeds = [SIDE_POOL.as_ref(), game.key().as_ref()],
+        bump = side_pool.bump,
+    )]
+    pub side_pool: Box<Account<'info, SidePool>>,
+    #[account(
+        init,
+        payer = bettor,
+        space = SideBet::size(),
+        seeds = [SIDE_BET.as_ref(), side_pool.key().as_ref(), bettor.key().as_ref()],
+        bump,
+    )]
+    pub side_bet: Box<Account<'info, SideBet>>,
+
+    #[account(mut)]
+    pub bettor: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// One bet per bettor and pool, staking lamports whatever the game wagers.
+pub fn processor(ctx: Context<PlaceSideBet>, outcome: SideBetOutcome, amount: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    let game = &ctx.accounts.game;
+    let side_pool = &mut ctx.accounts.side_pool;
+    let bettor = &ctx.accounts.bettor;
+
+    require!(
+        side_pool.is_open(game),
+        RockPaperScissorsError::SideBetsClosed
+    );
+    require!(
+        amount > 0 && !game.is_player(&bettor.key()),
+        RockPaperScissorsError::InvalidSideBet
+    );
+
+    transfer_lamports(
+        TransferLamports {
+            from: bettor.to_account_info(),
+            to: side_pool.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        },
+        amount,
+    )?;
+    side_pool.record_bet(&outcome, amount)?;
+
+    ctx.accounts.side_bet.set_inner(SideBet {
+        bump: ctx.bumps.side_bet,
+        side_pool: side_pool.key(),
+        bettor: bettor.key(),
+        outcome: outcome.clone(),
+        amount,
+        placed_at: clock.unix_timestamp,
+    });
+
+    emit_cpi!(SideBetPlaced {
+        side_pool: side_pool.key(),
+        game: game.key(),
+        bettor: bettor.key(),
+        outcome,
+        amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RockPaperScissorsError, withdraw_lamports, Tournament, TournamentPrizeClaimed,
+    TOURNAMENT,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimTournamentPrize<'info> {
+    #[account(
+        mut,
+        seeds = [
+            TOURNAMENT.as_ref(),
+            tournament.organizer.as_ref(),
+            tournament.tournament_id.as_bytes()
+        ],
+        bump = tournament.bump,
+    )]
+    pub tournament: Box<Account<'info, Tournament>>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+}
+
+pub fn processor(ctx: Context<ClaimTournamentPrize>) -> Result<()> {
+    let tournament = &mut ctx.accounts.tournament;
+    let player = &ctx.accounts.player;
+
+    let place = tournament
+        .place_of(&player.key())
+        .ok_or(RockPaperScissorsError::NoPrizeToClaim)?;
+    let amount = tournament.prize_for(place)?;
+    require!(
+        !tournament.is_prize_claimed(place),
+        RockPaperScissorsError::PrizeAlreadyClaimed
+    );
+    tournament.set_prize_claimed(place);
+    withdraw_lamports(
+        &tournament.to_account_info(),
+        &player.to_account_info(),
+        amount,
+    )?;
+
+    emit_cpi!(TournamentPrizeClaimed {
+        tournament: tournament.key(),
+        player: player.key(),
+        place: place as u8,
+        amount,
+    });
+
+    Ok(())
+}
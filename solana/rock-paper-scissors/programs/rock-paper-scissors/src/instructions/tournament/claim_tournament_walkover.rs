@@ -0,0 +1,120 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RockPaperScissorsError, Game, GameCancelled, GameState, Tournament, TournamentFinished,
+    TournamentMatchDecided, TournamentRoundStarted, TOURNAMENT,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimTournamentWalkover<'info> {
+    #[account(
+        mut,
+        seeds = [
+            TOURNAMENT.as_ref(),
+            tournament.organizer.as_ref(),
+            tournament.tournament_id.as_bytes()
+        ],
+        bump = tournament.bump,
+    )]
+    pub tournament: Box<Account<'info, Tournament>>,
+
+    /// The match's game, when one was created but never joined.
+    #[account(mut)]
+    pub game: Option<Box<Account<'info, Game>>>,
+    /// CHECK: Checked against the game, gets its rent back.
+    #[account(mut)]
+    pub first_player: Option<AccountInfo<'info>>,
+
+    /// Not checked, so this call is permissionless.
+    pub signer: Signer<'info>,
+}
+
+/// Decides a match nobody played once its deadline passes. A player
+/// who opened the match's game advances, the higher seed does when nobody
+/// did. Started games are decided by `settle_tournament_game`, forfeits
+/// included.
+pub fn processor(ctx: Context<ClaimTournamentWalkover>, match_index: u8) -> Result<()> {
+    let clock = Clock::get()?;
+    let tournament = &mut ctx.accounts.tournament;
+    let now = clock.unix_timestamp;
+
+    let tournament_match = tournament.match_at(match_index)?.clone();
+    require!(
+        tournament_match.winner.is_none(),
+        RockPaperScissorsError::InvalidMatch
+    );
+    require!(
+        tournament
+            .match_deadline(match_index)?
+            .is_some_and(|match_deadline| match_deadline <= now),
+        RockPaperScissorsError::RoundDeadlineNotReached
+    );
+
+    let winner = match tournament_match.game {
+        None => tournament_match.first_player,
+        Some(game_key) => {
+            let game = ctx
+                .accounts
+                .game
+                .as_ref()
+                .ok_or(RockPaperScissorsError::InvalidMatch)?;
+            let first_player = ctx
+                .accounts
+                .first_player
+                .as_ref()
+                .ok_or(RockPaperScissorsError::InvalidPlayer)?;
+            require_keys_eq!(game.key(), game_key, RockPaperScissorsError::InvalidMatch);
+            require!(
+                game.state == GameState::Created,
+                RockPaperScissorsError::InvalidGameState
+            );
+            require_keys_eq!(
+                first_player.key(),
+                game.first_player,
+                RockPaperScissorsError::InvalidPlayer
+            );
+
+            emit_cpi!(GameCancelled {
+                game: game_key,
+                first_player: game.first_player,
+                mint: game.mint,
+                amount_to_match: game.amount_to_match,
+                cancelled_at: now,
+            });
+            game.close(first_player.to_account_info())?;
+            first_player.key()
+        }
+    };
+    let loser = tournament_match
+        .opponent_of(&winner)
+        .ok_or(RockPaperScissorsError::InvalidPlayer)?;
+
+    let round = tournament.round;
+    tournament.record_winner(match_index, winner, now)?;
+    emit_cpi!(TournamentMatchDecided {
+        tournament: tournament.key(),
+        round,
+        match_index,
+        winner,
+        loser,
+        game: None,
+    });
+    if let Some(champion) = tournament.champion() {
+        emit_cpi!(TournamentFinished {
+            tournament: tournament.key(),
+            champion,
+            prize_pool: tournament.prize_pool,
+            finished_at: now,
+        });
+    } else if tournament.round != round {
+        emit_cpi!(TournamentRoundStarted {
+            tournament: tournament.key(),
+            round: tournament.round,
+            matches: tournament.matches.clone(),
+            started_at: now,
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RockPaperScissorsError, Settings, Tournament, TournamentClosed, SETTINGS, TOURNAMENT,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseTournament<'info> {
+    #[account(
+        mut,
+        close = organizer,
+        seeds = [
+            TOURNAMENT.as_ref(),
+            tournament.organizer.as_ref(),
+            tournament.tournament_id.as_bytes()
+        ],
+        bump = tournament.bump,
+    )]
+    pub tournament: Box<Account<'info, Tournament>>,
+    /// Prizes are swept after the retention period settled games are kept for.
+    #[account(
+        seeds = [SETTINGS.as_ref()],
+        bump = settings.bump,
+    )]
+    pub settings: Account<'info, Settings>,
+
+    #[account(
+        mut,
+        address = tournament.organizer
+    )]
+    pub organizer: Signer<'info>,
+}
+
+/// Returns the rent, any rounding dust of the prize pool and the prizes left
+/// unclaimed to the organizer.
+pub fn processor(ctx: Context<CloseTournament>) -> Result<()> {
+    let clock = Clock::get()?;
+    let tournament = &ctx.accounts.tournament;
+    let settings = &ctx.accounts.settings;
+
+    require!(
+        tournament.is_closable(clock.unix_timestamp, settings.game_retention_period),
+        RockPaperScissorsError::TournamentNotClosable
+    );
+
+    emit_cpi!(TournamentClosed {
+        tournament: tournament.key(),
+        organizer: tournament.organizer,
+        state: tournament.state.clone(),
+        champion: tournament.champion(),
+        unclaimed_prizes: tournament.unclaimed_prizes()?,
+    });
+
+    Ok(())
+}
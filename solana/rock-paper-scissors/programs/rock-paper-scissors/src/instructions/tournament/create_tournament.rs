@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RockPaperScissorsError, Game, GameVariant, Settings, Tournament, TournamentCreated,
+    PAUSE_NEW_GAMES, SETTINGS, TOURNAMENT,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(tournament_id: String, entry_fee: u64, max_players: u8)]
+pub struct CreateTournament<'info> {
+    #[account(
+        init,
+        payer = organizer,
+        space = Tournament::size(max_players),
+        seeds = [TOURNAMENT.as_ref(), organizer.key().as_ref(), tournament_id.as_bytes()],
+        bump,
+    )]
+    pub tournament: Box<Account<'info, Tournament>>,
+    #[account(
+        seeds = [SETTINGS.as_ref()],
+        bump = settings.bump,
+        constraint = !settings.is_paused(PAUSE_NEW_GAMES) @ RockPaperScissorsError::NewGamesPaused,
+    )]
+    pub settings: Box<Account<'info, Settings>>,
+
+    #[account(mut)]
+    pub organizer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn processor(
+    ctx: Context<CreateTournament>,
+    tournament_id: String,
+    entry_fee: u64,
+    max_players: u8,
+    best_of: u8,
+    variant: GameVariant,
+    prize_split_bps: Vec<u16>, // Champion first, adds up to `MAX_BPS`
+    time_for_penalization: Option<i64>, // Within the admin's bounds, default otherwise
+    time_for_stale: Option<i64>,
+) -> Result<()> {
+    require!(
+        Tournament::is_valid_max_players(max_players),
+        RockPaperScissorsError::InvalidTournamentSize
+    );
+    require!(
+        Tournament::is_valid_prize_split(&prize_split_bps, max_players),
+        RockPaperScissorsError::InvalidPrizeSplit
+    );
+    require!(
+        Game::is_valid_best_of(best_of),
+        RockPaperScissorsError::InvalidBestOf
+    );
+
+    let clock = Clock::get()?;
+    let settings = &ctx.accounts.settings;
+    let time_for_penalization = settings.pick_time_for_penalization(time_for_penalization)?;
+    let time_for_stale = settings.pick_time_for_stale(time_for_stale)?;
+
+    let tournament = &mut ctx.accounts.tournament;
+    tournament.set_inner(Tournament::new(
+        ctx.bumps.tournament,
+        ctx.accounts.organizer.key(),
+        tournament_id,
+        entry_fee,
+        max_players,
+        best_of,
+        variant,
+        time_for_penalization,
+        time_for_stale,
+        prize_split_bps,
+        clock.unix_timestamp,
+    ));
+
+    emit_cpi!(TournamentCreated {
+        tournament: tournament.key(),
+        tournament_id: tournament.tournament_id.clone(),
+        organizer: tournament.organizer,
+        entry_fee,
+        max_players,
+        best_of,
+        variant: tournament.variant.clone(),
+        time_for_penalization,
+        time_for_stale,
+        prize_split_bps: tournament.prize_split_bps.clone(),
+        created_at: tournament.created_at,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,105 @@
+use anchor_lang::{prelude::*, system_program};
+
+use crate::{
+    error::RockPaperScissorsError, Game, GameCreated, JoinRestriction, RakeTerms, Tournament,
+    TournamentGameCreated, WagerMode, GAME, TOURNAMENT,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(match_index: u8, game_id: String)]
+pub struct CreateTournamentGame<'info> {
+    #[account(
+        mut,
+        seeds = [
+            TOURNAMENT.as_ref(),
+            tournament.organizer.as_ref(),
+            tournament.tournament_id.as_bytes()
+        ],
+        bump = tournament.bump,
+        constraint = game_id == Tournament::game_id(&tournament.key(), tournament.round, match_index) @ RockPaperScissorsError::InvalidMatch,
+    )]
+    pub tournament: Box<Account<'info, Tournament>>,
+    #[account(
+        init,
+        payer = player,
+        space = Game::size(tournament.best_of),
+        seeds = [GAME.as_ref(), player.key().as_ref(), game_id.as_bytes()],
+        bump,
+    )]
+    pub game: Box<Account<'info, Game>>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Either player of a match opens its game, only their opponent can join.
+pub fn processor(
+    ctx: Context<CreateTournamentGame>,
+    match_index: u8,
+    game_id: String, // `Tournament::game_id` of the current round and match
+    hash: [u8; 32],  // Commitment, see `CommitmentScheme`
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let tournament = &mut ctx.accounts.tournament;
+    let game = &mut ctx.accounts.game;
+    let player = &ctx.accounts.player;
+
+    let opponent = tournament
+        .match_at(match_index)?
+        .opponent_of(&player.key())
+        .ok_or(RockPaperScissorsError::InvalidPlayer)?;
+    let game_key = game.key();
+    tournament.set_game(match_index, game_key)?;
+
+    game.set_inner(Game::new(
+        ctx.bumps.game,
+        game_id,
+        Pubkey::default(),
+        system_program::ID,
+        0,
+        player.key(),
+        hash,
+        game_key,
+        clock.unix_timestamp,
+        tournament.best_of,
+        tournament.variant.clone(),
+        None,
+        WagerMode::Tournament,
+        tournament.time_for_penalization,
+        tournament.time_for_stale,
+        0,
+        // Tournament games hold no wagers to rake.
+        RakeTerms::default(),
+    ));
+    game.join_restriction = Some(JoinRestriction::Opponent(opponent));
+
+    emit_cpi!(GameCreated {
+        game: game_key,
+        game_id: game.game_id.clone(),
+        first_player: game.first_player,
+        mint: game.mint,
+        amount_to_match: game.amount_to_match,
+        player_fee_lamports: 0,
+        best_of: game.best_of,
+        variant: game.variant.clone(),
+        join_deadline: game.join_deadline,
+        created_at: game.created_at,
+        time_for_penalization: tournament.time_for_penalization,
+        time_for_stale: tournament.time_for_stale,
+        join_restriction: game.join_restriction.clone(),
+        lobby: None,
+        rating_band: None,
+        season: None,
+    });
+    emit_cpi!(TournamentGameCreated {
+        tournament: tournament.key(),
+        round: tournament.round,
+        match_index,
+        game: game_key,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RockPaperScissorsError, Game, GameJoined, GameState, Tournament, WagerMode, GAME,
+    TOURNAMENT,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct JoinTournamentGame<'info> {
+    #[account(
+        seeds = [
+            TOURNAMENT.as_ref(),
+            tournament.organizer.as_ref(),
+            tournament.tournament_id.as_bytes()
+        ],
+        bump = tournament.bump,
+    )]
+    pub tournament: Box<Account<'info, Tournament>>,
+    #[account(
+        mut,
+        seeds = [GAME.as_ref(), game.first_player.key().as_ref(), game.game_id.as_bytes()],
+        bump = game.bump,
+        constraint = game.state == GameState::Created @ RockPaperScissorsError::InvalidGameState,
+        constraint = game.wager_mode == WagerMode::Tournament @ RockPaperScissorsError::InvalidWagerMode,
+    )]
+    pub game: Box<Account<'info, Game>>,
+
+    pub player: Signer<'info>,
+}
+
+pub fn processor(
+    ctx: Context<JoinTournamentGame>,
+    match_index: u8,
+    hash: [u8; 32], // Commitment, see `CommitmentScheme`
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let tournament = &ctx.accounts.tournament;
+    let game = &mut ctx.accounts.game;
+    let player = &ctx.accounts.player;
+
+    let game_key = game.key();
+    require!(
+        tournament.match_at(match_index)?.game == Some(game_key),
+        RockPaperScissorsError::InvalidMatch
+    );
+    // The game's `Opponent` restriction keeps anyone else out.
+    game.join_game(player.key(), hash, game_key, clock.unix_timestamp, &[])?;
+
+    emit_cpi!(GameJoined {
+        game: game_key,
+        first_player: game.first_player,
+        second_player: player.key(),
+        mint: game.mint,
+        amount_to_match: game.amount_to_match,
+        player_fee_lamports: 0,
+        started_at: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::{withdraw_lamports, Tournament, TournamentPlayerLeft, TOURNAMENT};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct LeaveTournament<'info> {
+    #[account(
+        mut,
+        seeds = [
+            TOURNAMENT.as_ref(),
+            tournament.organizer.as_ref(),
+            tournament.tournament_id.as_bytes()
+        ],
+        bump = tournament.bump,
+    )]
+    pub tournament: Box<Account<'info, Tournament>>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+}
+
+/// Refunds the entry fee, only possible before the tournament starts.
+pub fn processor(ctx: Context<LeaveTournament>) -> Result<()> {
+    let tournament = &mut ctx.accounts.tournament;
+    let player = &ctx.accounts.player;
+
+    tournament.leave(&player.key())?;
+    withdraw_lamports(
+        &tournament.to_account_info(),
+        &player.to_account_info(),
+        tournament.entry_fee,
+    )?;
+
+    emit_cpi!(TournamentPlayerLeft {
+        tournament: tournament.key(),
+        player: player.key(),
+        refund: tournament.entry_fee,
+        players: tournament.players.len() as u8,
+    });
+
+    Ok(())
+}
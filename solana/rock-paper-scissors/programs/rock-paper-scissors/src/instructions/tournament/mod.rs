@@ -0,0 +1,21 @@
+pub mod claim_tournament_prize;
+pub mod claim_tournament_walkover;
+pub mod close_tournament;
+pub mod create_tournament;
+pub mod create_tournament_game;
+pub mod join_tournament_game;
+pub mod leave_tournament;
+pub mod register_for_tournament;
+pub mod settle_tournament_game;
+pub mod start_tournament;
+
+pub use claim_tournament_prize::*;
+pub use claim_tournament_walkover::*;
+pub use close_tournament::*;
+pub use create_tournament::*;
+pub use create_tournament_game::*;
+pub use join_tournament_game::*;
+pub use leave_tournament::*;
+pub use register_for_tournament::*;
+pub use settle_tournament_game::*;
+pub use start_tournament::*;
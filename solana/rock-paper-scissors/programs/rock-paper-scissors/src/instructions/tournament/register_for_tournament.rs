@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RockPaperScissorsError, transfer_lamports, Settings, Tournament,
+    TournamentPlayerRegistered, TransferLamports, PAUSE_JOINS, SETTINGS, TOURNAMENT,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RegisterForTournament<'info> {
+    #[account(
+        mut,
+        seeds = [
+            TOURNAMENT.as_ref(),
+            tournament.organizer.as_ref(),
+            tournament.tournament_id.as_bytes()
+        ],
+        bump = tournament.bump,
+    )]
+    pub tournament: Box<Account<'info, Tournament>>,
+    #[account(
+        seeds = [SETTINGS.as_ref()],
+        bump = settings.bump,
+        constraint = !settings.is_paused(PAUSE_JOINS) @ RockPaperScissorsError::JoinsPaused,
+    )]
+    pub settings: Box<Account<'info, Settings>>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Escrows the entry fee in the tournament account.
+pub fn processor(ctx: Context<RegisterForTournament>) -> Result<()> {
+    let tournament = &mut ctx.accounts.tournament;
+    let player = &ctx.accounts.player;
+
+    tournament.register(player.key())?;
+    transfer_lamports(
+        TransferLamports {
+            from: player.to_account_info(),
+            to: tournament.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        },
+        tournament.entry_fee,
+    )?;
+
+    emit_cpi!(TournamentPlayerRegistered {
+        tournament: tournament.key(),
+        player: player.key(),
+        entry_fee: tournament.entry_fee,
+        players: tournament.players.len() as u8,
+    });
+
+    Ok(())
+}
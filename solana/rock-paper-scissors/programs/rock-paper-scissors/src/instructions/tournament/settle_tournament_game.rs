@@ -0,0 +1,144 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RockPaperScissorsError, Game, GameClosed, GameSettled, GameState, Player, Settings,
+    Tournament, TournamentFinished, TournamentMatchDecided, TournamentMatchReplayed,
+    TournamentRoundStarted, WagerMode, GAME, SETTINGS, TOURNAMENT,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SettleTournamentGame<'info> {
+    #[account(
+        mut,
+        seeds = [
+            TOURNAMENT.as_ref(),
+            tournament.organizer.as_ref(),
+            tournament.tournament_id.as_bytes()
+        ],
+        bump = tournament.bump,
+    )]
+    pub tournament: Box<Account<'info, Tournament>>,
+    #[account(
+        mut,
+        close = first_player,
+        seeds = [GAME.as_ref(), game.first_player.as_ref(), game.game_id.as_bytes()],
+        bump = game.bump,
+        constraint = game.state == GameState::Started @ RockPaperScissorsError::InvalidGameState,
+        constraint = game.wager_mode == WagerMode::Tournament @ RockPaperScissorsError::InvalidWagerMode,
+    )]
+    pub game: Box<Account<'info, Game>>,
+    #[account(
+        seeds = [SETTINGS.as_ref()],
+        bump = settings.bump,
+    )]
+    pub settings: Box<Account<'info, Settings>>,
+
+    /// CHECK: No check needed, gets the game's rent back.
+    #[account(
+        mut,
+        address = game.first_player
+    )]
+    pub first_player: AccountInfo<'info>,
+
+    /// Not checked, so this call is permissionless.
+    pub signer: Signer<'info>,
+}
+
+/// Decides the match with the game's result, forfeits included, and closes
+/// the game. A draw or a stale game leaves the match to be replayed.
+pub fn processor(ctx: Context<SettleTournamentGame>, match_index: u8) -> Result<()> {
+    let clock = Clock::get()?;
+    let tournament = &mut ctx.accounts.tournament;
+    let game = &mut ctx.accounts.game;
+    let settings = &ctx.accounts.settings;
+    let now = clock.unix_timestamp;
+
+    let game_key = game.key();
+    require!(
+        tournament.match_at(match_index)?.game == Some(game_key),
+        RockPaperScissorsError::InvalidMatch
+    );
+
+    let winner = if game.is_stale(now, game.time_for_stale(settings)) {
+        game.set_claimed(&None, 0, now);
+        None
+    } else {
+        // Nothing is staked, so there's nothing to pay out.
+        game.settle(now, settings, 0, 0)?.winner
+    };
+    // Unwrapping here is fine since the game was started.
+    let second_player = game.second_player.unwrap();
+
+    emit_cpi!(GameSettled {
+        game: game_key,
+        first_player: game.first_player,
+        second_player,
+        mint: game.mint,
+        amount_to_match: game.amount_to_match,
+        winner: winner.clone(),
+        amount_won: game.amount_won,
+        rake: 0,
+        settled_at: now,
+    });
+
+    let round = tournament.round;
+    match winner {
+        None => {
+            tournament.clear_game(match_index, now)?;
+            emit_cpi!(TournamentMatchReplayed {
+                tournament: tournament.key(),
+                round,
+                match_index,
+                game: game_key,
+            });
+        }
+        Some(winner) => {
+            let (winner, loser) = match winner {
+                Player::First => (game.first_player, second_player),
+                Player::Second => (second_player, game.first_player),
+            };
+            tournament.record_winner(match_index, winner, now)?;
+            emit_cpi!(TournamentMatchDecided {
+                tournament: tournament.key(),
+                round,
+                match_index,
+                winner,
+                loser,
+                game: Some(game_key),
+            });
+            if let Some(champion) = tournament.champion() {
+                emit_cpi!(TournamentFinished {
+                    tournament: tournament.key(),
+                    champion,
+                    prize_pool: tournament.prize_pool,
+                    finished_at: now,
+                });
+            } else if tournament.round != round {
+                emit_cpi!(TournamentRoundStarted {
+                    tournament: tournament.key(),
+                    round: tournament.round,
+                    matches: tournament.matches.clone(),
+                    started_at: now,
+                });
+            }
+        }
+    }
+
+    emit_cpi!(GameClosed {
+        game: game_key,
+        game_id: game.game_id.clone(),
+        mint: game.mint,
+        amount_to_match: game.amount_to_match,
+        first_player: game.first_player,
+        second_player: game.second_player,
+        state: game.state.clone(),
+        amount_won: game.amount_won,
+        rounds: game.rounds.clone(),
+        created_at: game.created_at,
+        settled_at: game.drawn_at,
+        closed_at: now,
+    });
+
+    Ok(())
+}
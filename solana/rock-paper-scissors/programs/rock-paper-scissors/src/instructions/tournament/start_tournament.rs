@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::{Tournament, TournamentRoundStarted, TOURNAMENT};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct StartTournament<'info> {
+    #[account(
+        mut,
+        seeds = [
+            TOURNAMENT.as_ref(),
+            tournament.organizer.as_ref(),
+            tournament.tournament_id.as_bytes()
+        ],
+        bump = tournament.bump,
+    )]
+    pub tournament: Box<Account<'info, Tournament>>,
+
+    #[account(address = tournament.organizer)]
+    pub organizer: Signer<'info>,
+}
+
+pub fn processor(
+    ctx: Context<StartTournament>,
+    seeding: Vec<u8>, // Registration index of each bracket slot, slots 2n and 2n+1 meet first
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let tournament = &mut ctx.accounts.tournament;
+
+    tournament.start(&seeding, clock.unix_timestamp)?;
+
+    emit_cpi!(TournamentRoundStarted {
+        tournament: tournament.key(),
+        round: tournament.round,
+        matches: tournament.matches.clone(),
+        started_at: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -2,6 +2,7 @@
 #![allow(ambiguous_glob_reexports)]
 pub mod constants;
 pub mod error;
+pub mod events;
 pub mod instructions;
 pub mod state;
 pub mod tools;
@@ -9,6 +10,7 @@ pub mod tools;
 use anchor_lang::prelude::*;
 
 pub use constants::*;
+pub use events::*;
 pub use instructions::*;
 pub use state::*;
 pub use tools::*;
@@ -19,63 +21,394 @@ declare_id!("rock7uz5eZdz8fb1ZEfZ1aR428ncvkYBrgebwyzaqBG");
 pub mod rock_paper_scissors {
     use super::*;
 
+    #[allow(clippy::too_many_arguments)]
     pub fn initialize_settings(
         ctx: Context<InitializeSettings>,
         time_for_penalization: i64,
         time_for_stale: i64,
         player_fee_lamports: u64,
+        rake_bps: u16,
+        rake_draws: bool,
+        game_retention_period: i64,
+        treasury: Pubkey,
     ) -> Result<()> {
         initialize_settings::processor(
             ctx,
             time_for_penalization,
             time_for_stale,
             player_fee_lamports,
+            rake_bps,
+            rake_draws,
+            game_retention_period,
+            treasury,
         )
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn update_settings(
         ctx: Context<UpdateSettings>,
         time_for_penalization: i64,
         time_for_stale: i64,
         player_fee_lamports: u64,
+        rake_bps: u16,
+        rake_draws: bool,
+        game_retention_period: i64,
+        treasury: Pubkey,
     ) -> Result<()> {
         update_settings::processor(
             ctx,
             time_for_penalization,
             time_for_stale,
             player_fee_lamports,
+            rake_bps,
+            rake_draws,
+            game_retention_period,
+            treasury,
         )
     }
 
-    pub fn initialize_game(
-        ctx: Context<InitializeGame>,
+    pub fn update_window_bounds(
+        ctx: Context<UpdateWindowBounds>,
+        min_time_for_penalization: i64,
+        max_time_for_penalization: i64,
+        min_time_for_stale: i64,
+        max_time_for_stale: i64,
+    ) -> Result<()> {
+        update_window_bounds::processor(
+            ctx,
+            min_time_for_penalization,
+            max_time_for_penalization,
+            min_time_for_stale,
+            max_time_for_stale,
+        )
+    }
+
+    pub fn migrate_settings(ctx: Context<MigrateSettings>) -> Result<()> {
+        migrate_settings::processor(ctx)
+    }
+
+    pub fn propose_admin(ctx: Context<ProposeAdmin>, pending_admin: Option<Pubkey>) -> Result<()> {
+        propose_admin::processor(ctx, pending_admin)
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        accept_admin::processor(ctx)
+    }
+
+    pub fn set_paused(ctx: Context<SetPaused>, paused: u8) -> Result<()> {
+        set_paused::processor(ctx, paused)
+    }
+
+    pub fn set_referral_share(
+        ctx: Context<SetReferralShare>,
+        referral_share_bps: u16,
+    ) -> Result<()> {
+        set_referral_share::processor(ctx, referral_share_bps)
+    }
+
+    pub fn initialize_mint_config(
+        ctx: Context<InitializeMintConfig>,
+        mint: Pubkey, // `Pubkey::default()` for native SOL
+        enabled: bool,
+        min_amount: u64,
+        max_amount: u64,
+        player_fee_lamports: Option<u64>,
+    ) -> Result<()> {
+        initialize_mint_config::processor(
+            ctx,
+            mint,
+            enabled,
+            min_amount,
+            max_amount,
+            player_fee_lamports,
+        )
+    }
+
+    pub fn update_mint_config(
+        ctx: Context<UpdateMintConfig>,
+        enabled: bool,
+        min_amount: u64,
+        max_amount: u64,
+        player_fee_lamports: Option<u64>,
+    ) -> Result<()> {
+        update_mint_config::processor(ctx, enabled, min_amount, max_amount, player_fee_lamports)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn initialize_game<'info>(
+        ctx: Context<'_, '_, '_, 'info, InitializeGame<'info>>,
+        game_id: String,
+        amount: u64,
+        hash: [u8; 32], // Commitment, see `CommitmentScheme`
+        best_of: u8,
+        variant: GameVariant,
+        join_deadline: Option<i64>,
+        time_for_penalization: Option<i64>,
+        time_for_stale: Option<i64>,
+        join_restriction: Option<JoinRestriction>,
+        rating_band: Option<RatingBand>,
+    ) -> Result<()> {
+        initialize_game::processor(
+            ctx,
+            game_id,
+            amount,
+            hash,
+            best_of,
+            variant,
+            join_deadline,
+            time_for_penalization,
+            time_for_stale,
+            join_restriction,
+            rating_band,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn initialize_native_game(
+        ctx: Context<InitializeNativeGame>,
         game_id: String,
         amount: u64,
-        hash: [u8; 32], // Choice + Salt
+        hash: [u8; 32], // Commitment, see `CommitmentScheme`
+        best_of: u8,
+        variant: GameVariant,
+        join_deadline: Option<i64>,
+        time_for_penalization: Option<i64>,
+        time_for_stale: Option<i64>,
+        join_restriction: Option<JoinRestriction>,
+        rating_band: Option<RatingBand>,
     ) -> Result<()> {
-        initialize_game::processor(ctx, game_id, amount, hash)
+        initialize_native_game::processor(
+            ctx,
+            game_id,
+            amount,
+            hash,
+            best_of,
+            variant,
+            join_deadline,
+            time_for_penalization,
+            time_for_stale,
+            join_restriction,
+            rating_band,
+        )
     }
 
-    pub fn join_game(
-        ctx: Context<JoinGame>,
-        hash: [u8; 32], // Choice + Salt
+    pub fn initialize_player_profile(ctx: Context<InitializePlayerProfile>) -> Result<()> {
+        initialize_player_profile::processor(ctx)
+    }
+
+    pub fn join_game<'info>(
+        ctx: Context<'_, '_, '_, 'info, JoinGame<'info>>,
+        hash: [u8; 32],       // Commitment, see `CommitmentScheme`
+        proof: Vec<[u8; 32]>, // Merkle proof for invite lists, empty otherwise
     ) -> Result<()> {
-        join_game::processor(ctx, hash)
+        join_game::processor(ctx, hash, proof)
     }
 
-    pub fn cancel_game(ctx: Context<CancelGame>) -> Result<()> {
+    pub fn join_native_game(
+        ctx: Context<JoinNativeGame>,
+        hash: [u8; 32],       // Commitment, see `CommitmentScheme`
+        proof: Vec<[u8; 32]>, // Merkle proof for invite lists, empty otherwise
+    ) -> Result<()> {
+        join_native_game::processor(ctx, hash, proof)
+    }
+
+    pub fn cancel_game<'info>(ctx: Context<'_, '_, '_, 'info, CancelGame<'info>>) -> Result<()> {
         cancel_game::processor(ctx)
     }
 
-    pub fn unwind_game(ctx: Context<UnwindGame>) -> Result<()> {
+    pub fn cancel_native_game(ctx: Context<CancelNativeGame>) -> Result<()> {
+        cancel_native_game::processor(ctx)
+    }
+
+    pub fn expire_game<'info>(ctx: Context<'_, '_, '_, 'info, ExpireGame<'info>>) -> Result<()> {
+        expire_game::processor(ctx)
+    }
+
+    pub fn expire_native_game(ctx: Context<ExpireNativeGame>) -> Result<()> {
+        expire_native_game::processor(ctx)
+    }
+
+    pub fn unwind_game<'info>(ctx: Context<'_, '_, '_, 'info, UnwindGame<'info>>) -> Result<()> {
         unwind_game::processor(ctx)
     }
 
+    pub fn unwind_native_game(ctx: Context<UnwindNativeGame>) -> Result<()> {
+        unwind_native_game::processor(ctx)
+    }
+
+    pub fn commit_choice(
+        ctx: Context<CommitChoice>,
+        hash: [u8; 32], // Commitment, see `CommitmentScheme`
+    ) -> Result<()> {
+        commit_choice::processor(ctx, hash)
+    }
+
     pub fn reveal_choice(ctx: Context<RevealChoice>, choice: Choice, salt: [u8; 32]) -> Result<()> {
         reveal_choice::processor(ctx, choice, salt)
     }
 
-    pub fn settle_game(ctx: Context<SettleGame>) -> Result<()> {
+    pub fn settle_game<'info>(ctx: Context<'_, '_, '_, 'info, SettleGame<'info>>) -> Result<()> {
         settle_game::processor(ctx)
     }
+
+    pub fn settle_native_game(ctx: Context<SettleNativeGame>) -> Result<()> {
+        settle_native_game::processor(ctx)
+    }
+
+    pub fn close_game(ctx: Context<CloseGame>) -> Result<()> {
+        close_game::processor(ctx)
+    }
+
+    pub fn migrate_game(ctx: Context<MigrateGame>) -> Result<()> {
+        migrate_game::processor(ctx)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_tournament(
+        ctx: Context<CreateTournament>,
+        tournament_id: String,
+        entry_fee: u64,
+        max_players: u8,
+        best_of: u8,
+        variant: GameVariant,
+        prize_split_bps: Vec<u16>,
+        time_for_penalization: Option<i64>,
+        time_for_stale: Option<i64>,
+    ) -> Result<()> {
+        create_tournament::processor(
+            ctx,
+            tournament_id,
+            entry_fee,
+            max_players,
+            best_of,
+            variant,
+            prize_split_bps,
+            time_for_penalization,
+            time_for_stale,
+        )
+    }
+
+    pub fn register_for_tournament(ctx: Context<RegisterForTournament>) -> Result<()> {
+        register_for_tournament::processor(ctx)
+    }
+
+    pub fn leave_tournament(ctx: Context<LeaveTournament>) -> Result<()> {
+        leave_tournament::processor(ctx)
+    }
+
+    pub fn start_tournament(ctx: Context<StartTournament>, seeding: Vec<u8>) -> Result<()> {
+        start_tournament::processor(ctx, seeding)
+    }
+
+    pub fn create_tournament_game(
+        ctx: Context<CreateTournamentGame>,
+        match_index: u8,
+        game_id: String,
+        hash: [u8; 32],
+    ) -> Result<()> {
+        create_tournament_game::processor(ctx, match_index, game_id, hash)
+    }
+
+    pub fn join_tournament_game(
+        ctx: Context<JoinTournamentGame>,
+        match_index: u8,
+        hash: [u8; 32],
+    ) -> Result<()> {
+        join_tournament_game::processor(ctx, match_index, hash)
+    }
+
+    pub fn settle_tournament_game(
+        ctx: Context<SettleTournamentGame>,
+        match_index: u8,
+    ) -> Result<()> {
+        settle_tournament_game::processor(ctx, match_index)
+    }
+
+    pub fn claim_tournament_walkover(
+        ctx: Context<ClaimTournamentWalkover>,
+        match_index: u8,
+    ) -> Result<()> {
+        claim_tournament_walkover::processor(ctx, match_index)
+    }
+
+    pub fn claim_tournament_prize(ctx: Context<ClaimTournamentPrize>) -> Result<()> {
+        claim_tournament_prize::processor(ctx)
+    }
+
+    pub fn close_tournament(ctx: Context<CloseTournament>) -> Result<()> {
+        close_tournament::processor(ctx)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_season(
+        ctx: Context<CreateSeason>,
+        season_id: u32,
+        leaderboard_size: u8,
+        start_at: i64,
+        end_at: i64,
+        ranking: SeasonRanking,
+        fee_share_bps: u16,
+        prize_split_bps: Vec<u16>,
+    ) -> Result<()> {
+        create_season::processor(
+            ctx,
+            season_id,
+            leaderboard_size,
+            start_at,
+            end_at,
+            ranking,
+            fee_share_bps,
+            prize_split_bps,
+        )
+    }
+
+    pub fn join_season(ctx: Context<JoinSeason>) -> Result<()> {
+        join_season::processor(ctx)
+    }
+
+    pub fn rank_season_entry(ctx: Context<RankSeasonEntry>) -> Result<()> {
+        rank_season_entry::processor(ctx)
+    }
+
+    pub fn claim_season_prize(ctx: Context<ClaimSeasonPrize>) -> Result<()> {
+        claim_season_prize::processor(ctx)
+    }
+
+    pub fn sweep_season_prizes(ctx: Context<SweepSeasonPrizes>) -> Result<()> {
+        sweep_season_prizes::processor(ctx)
+    }
+
+    pub fn register_referrer(ctx: Context<RegisterReferrer>) -> Result<()> {
+        register_referrer::processor(ctx)
+    }
+
+    pub fn claim_referral_rewards(ctx: Context<ClaimReferralRewards>) -> Result<()> {
+        claim_referral_rewards::processor(ctx)
+    }
+
+    pub fn claim_referral_token_rewards<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimReferralTokenRewards<'info>>,
+    ) -> Result<()> {
+        claim_referral_token_rewards::processor(ctx)
+    }
+
+    pub fn open_side_pool(ctx: Context<OpenSidePool>) -> Result<()> {
+        open_side_pool::processor(ctx)
+    }
+
+    pub fn place_side_bet(
+        ctx: Context<PlaceSideBet>,
+        outcome: SideBetOutcome,
+        amount: u64,
+    ) -> Result<()> {
+        place_side_bet::processor(ctx, outcome, amount)
+    }
+
+    pub fn claim_side_bet(ctx: Context<ClaimSideBet>) -> Result<()> {
+        claim_side_bet::processor(ctx)
+    }
+
+    pub fn close_side_pool(ctx: Context<CloseSidePool>) -> Result<()> {
+        close_side_pool::processor(ctx)
+    }
 }
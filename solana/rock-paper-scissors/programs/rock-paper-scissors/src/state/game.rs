@@ -1,6 +1,12 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{
+    prelude::*,
+    solana_program::hash::{hashv, Hash},
+};
 
-use crate::{error::RockPaperScissorsError, Settings};
+use crate::{
+    error::RockPaperScissorsError, invite_leaf, vec_to_arr_of_n, verify_merkle_proof, GameReferral,
+    GameVariant, MintConfig, RakeTerms, Settings, COMMITMENT_DOMAIN, MAX_BEST_OF, MAX_MATCH_ROUNDS,
+};
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
 pub enum Player {
@@ -25,6 +31,157 @@ pub enum Choice {
     Rock,
     Paper,
     Scissors,
+    Lizard,
+    Spock,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub enum CommitmentScheme {
+    /// `sha256(choice || salt)`. Only kept so games created before the bound
+    /// scheme existed can still be revealed and settled.
+    Legacy,
+    /// `sha256(COMMITMENT_DOMAIN || game || player || choice || salt)`, so a
+    /// commitment can't be replayed by another player or in another game.
+    Bound,
+}
+
+impl CommitmentScheme {
+    pub fn hash(&self, game: &Pubkey, player: &Pubkey, choice: &Choice, salt: &[u8; 32]) -> Hash {
+        let choice_bytes = choice.clone() as u8;
+        match self {
+            Self::Legacy => {
+                let val_to_hash = vec_to_arr_of_n::<u8, 33>([&[choice_bytes], &salt[..]].concat());
+                hashv(&[&val_to_hash])
+            }
+            Self::Bound => hashv(&[
+                &COMMITMENT_DOMAIN,
+                game.as_ref(),
+                player.as_ref(),
+                &[choice_bytes],
+                salt,
+            ]),
+        }
+    }
+}
+
+/// A resolved round of a match, kept on the game so clients can render the
+/// match history.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct Round {
+    pub first_player_choice: Choice,
+    pub first_player_revealed_at: i64,
+    pub second_player_choice: Choice,
+    pub second_player_revealed_at: i64,
+    /// `None` if the round was a tie.
+    pub winner: Option<Player>,
+}
+
+impl Round {
+    pub fn size() -> usize {
+        1 + // First player choice
+        8 + // First player revealed at
+        1 + // Second player choice
+        8 + // Second player revealed at
+        (1 + 1) // Winner
+    }
+
+    pub fn resolved_at(&self) -> i64 {
+        self.first_player_revealed_at
+            .max(self.second_player_revealed_at)
+    }
+}
+
+/// What the players stake.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub enum WagerMode {
+    /// SPL or Token-2022 tokens, held in one escrow token account per player.
+    Token,
+    /// Lamports, held by the game account itself on top of its rent.
+    Native,
+    /// Nothing, the game decides a `Tournament` match and is settled through
+    /// it.
+    Tournament,
+}
+
+/// Who may take the second seat of an invite-only game.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub enum JoinRestriction {
+    Opponent(Pubkey),
+    /// Root of a merkle tree of `invite_leaf`s, joiners prove they're in it.
+    MerkleRoot([u8; 32]),
+}
+
+impl JoinRestriction {
+    pub fn size() -> usize {
+        1 + 32
+    }
+
+    pub fn allows(&self, player: &Pubkey, proof: &[[u8; 32]]) -> bool {
+        match self {
+            Self::Opponent(opponent) => opponent == player,
+            Self::MerkleRoot(root) => verify_merkle_proof(root, invite_leaf(player), proof),
+        }
+    }
+}
+
+/// Ratings, both inclusive, a player needs to join a game, see `PlayerRating`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct RatingBand {
+    pub min: u32,
+    pub max: u32,
+}
+
+impl RatingBand {
+    pub fn size() -> usize {
+        4 + 4
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.min <= self.max
+    }
+
+    pub fn contains(&self, rating: u32) -> bool {
+        (self.min..=self.max).contains(&rating)
+    }
+}
+
+/// How one escrow is emptied when a game settles.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EscrowPayout {
+    /// Paid to the winner, or back to the escrow's owner on a draw.
+    pub payout: u64,
+    /// Paid to the treasury.
+    pub rake: u64,
+}
+
+/// Amounts moved out of each escrow when a game settles.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Settlement {
+    /// `None` on a draw.
+    pub winner: Option<Player>,
+    pub first_player: EscrowPayout,
+    pub second_player: EscrowPayout,
+}
+
+impl Settlement {
+    /// Everything `player` is paid out of both escrows, `amount_won` being
+    /// the game's.
+    pub fn paid_out_to(&self, player: &Player, amount_won: Option<u64>) -> u64 {
+        match (&self.winner, player) {
+            (None, Player::First) => self.first_player.payout,
+            (None, Player::Second) => self.second_player.payout,
+            (Some(winner), _) if winner == player => amount_won.unwrap_or_default(),
+            (Some(_), _) => 0,
+        }
+    }
+
+    /// Rake taken across both escrows.
+    pub fn rake(&self) -> Result<u64> {
+        self.first_player
+            .rake
+            .checked_add(self.second_player.rake)
+            .ok_or(RockPaperScissorsError::NumericOverflow.into())
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
@@ -36,17 +193,23 @@ pub enum GameState {
     Draw,
 }
 
+/// Fields are only ever appended. Games created before the latest ones are
+/// too short to decode until `migrate_game` grows them, zeroing the new
+/// fields.
 #[account]
 pub struct Game {
     pub bump: u8,
     pub game_id: String,
+    /// `Pubkey::default()` for native wagers.
     pub mint: Pubkey,
+    /// The system program for native wagers.
     pub token_program: Pubkey,
 
     pub amount_to_match: u64,
 
     pub first_player: Pubkey,
     pub first_player_hash: [u8; 32],
+    /// The game itself for native wagers.
     pub first_player_escrow: Pubkey,
     pub first_player_choice: Option<Choice>,
     pub first_player_revealed_at: Option<i64>,
@@ -62,10 +225,72 @@ pub struct Game {
 
     pub state: GameState,
     pub created_at: i64,
+
+    /// `Legacy` for games created before commitments were bound to the game
+    /// and player.
+    pub commitment_scheme: CommitmentScheme,
+
+    /// Number of rounds in the match, a single throw is a best of 1.
+    pub best_of: u8,
+    pub first_player_wins: u8,
+    pub second_player_wins: u8,
+    /// When each player committed to the current round.
+    pub first_player_committed_at: Option<i64>,
+    pub second_player_committed_at: Option<i64>,
+    /// Resolved rounds, ties included.
+    pub rounds: Vec<Round>,
+
+    pub variant: GameVariant,
+
+    /// After this, nobody can join and anyone can expire the game.
+    pub join_deadline: Option<i64>,
+
+    /// When the second player joined.
+    pub started_at: Option<i64>,
+
+    /// `Token` on games migrated from before native wagers.
+    pub wager_mode: WagerMode,
+
+    /// What reached each escrow, below `amount_to_match` for mints with
+    /// transfer fees.
+    pub first_player_deposit: Option<u64>,
+    pub second_player_deposit: Option<u64>,
+
+    /// Rules fixed at creation, so settings changes don't reach games in
+    /// progress. `None` on games migrated from before they were, which follow
+    /// the live settings instead.
+    pub time_for_penalization: Option<i64>,
+    pub time_for_stale: Option<i64>,
+    pub player_fee_lamports: Option<u64>,
+
+    /// `None` for games anyone can join.
+    pub join_restriction: Option<JoinRestriction>,
+
+    /// Lobby the game was listed in while open, `None` for invite-only games
+    /// and games migrated from before lobbies existed.
+    pub lobby: Option<Pubkey>,
+
+    /// `None` for games open to every rating.
+    pub rating_band: Option<RatingBand>,
+
+    /// Season the game counts towards, `None` outside of seasons.
+    pub season: Option<Pubkey>,
+
+    /// Referrers of players who registered one, paid a share of the rake
+    /// taken from that player's escrow.
+    pub first_player_referral: Option<GameReferral>,
+    pub second_player_referral: Option<GameReferral>,
+
+    /// Spectators' `SidePool`, resolved when the game settles or unwinds.
+    pub side_pool: Option<Pubkey>,
+
+    /// Rake fixed at creation like the rules above, `None` on games migrated
+    /// from before it was, which follow the live settings instead.
+    pub rake_terms: Option<RakeTerms>,
 }
 
 impl Game {
-    pub fn size() -> usize {
+    pub fn size(best_of: u8) -> usize {
         8 + // Discriminator
         1 + // Bump
         (4 + 32) + // Game ID
@@ -89,9 +314,63 @@ impl Game {
         (1 + 8) + // Drawn at
 
         1 + // State
-        8 // Created at
+        8 + // Created at
+
+        1 + // Commitment scheme
+
+        1 + // Best of
+        1 + // First player wins
+        1 + // Second player wins
+        (1 + 8) + // First player committed at
+        (1 + 8) + // Second player committed at
+        (4 + Self::max_rounds_for(best_of) * Round::size()) + // Rounds
+
+        1 + // Variant
+
+        (1 + 8) + // Join deadline
+
+        (1 + 8) + // Started at
+
+        1 + // Wager mode
+
+        (1 + 8) + // First player deposit
+        (1 + 8) + // Second player deposit
+
+        (1 + 8) + // Time for penalization
+        (1 + 8) + // Time for stale
+        (1 + 8) + // Player fee lamports
+
+        (1 + JoinRestriction::size()) + // Join restriction
+
+        (1 + 32) + // Lobby
+
+        (1 + RatingBand::size()) + // Rating band
+
+        (1 + 32) + // Season
+
+        (1 + GameReferral::size()) + // First player referral
+        (1 + GameReferral::size()) + // Second player referral
+
+        (1 + 32) + // Side pool
+
+        (1 + RakeTerms::size()) // Rake terms
     }
 
+    /// Rounds a match can last, replayed ties included. A single throw ends
+    /// on its first round, draw or not, legacy ones included.
+    pub fn max_rounds_for(best_of: u8) -> usize {
+        if best_of <= 1 {
+            1
+        } else {
+            MAX_MATCH_ROUNDS as usize
+        }
+    }
+
+    pub fn is_valid_best_of(best_of: u8) -> bool {
+        best_of % 2 == 1 && best_of <= MAX_BEST_OF
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         bump: u8,
         game_id: String,
@@ -102,6 +381,14 @@ impl Game {
         first_player_hash: [u8; 32],
         first_player_escrow: Pubkey,
         created_at: i64,
+        best_of: u8,
+        variant: GameVariant,
+        join_deadline: Option<i64>,
+        wager_mode: WagerMode,
+        time_for_penalization: i64,
+        time_for_stale: i64,
+        player_fee_lamports: u64,
+        rake_terms: RakeTerms,
     ) -> Self {
         Self {
             bump,
@@ -127,19 +414,226 @@ impl Game {
 
             state: GameState::Created,
             created_at,
+
+            commitment_scheme: CommitmentScheme::Bound,
+
+            best_of,
+            first_player_wins: 0,
+            second_player_wins: 0,
+            first_player_committed_at: Some(created_at),
+            second_player_committed_at: None,
+            rounds: vec![],
+
+            variant,
+
+            join_deadline,
+
+            started_at: None,
+
+            wager_mode,
+
+            first_player_deposit: None,
+            second_player_deposit: None,
+
+            time_for_penalization: Some(time_for_penalization),
+            time_for_stale: Some(time_for_stale),
+            player_fee_lamports: Some(player_fee_lamports),
+
+            join_restriction: None,
+
+            lobby: None,
+
+            rating_band: None,
+
+            season: None,
+
+            first_player_referral: None,
+            second_player_referral: None,
+
+            side_pool: None,
+
+            rake_terms: Some(rake_terms),
         }
     }
 
+    pub fn is_past_join_deadline(&self, now: i64) -> bool {
+        self.join_deadline
+            .is_some_and(|join_deadline| join_deadline <= now)
+    }
+
     pub fn join_game(
         &mut self,
         second_player: Pubkey,
         second_player_hash: [u8; 32],
         second_player_escrow: Pubkey,
-    ) {
+        joined_at: i64,
+        proof: &[[u8; 32]], // Only for `JoinRestriction::MerkleRoot`
+    ) -> Result<()> {
+        if let Some(restriction) = &self.join_restriction {
+            require!(
+                restriction.allows(&second_player, proof),
+                RockPaperScissorsError::NotInvited
+            );
+        }
+        // A copied commitment would let the second player mirror the first
+        // reveal and force a draw.
+        require!(
+            second_player_hash != self.first_player_hash,
+            RockPaperScissorsError::DuplicateHash
+        );
         self.second_player = Some(second_player);
         self.second_player_hash = Some(second_player_hash);
         self.second_player_escrow = Some(second_player_escrow);
+        self.second_player_committed_at = Some(joined_at);
+        self.started_at = Some(joined_at);
         self.state = GameState::Started;
+        Ok(())
+    }
+
+    /// Commits a player to the current round. The first round is committed
+    /// at `initialize_game`/`join_game`, later rounds go through here.
+    pub fn commit_choice(&mut self, player: Player, hash: [u8; 32], now: i64) -> Result<()> {
+        require!(
+            !self.is_match_over(),
+            RockPaperScissorsError::InvalidGameState
+        );
+        // Legacy single throws don't record their commitments, which must
+        // not be replaced once both players made them.
+        require!(
+            !self.is_round_committed(),
+            RockPaperScissorsError::PlayerAlreadyCommitted
+        );
+        let (committed_at, adversary_committed_at, adversary_hash) = match player {
+            Player::First => (
+                self.first_player_committed_at,
+                self.second_player_committed_at,
+                self.second_player_hash,
+            ),
+            Player::Second => (
+                self.second_player_committed_at,
+                self.first_player_committed_at,
+                Some(self.first_player_hash),
+            ),
+        };
+        require!(
+            committed_at.is_none(),
+            RockPaperScissorsError::PlayerAlreadyCommitted
+        );
+        if adversary_committed_at.is_some() {
+            require!(
+                adversary_hash != Some(hash),
+                RockPaperScissorsError::DuplicateHash
+            );
+        }
+        match player {
+            Player::First => {
+                self.first_player_hash = hash;
+                self.first_player_committed_at = Some(now);
+            }
+            Player::Second => {
+                self.second_player_hash = Some(hash);
+                self.second_player_committed_at = Some(now);
+            }
+        }
+        Ok(())
+    }
+
+    /// Games created before matches existed decode a `best_of` of 0. They're
+    /// single throws, committed at `initialize_game` and `join_game` without
+    /// recording when.
+    pub fn is_legacy_single_throw(&self) -> bool {
+        self.best_of == 0
+    }
+
+    pub fn is_round_committed(&self) -> bool {
+        if self.is_legacy_single_throw() {
+            return self.second_player_hash.is_some();
+        }
+        self.first_player_committed_at.is_some() && self.second_player_committed_at.is_some()
+    }
+
+    /// Records the round of a legacy single throw both players revealed
+    /// before rounds were, which `migrate_game` leaves without any.
+    pub fn resolve_legacy_round(&mut self) {
+        if self.is_legacy_single_throw() && self.rounds.is_empty() {
+            self.resolve_round();
+        }
+    }
+
+    /// Records the current round once both players revealed. Ties are replayed
+    /// unless this is a single throw, and the round state is cleared for the
+    /// next commitments while the match is still undecided.
+    pub fn resolve_round(&mut self) {
+        let (
+            Some(first_player_choice),
+            Some(first_player_revealed_at),
+            Some(second_player_choice),
+            Some(second_player_revealed_at),
+        ) = (
+            self.first_player_choice.clone(),
+            self.first_player_revealed_at,
+            self.second_player_choice.clone(),
+            self.second_player_revealed_at,
+        )
+        else {
+            return;
+        };
+
+        let winner = if first_player_choice == second_player_choice {
+            msg!("Round tied with {:?}", first_player_choice);
+            None
+        } else if self
+            .variant
+            .rules()
+            .beats(&first_player_choice, &second_player_choice)
+        {
+            msg!(
+                "First player wins the round with {:?} against {:?}",
+                first_player_choice,
+                second_player_choice
+            );
+            self.first_player_wins += 1;
+            Some(Player::First)
+        } else {
+            msg!(
+                "Second player wins the round with {:?} against {:?}",
+                second_player_choice,
+                first_player_choice
+            );
+            self.second_player_wins += 1;
+            Some(Player::Second)
+        };
+
+        self.rounds.push(Round {
+            first_player_choice,
+            first_player_revealed_at,
+            second_player_choice,
+            second_player_revealed_at,
+            winner,
+        });
+
+        if !self.is_match_over() {
+            self.first_player_choice = None;
+            self.first_player_revealed_at = None;
+            self.first_player_committed_at = None;
+            self.second_player_choice = None;
+            self.second_player_revealed_at = None;
+            self.second_player_committed_at = None;
+        }
+    }
+
+    pub fn is_match_over(&self) -> bool {
+        let majority = self.best_of / 2 + 1;
+        self.first_player_wins >= majority
+            || self.second_player_wins >= majority
+            || self.rounds.len() >= Self::max_rounds_for(self.best_of)
+    }
+
+    /// Whether either player revealed a choice, in any round.
+    pub fn has_revealed(&self) -> bool {
+        !self.rounds.is_empty()
+            || self.first_player_revealed_at.is_some()
+            || self.second_player_revealed_at.is_some()
     }
 
     pub fn set_player_choice(&mut self, player: Player, choice: Choice, revealed_at: i64) {
@@ -155,31 +649,101 @@ impl Game {
         }
     }
 
-    pub fn did_player_forfeit(&self, player: Player, now: i64, time_for_penalization: i64) -> bool {
-        let (player_revealed_at, adversary_revealed_at) = match player {
-            Player::First => (
+    /// When the player and their adversary acted in the current phase. Until
+    /// both players committed to the current round, that's their commitment,
+    /// then their reveal.
+    fn phase_acted_at(&self, player: &Player) -> (Option<i64>, Option<i64>) {
+        match (player, self.is_round_committed()) {
+            (Player::First, true) => (
                 self.first_player_revealed_at,
                 self.second_player_revealed_at,
             ),
-            Player::Second => (
+            (Player::Second, true) => (
                 self.second_player_revealed_at,
                 self.first_player_revealed_at,
             ),
-        };
+            (Player::First, false) => (
+                self.first_player_committed_at,
+                self.second_player_committed_at,
+            ),
+            (Player::Second, false) => (
+                self.second_player_committed_at,
+                self.first_player_committed_at,
+            ),
+        }
+    }
 
-        // This player revealed, can't count as a forfeit.
-        if player_revealed_at.is_some() {
-            return false;
+    pub fn did_player_forfeit(&self, player: Player, now: i64, time_for_penalization: i64) -> bool {
+        match self.phase_acted_at(&player) {
+            // This player didn't act, but the adversary did, so forfeit
+            // after the time for expiry.
+            (None, Some(adversary_acted_at)) => adversary_acted_at + time_for_penalization <= now,
+            // This player acted, or neither did, so no forfeit.
+            _ => false,
         }
+    }
 
-        // Neither player has revealed, so no forfeit.
-        if player_revealed_at.is_none() && adversary_revealed_at.is_none() {
-            return false;
+    /// Earliest time `settle_game` can succeed, `None` while it's waiting on
+    /// both players.
+    pub fn settleable_at(&self, time_for_penalization: i64) -> Option<i64> {
+        if self.is_match_over() {
+            return self.rounds.last().map(Round::resolved_at);
+        }
+        match self.phase_acted_at(&Player::First) {
+            (Some(acted_at), None) | (None, Some(acted_at)) => {
+                Some(acted_at + time_for_penalization)
+            }
+            _ => None,
         }
+    }
+
+    /// Decides the game and marks it claimed, leaving the escrow payouts to
+    /// the caller.
+    ///
+    /// Escrow balances are passed in rather than assumed to be
+    /// `amount_to_match`, since mints with transfer fees deliver less.
+    pub fn settle(
+        &mut self,
+        now: i64,
+        settings: &Settings,
+        first_player_balance: u64,
+        second_player_balance: u64,
+    ) -> Result<Settlement> {
+        let winner = self.get_winner(now, settings)?;
+
+        let rake_terms = self.rake_terms(settings);
+        let is_raked = winner.is_some() || rake_terms.rake_draws;
+        let escrow_payout = |balance: u64| -> Result<EscrowPayout> {
+            let rake = if is_raked {
+                rake_terms.rake_for(balance)?
+            } else {
+                0
+            };
+            Ok(EscrowPayout {
+                payout: balance
+                    .checked_sub(rake)
+                    .ok_or(RockPaperScissorsError::NumericOverflow)?,
+                rake,
+            })
+        };
+        let first_player = escrow_payout(first_player_balance)?;
+        let second_player = escrow_payout(second_player_balance)?;
 
-        // This player didn't reveal, but the adversary did, so forfeit
-        // after the time for expiry.
-        adversary_revealed_at.unwrap() + time_for_penalization <= now
+        let amount_won: u64 = match winner {
+            Some(_) => match first_player.payout.checked_add(second_player.payout) {
+                Some(value) => value,
+                None => return Err(RockPaperScissorsError::NumericOverflow.into()),
+            },
+            None => 0,
+        };
+
+        self.set_claimed(&winner, amount_won, now);
+
+        Ok(Settlement {
+            winner,
+            first_player,
+            second_player,
+        })
     }
 
     pub fn set_claimed(&mut self, winner: &Option<Player>, amount_won: u64, now: i64) {
@@ -196,11 +760,100 @@ impl Game {
         self.drawn_at = Some(now);
     }
 
+    pub fn is_settled(&self) -> bool {
+        matches!(
+            self.state,
+            GameState::FirstPlayerWon | GameState::SecondPlayerWon | GameState::Draw
+        )
+    }
+
+    pub fn is_closable(&self, now: i64, game_retention_period: i64) -> bool {
+        match self.drawn_at {
+            Some(drawn_at) => self.is_settled() && drawn_at + game_retention_period <= now,
+            None => false,
+        }
+    }
+
+    /// A game is stale once neither player acted in the current phase for
+    /// `time_for_stale`. A phase where only one player acted is a forfeit
+    /// instead, see `did_player_forfeit`.
     pub fn is_stale(&self, now: i64, time_for_stale: i64) -> bool {
-        let no_player_revealed = self.state == GameState::Started
-            && self.first_player_revealed_at.is_none()
-            && self.second_player_revealed_at.is_none();
-        no_player_revealed && self.created_at + time_for_stale > now
+        if self.state != GameState::Started {
+            return false;
+        }
+
+        let (phase_started_at, no_player_acted) = if self.is_round_committed() {
+            // First round commitments land at `initialize_game` and
+            // `join_game`, so its reveal phase starts when the game does.
+            // Games from before `started_at` was recorded are measured from
+            // their creation instead.
+            let phase_started_at = if self.rounds.is_empty() {
+                Some(self.started_at.unwrap_or(self.created_at))
+            } else {
+                self.first_player_committed_at
+                    .max(self.second_player_committed_at)
+            };
+            (
+                phase_started_at,
+                self.first_player_revealed_at.is_none() && self.second_player_revealed_at.is_none(),
+            )
+        } else {
+            (
+                self.rounds.last().map(Round::resolved_at),
+                self.first_player_committed_at.is_none()
+                    && self.second_player_committed_at.is_none(),
+            )
+        };
+
+        match phase_started_at {
+            Some(phase_started_at) => no_player_acted && phase_started_at + time_for_stale <= now,
+            None => false,
+        }
+    }
+
+    /// What actually reached a player's escrow, `amount_to_match` for games
+    /// created before deposits were recorded.
+    pub fn deposit_of(&self, player: &Player) -> u64 {
+        match player {
+            Player::First => self.first_player_deposit,
+            Player::Second => self.second_player_deposit,
+        }
+        .unwrap_or(self.amount_to_match)
+    }
+
+    pub fn referral_of(&self, player: &Player) -> Option<&GameReferral> {
+        match player {
+            Player::First => self.first_player_referral.as_ref(),
+            Player::Second => self.second_player_referral.as_ref(),
+        }
+    }
+
+    pub fn set_deposit(&mut self, player: &Player, amount: u64) {
+        match player {
+            Player::First => self.first_player_deposit = Some(amount),
+            Player::Second => self.second_player_deposit = Some(amount),
+        }
+    }
+
+    pub fn time_for_penalization(&self, settings: &Settings) -> i64 {
+        self.time_for_penalization
+            .unwrap_or(settings.time_for_penalization)
+    }
+
+    pub fn time_for_stale(&self, settings: &Settings) -> i64 {
+        self.time_for_stale.unwrap_or(settings.time_for_stale)
+    }
+
+    pub fn rake_terms(&self, settings: &Settings) -> RakeTerms {
+        self.rake_terms
+            .clone()
+            .unwrap_or_else(|| settings.rake_terms())
+    }
+
+    /// What the second player pays to join, the same as the first player.
+    pub fn player_fee_lamports(&self, mint_config: &MintConfig, settings: &Settings) -> u64 {
+        self.player_fee_lamports
+            .unwrap_or_else(|| mint_config.player_fee_lamports(settings))
     }
 
     pub fn is_player(&self, key: &Pubkey) -> bool {
@@ -217,53 +870,487 @@ impl Game {
         }
     }
 
+    /// The player who forfeited at `now`, the first one when both did.
+    pub fn forfeiter(&self, now: i64, settings: &Settings) -> Option<Player> {
+        let time_for_penalization = self.time_for_penalization(settings);
+        [Player::First, Player::Second]
+            .into_iter()
+            .find(|player| self.did_player_forfeit(player.clone(), now, time_for_penalization))
+    }
+
     pub fn get_winner(&self, now: i64, settings: &Settings) -> Result<Option<Player>> {
         require!(
             self.state == GameState::Started,
             RockPaperScissorsError::InvalidGameState
         );
 
-        let did_first_player_forfeit =
-            self.did_player_forfeit(Player::First, now, settings.time_for_penalization);
-        if did_first_player_forfeit {
-            msg!("First player forfeited due to time elapsed. Second player wins.");
-            return Ok(Some(Player::Second));
+        let time_for_penalization = self.time_for_penalization(settings);
+        match self.forfeiter(now, settings) {
+            Some(Player::First) => {
+                msg!("First player forfeited due to time elapsed. Second player wins.");
+                return Ok(Some(Player::Second));
+            }
+            Some(Player::Second) => {
+                msg!("Second player forfeited due to time elapsed. First player wins.");
+                return Ok(Some(Player::First));
+            }
+            None => {}
         }
 
-        let did_second_player_forfeit =
-            self.did_player_forfeit(Player::Second, now, settings.time_for_penalization);
-        if did_second_player_forfeit {
-            msg!("Second player forfeited due to time elapsed. First player wins.");
-            return Ok(Some(Player::First));
+        if !self.is_match_over() {
+            return match self.settleable_at(time_for_penalization) {
+                Some(settleable_at) => {
+                    msg!("Game can be settled at {}", settleable_at);
+                    Err(RockPaperScissorsError::PenalizationWindowNotElapsed.into())
+                }
+                None if self.is_round_committed() => {
+                    Err(RockPaperScissorsError::AwaitingReveals.into())
+                }
+                None => Err(RockPaperScissorsError::AwaitingCommitments.into()),
+            };
         }
 
-        let first_player_choice = self.first_player_choice.as_ref().unwrap();
-        let second_player_choice = self.second_player_choice.as_ref().unwrap();
+        msg!(
+            "Match ended {} - {} after {} round(s)",
+            self.first_player_wins,
+            self.second_player_wins,
+            self.rounds.len()
+        );
 
-        if first_player_choice == second_player_choice {
-            return Ok(None);
+        if self.first_player_wins > self.second_player_wins {
+            Ok(Some(Player::First))
+        } else if self.second_player_wins > self.first_player_wins {
+            Ok(Some(Player::Second))
+        } else {
+            Ok(None)
         }
+    }
+}
 
-        let first_player_wins = match first_player_choice {
-            Choice::Rock => second_player_choice == &Choice::Scissors,
-            Choice::Paper => second_player_choice == &Choice::Rock,
-            Choice::Scissors => second_player_choice == &Choice::Paper,
-        };
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{merkle_parent, MAX_BPS};
+    use anchor_lang::Discriminator;
 
-        if first_player_wins {
-            msg!(
-                "First player wins with {:?} against {:?}",
-                first_player_choice,
-                second_player_choice
-            );
-            Ok(Some(Player::First))
-        } else {
-            msg!(
-                "Second player wins with {:?} against {:?}",
-                second_player_choice,
-                first_player_choice
-            );
-            Ok(Some(Player::Second))
+    const TIME_FOR_STALE: i64 = 100;
+    const CREATED_AT: i64 = 1_000;
+    const STARTED_AT: i64 = 5_000;
+    const TIME_FOR_PENALIZATION: i64 = 50;
+
+    fn settings() -> Settings {
+        Settings::new(
+            255,
+            TIME_FOR_PENALIZATION,
+            TIME_FOR_STALE,
+            Pubkey::new_unique(),
+            0,
+            0,
+            false,
+            0,
+            Pubkey::new_unique(),
+        )
+    }
+
+    fn created_game(best_of: u8) -> Game {
+        Game::new(
+            255,
+            "game".to_string(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            10,
+            Pubkey::new_unique(),
+            [1; 32],
+            Pubkey::new_unique(),
+            CREATED_AT,
+            best_of,
+            GameVariant::Classic,
+            None,
+            WagerMode::Token,
+            TIME_FOR_PENALIZATION,
+            TIME_FOR_STALE,
+            0,
+            settings().rake_terms(),
+        )
+    }
+
+    /// A single throw both players revealed, as encoded before any field was
+    /// appended to `Game`, in an account of the baseline `Game::size()`.
+    fn baseline_game_data() -> Vec<u8> {
+        #[derive(AnchorSerialize)]
+        struct BaselineGame {
+            bump: u8,
+            game_id: String,
+            mint: Pubkey,
+            token_program: Pubkey,
+            amount_to_match: u64,
+            first_player: Pubkey,
+            first_player_hash: [u8; 32],
+            first_player_escrow: Pubkey,
+            first_player_choice: Option<Choice>,
+            first_player_revealed_at: Option<i64>,
+            second_player: Option<Pubkey>,
+            second_player_hash: Option<[u8; 32]>,
+            second_player_escrow: Option<Pubkey>,
+            second_player_choice: Option<Choice>,
+            second_player_revealed_at: Option<i64>,
+            amount_won: Option<u64>,
+            drawn_at: Option<i64>,
+            state: GameState,
+            created_at: i64,
+        }
+
+        let mut data = Game::DISCRIMINATOR.to_vec();
+        BaselineGame {
+            bump: 255,
+            // The longest ID leaves no slack after it.
+            game_id: "g".repeat(32),
+            mint: Pubkey::new_unique(),
+            token_program: Pubkey::new_unique(),
+            amount_to_match: 10,
+            first_player: Pubkey::new_unique(),
+            first_player_hash: [1; 32],
+            first_player_escrow: Pubkey::new_unique(),
+            first_player_choice: Some(Choice::Rock),
+            first_player_revealed_at: Some(STARTED_AT),
+            second_player: Some(Pubkey::new_unique()),
+            second_player_hash: Some([2; 32]),
+            second_player_escrow: Some(Pubkey::new_unique()),
+            second_player_choice: Some(Choice::Scissors),
+            second_player_revealed_at: Some(STARTED_AT),
+            amount_won: None,
+            drawn_at: None,
+            state: GameState::Started,
+            created_at: CREATED_AT,
         }
+        .serialize(&mut data)
+        .unwrap();
+        // The rest of the account, reserved for the unset options, is zeroed.
+        data.resize(361, 0);
+        data
+    }
+
+    /// Decodes `data` the way `migrate_game` does.
+    fn migrated_game(mut data: Vec<u8>) -> Game {
+        data.resize(Game::size(MAX_BEST_OF).max(data.len()), 0);
+        let mut game = Game::try_deserialize(&mut &data[..]).unwrap();
+        data.truncate(Game::size(game.best_of));
+        game.resolve_legacy_round();
+        game
+    }
+
+    fn started_game(best_of: u8) -> Game {
+        let mut game = created_game(best_of);
+        game.join_game(
+            Pubkey::new_unique(),
+            [2; 32],
+            Pubkey::new_unique(),
+            STARTED_AT,
+            &[],
+        )
+        .unwrap();
+        game
+    }
+
+    #[test]
+    fn baseline_games_decode_once_migrated() {
+        let data = baseline_game_data();
+        assert!(Game::try_deserialize(&mut &data[..]).is_err());
+
+        let game = migrated_game(data);
+        assert!(361 < Game::size(game.best_of));
+        assert_eq!(game.game_id, "g".repeat(32));
+        assert_eq!(game.state, GameState::Started);
+        assert_eq!(game.created_at, CREATED_AT);
+        assert_eq!(game.second_player_choice, Some(Choice::Scissors));
+        assert_eq!(game.commitment_scheme, CommitmentScheme::Legacy);
+        assert_eq!(game.best_of, 0);
+        assert_eq!(game.wager_mode, WagerMode::Token);
+        assert_eq!(game.started_at, None);
+        assert_eq!(game.deposit_of(&Player::Second), 10);
+        assert_eq!(game.side_pool, None);
+        assert_eq!(game.rake_terms, None);
+
+        // Both reveals predate rounds, so the throw is resolved on migration.
+        assert_eq!(game.rounds.len(), 1);
+        assert_eq!(
+            game.get_winner(STARTED_AT + 1, &settings()).unwrap(),
+            Some(Player::First)
+        );
+    }
+
+    /// A single throw started before matches existed, as `migrate_game`
+    /// decodes it.
+    fn legacy_game() -> Game {
+        let mut game = started_game(1);
+        game.commitment_scheme = CommitmentScheme::Legacy;
+        game.best_of = 0;
+        game.first_player_committed_at = None;
+        game.second_player_committed_at = None;
+        game.started_at = None;
+        game
+    }
+
+    #[test]
+    fn legacy_single_throws_are_committed() {
+        let mut game = legacy_game();
+        assert!(game.is_round_committed());
+        assert_eq!(
+            game.commit_choice(Player::First, [3; 32], STARTED_AT)
+                .unwrap_err(),
+            RockPaperScissorsError::PlayerAlreadyCommitted.into()
+        );
+        assert_eq!(
+            game.get_winner(STARTED_AT, &settings()).unwrap_err(),
+            RockPaperScissorsError::AwaitingReveals.into()
+        );
+
+        let revealed_at = STARTED_AT + 10;
+        game.set_player_choice(Player::First, Choice::Rock, revealed_at);
+        game.resolve_round();
+        assert_eq!(
+            game.get_winner(revealed_at + TIME_FOR_PENALIZATION, &settings())
+                .unwrap(),
+            Some(Player::First)
+        );
+    }
+
+    #[test]
+    fn legacy_single_throws_settle_ties_as_draws() {
+        let mut game = legacy_game();
+        game.set_player_choice(Player::First, Choice::Rock, STARTED_AT + 10);
+        game.set_player_choice(Player::Second, Choice::Rock, STARTED_AT + 20);
+        game.resolve_round();
+
+        assert!(game.is_match_over());
+        assert_eq!(game.get_winner(STARTED_AT + 21, &settings()).unwrap(), None);
+    }
+
+    #[test]
+    fn join_game_enforces_invites() {
+        let invited = Pubkey::new_unique();
+        let also_invited = Pubkey::new_unique();
+        let stranger = Pubkey::new_unique();
+        let join = |restriction: JoinRestriction, player: Pubkey, proof: &[[u8; 32]]| {
+            let mut game = created_game(1);
+            game.join_restriction = Some(restriction);
+            game.join_game(player, [2; 32], Pubkey::new_unique(), STARTED_AT, proof)
+        };
+
+        assert!(join(JoinRestriction::Opponent(invited), invited, &[]).is_ok());
+        assert_eq!(
+            join(JoinRestriction::Opponent(invited), stranger, &[]).unwrap_err(),
+            RockPaperScissorsError::NotInvited.into()
+        );
+
+        let root = merkle_parent(&invite_leaf(&invited), &invite_leaf(&also_invited));
+        let proof = [invite_leaf(&also_invited)];
+        assert!(join(JoinRestriction::MerkleRoot(root), invited, &proof).is_ok());
+        assert_eq!(
+            join(JoinRestriction::MerkleRoot(root), stranger, &proof).unwrap_err(),
+            RockPaperScissorsError::NotInvited.into()
+        );
+    }
+
+    #[test]
+    fn is_not_stale_before_it_starts() {
+        let mut game = started_game(1);
+        game.state = GameState::Created;
+        assert!(!game.is_stale(STARTED_AT + TIME_FOR_STALE, TIME_FOR_STALE));
+    }
+
+    #[test]
+    fn is_stale_measured_from_started_at() {
+        let game = started_game(1);
+        // Long enough after creation, but not after the second player joined.
+        assert!(!game.is_stale(CREATED_AT + TIME_FOR_STALE, TIME_FOR_STALE));
+        assert!(!game.is_stale(STARTED_AT + TIME_FOR_STALE - 1, TIME_FOR_STALE));
+        assert!(game.is_stale(STARTED_AT + TIME_FOR_STALE, TIME_FOR_STALE));
+        assert!(game.is_stale(STARTED_AT + TIME_FOR_STALE + 1, TIME_FOR_STALE));
+    }
+
+    #[test]
+    fn is_stale_measured_from_created_at_without_started_at() {
+        let game = legacy_game();
+        assert!(!game.is_stale(CREATED_AT + TIME_FOR_STALE - 1, TIME_FOR_STALE));
+        assert!(game.is_stale(CREATED_AT + TIME_FOR_STALE, TIME_FOR_STALE));
+    }
+
+    #[test]
+    fn is_not_stale_once_a_player_revealed() {
+        let mut game = started_game(1);
+        game.set_player_choice(Player::Second, Choice::Rock, STARTED_AT + 1);
+        assert!(!game.is_stale(STARTED_AT + TIME_FOR_STALE, TIME_FOR_STALE));
+    }
+
+    #[test]
+    fn is_stale_measured_from_last_round_in_a_match() {
+        let mut game = started_game(3);
+        game.set_player_choice(Player::First, Choice::Rock, STARTED_AT + 10);
+        game.set_player_choice(Player::Second, Choice::Rock, STARTED_AT + 20);
+        game.resolve_round();
+
+        let round_resolved_at = STARTED_AT + 20;
+        assert!(!game.is_stale(round_resolved_at + TIME_FOR_STALE - 1, TIME_FOR_STALE));
+        assert!(game.is_stale(round_resolved_at + TIME_FOR_STALE, TIME_FOR_STALE));
+
+        game.commit_choice(Player::First, [3; 32], round_resolved_at + 1)
+            .unwrap();
+        assert!(!game.is_stale(round_resolved_at + TIME_FOR_STALE, TIME_FOR_STALE));
+    }
+
+    #[test]
+    fn get_winner_awaits_reveals() {
+        let game = started_game(1);
+        assert_eq!(game.settleable_at(TIME_FOR_PENALIZATION), None);
+        assert_eq!(
+            game.get_winner(STARTED_AT + 1, &settings()).unwrap_err(),
+            RockPaperScissorsError::AwaitingReveals.into()
+        );
+    }
+
+    #[test]
+    fn get_winner_awaits_penalization_window() {
+        let mut game = started_game(1);
+        let revealed_at = STARTED_AT + 10;
+        game.set_player_choice(Player::First, Choice::Rock, revealed_at);
+
+        let settleable_at = revealed_at + TIME_FOR_PENALIZATION;
+        assert_eq!(
+            game.settleable_at(TIME_FOR_PENALIZATION),
+            Some(settleable_at)
+        );
+        assert_eq!(
+            game.get_winner(settleable_at - 1, &settings()).unwrap_err(),
+            RockPaperScissorsError::PenalizationWindowNotElapsed.into()
+        );
+        assert_eq!(
+            game.get_winner(settleable_at, &settings()).unwrap(),
+            Some(Player::First)
+        );
+    }
+
+    #[test]
+    fn get_winner_ignores_settings_changed_after_creation() {
+        let mut game = started_game(1);
+        let revealed_at = STARTED_AT + 10;
+        game.set_player_choice(Player::First, Choice::Rock, revealed_at);
+
+        let mut settings = settings();
+        settings.time_for_penalization = 1;
+        assert_eq!(
+            game.get_winner(revealed_at + 1, &settings).unwrap_err(),
+            RockPaperScissorsError::PenalizationWindowNotElapsed.into()
+        );
+
+        // Games from before the snapshot follow the live settings.
+        game.time_for_penalization = None;
+        assert_eq!(
+            game.get_winner(revealed_at + 1, &settings).unwrap(),
+            Some(Player::First)
+        );
+    }
+
+    #[test]
+    fn get_winner_awaits_commitments_in_a_match() {
+        let mut game = started_game(3);
+        game.set_player_choice(Player::First, Choice::Rock, STARTED_AT + 10);
+        game.set_player_choice(Player::Second, Choice::Paper, STARTED_AT + 20);
+        game.resolve_round();
+
+        assert_eq!(
+            game.get_winner(STARTED_AT + 21, &settings()).unwrap_err(),
+            RockPaperScissorsError::AwaitingCommitments.into()
+        );
+    }
+
+    #[test]
+    fn settle_rakes_each_escrow_from_its_balance() {
+        let mut settings = settings();
+        settings.rake_bps = 1_000;
+
+        let mut game = started_game(1);
+        game.rake_terms = Some(settings.rake_terms());
+        game.set_player_choice(Player::First, Choice::Rock, STARTED_AT + 10);
+        game.set_player_choice(Player::Second, Choice::Scissors, STARTED_AT + 20);
+        game.resolve_round();
+
+        // The second escrow lost 1 to a transfer fee.
+        let settlement = game.settle(STARTED_AT + 21, &settings, 10, 9).unwrap();
+        assert_eq!(
+            settlement,
+            Settlement {
+                winner: Some(Player::First),
+                first_player: EscrowPayout { payout: 9, rake: 1 },
+                second_player: EscrowPayout { payout: 9, rake: 0 },
+            }
+        );
+        assert_eq!(game.state, GameState::FirstPlayerWon);
+        assert_eq!(game.amount_won, Some(18));
+    }
+
+    #[test]
+    fn settle_skips_rake_on_draws_unless_configured() {
+        let mut settings = settings();
+        settings.rake_bps = 1_000;
+
+        let mut game = started_game(1);
+        game.rake_terms = Some(settings.rake_terms());
+        game.set_player_choice(Player::First, Choice::Rock, STARTED_AT + 10);
+        game.set_player_choice(Player::Second, Choice::Rock, STARTED_AT + 20);
+        game.resolve_round();
+
+        let settlement = game.settle(STARTED_AT + 21, &settings, 10, 10).unwrap();
+        assert_eq!(
+            settlement,
+            Settlement {
+                winner: None,
+                first_player: EscrowPayout {
+                    payout: 10,
+                    rake: 0
+                },
+                second_player: EscrowPayout {
+                    payout: 10,
+                    rake: 0
+                },
+            }
+        );
+        assert_eq!(game.state, GameState::Draw);
+    }
+
+    #[test]
+    fn settle_keeps_the_rake_of_creation() {
+        let mut settings = settings();
+        settings.rake_bps = 1_000;
+
+        let mut game = started_game(1);
+        game.rake_terms = Some(settings.rake_terms());
+        game.set_player_choice(Player::First, Choice::Rock, STARTED_AT + 10);
+        game.set_player_choice(Player::Second, Choice::Scissors, STARTED_AT + 20);
+        game.resolve_round();
+
+        // The admin takes it all once both players are in.
+        settings.rake_bps = MAX_BPS;
+        settings.referral_share_bps = MAX_BPS;
+        let settlement = game.settle(STARTED_AT + 21, &settings, 10, 10).unwrap();
+        assert_eq!(
+            settlement,
+            Settlement {
+                winner: Some(Player::First),
+                first_player: EscrowPayout { payout: 9, rake: 1 },
+                second_player: EscrowPayout { payout: 9, rake: 1 },
+            }
+        );
+        assert_eq!(game.amount_won, Some(18));
+        assert_eq!(
+            game.rake_terms(&settings)
+                .referral_share(settlement.rake().unwrap())
+                .unwrap(),
+            0
+        );
+
+        // Games from before the rake was fixed follow the admin.
+        game.rake_terms = None;
+        assert_eq!(game.rake_terms(&settings), settings.rake_terms());
     }
 }
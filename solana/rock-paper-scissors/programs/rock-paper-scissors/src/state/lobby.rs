@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::RockPaperScissorsError, LOBBY_CAPACITY};
+
+/// Open games of one (mint, amount) bucket, so clients can find a game to
+/// join without an indexer. Bounded: once full, listing a game evicts the
+/// oldest entry, which can still be joined through its address.
+#[account]
+pub struct Lobby {
+    pub bump: u8,
+    /// `Pubkey::default()` for native SOL wagers.
+    pub mint: Pubkey,
+    pub amount: u64,
+    /// Slot the next listing starts looking for room from.
+    pub head: u8,
+    /// `Pubkey::default()` marks an empty slot.
+    pub games: [Pubkey; LOBBY_CAPACITY],
+}
+
+impl Lobby {
+    pub fn size() -> usize {
+        8 + // Discriminator
+        1 + // bump
+        32 + // mint
+        8 + // amount
+        1 + // head
+        32 * LOBBY_CAPACITY // games
+    }
+
+    pub fn new(bump: u8, mint: Pubkey, amount: u64) -> Self {
+        Self {
+            bump,
+            mint,
+            amount,
+            head: 0,
+            games: [Pubkey::default(); LOBBY_CAPACITY],
+        }
+    }
+
+    /// Zero value games are never allowed, so only a lobby created by this
+    /// very instruction has no amount yet.
+    pub fn is_initialized(&self) -> bool {
+        self.amount > 0
+    }
+
+    /// Lists a game in the first empty slot from `head`, evicting the entry
+    /// at `head` when there's none. Returns the evicted game.
+    pub fn push(&mut self, game: Pubkey) -> Option<Pubkey> {
+        let head = self.head as usize;
+        let slot = (0..LOBBY_CAPACITY)
+            .map(|offset| (head + offset) % LOBBY_CAPACITY)
+            .find(|&slot| self.games[slot] == Pubkey::default())
+            .unwrap_or(head);
+        let evicted = Some(self.games[slot]).filter(|evicted| *evicted != Pubkey::default());
+        self.games[slot] = game;
+        self.head = ((slot + 1) % LOBBY_CAPACITY) as u8;
+        evicted
+    }
+
+    /// Returns whether the game was still listed.
+    pub fn remove(&mut self, game: &Pubkey) -> bool {
+        match self.games.iter().position(|listed| listed == game) {
+            Some(slot) => {
+                self.games[slot] = Pubkey::default();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn open_games(&self) -> impl Iterator<Item = &Pubkey> {
+        self.games.iter().filter(|game| **game != Pubkey::default())
+    }
+
+    /// Takes a game that left the `Created` state off the lobby it was
+    /// listed in, if any. Evicted games aren't an error.
+    pub fn unlist(
+        lobby: Option<&mut Account<'_, Lobby>>,
+        listed_in: Option<Pubkey>,
+        game: &Pubkey,
+    ) -> Result<()> {
+        let Some(listed_in) = listed_in else {
+            return Ok(());
+        };
+        let lobby = lobby.ok_or(RockPaperScissorsError::InvalidLobby)?;
+        require_keys_eq!(lobby.key(), listed_in, RockPaperScissorsError::InvalidLobby);
+        lobby.remove(game);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_fills_empty_slots_before_evicting() {
+        let mut lobby = Lobby::new(255, Pubkey::default(), 10);
+        let games: Vec<_> = (0..LOBBY_CAPACITY).map(|_| Pubkey::new_unique()).collect();
+        for game in &games {
+            assert_eq!(lobby.push(*game), None);
+        }
+
+        // A freed slot is reused before anything is evicted.
+        assert!(lobby.remove(&games[3]));
+        assert!(!lobby.remove(&games[3]));
+        let reuse = Pubkey::new_unique();
+        assert_eq!(lobby.push(reuse), None);
+        assert_eq!(lobby.games[3], reuse);
+
+        // Full again, so the oldest entry after the last write goes.
+        assert_eq!(lobby.push(Pubkey::new_unique()), Some(games[4]));
+        assert_eq!(lobby.open_games().count(), LOBBY_CAPACITY);
+    }
+}
@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::RockPaperScissorsError, Settings};
+
+/// Admin managed allowlist entry for a wager mint, `Pubkey::default()` for
+/// native SOL wagers. Games can only be created in mints that have one.
+#[account]
+pub struct MintConfig {
+    pub bump: u8,
+    pub settings: Pubkey,
+    pub mint: Pubkey,
+    /// Disabling a mint blocks new games, ongoing ones play out.
+    pub enabled: bool,
+    /// Bounds on `amount_to_match`, both inclusive.
+    pub min_amount: u64,
+    pub max_amount: u64,
+    /// Replaces `Settings::player_fee_lamports` for games in this mint.
+    pub player_fee_lamports: Option<u64>,
+}
+
+impl MintConfig {
+    pub fn size() -> usize {
+        8 + // Discriminator
+        1 + // bump
+        32 + // settings
+        32 + // mint
+        1 + // enabled
+        8 + // min_amount
+        8 + // max_amount
+        (1 + 8) // player_fee_lamports
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        bump: u8,
+        settings: Pubkey,
+        mint: Pubkey,
+        enabled: bool,
+        min_amount: u64,
+        max_amount: u64,
+        player_fee_lamports: Option<u64>,
+    ) -> Self {
+        Self {
+            bump,
+            settings,
+            mint,
+            enabled,
+            min_amount,
+            max_amount,
+            player_fee_lamports,
+        }
+    }
+
+    /// Zero value games are never allowed.
+    pub fn is_valid_limits(min_amount: u64, max_amount: u64) -> bool {
+        min_amount > 0 && min_amount <= max_amount
+    }
+
+    pub fn check_amount(&self, amount: u64) -> Result<()> {
+        require!(
+            amount >= self.min_amount,
+            RockPaperScissorsError::AmountBelowMinimum
+        );
+        require!(
+            amount <= self.max_amount,
+            RockPaperScissorsError::AmountAboveMaximum
+        );
+        Ok(())
+    }
+
+    pub fn player_fee_lamports(&self, settings: &Settings) -> u64 {
+        self.player_fee_lamports
+            .unwrap_or(settings.player_fee_lamports)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mint_config() -> MintConfig {
+        MintConfig::new(
+            255,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            true,
+            10,
+            100,
+            None,
+        )
+    }
+
+    #[test]
+    fn rejects_zero_and_inverted_limits() {
+        assert!(!MintConfig::is_valid_limits(0, 100));
+        assert!(!MintConfig::is_valid_limits(101, 100));
+        assert!(MintConfig::is_valid_limits(100, 100));
+    }
+
+    #[test]
+    fn checks_amount_against_inclusive_limits() {
+        let mint_config = mint_config();
+        assert_eq!(
+            mint_config.check_amount(9).unwrap_err(),
+            RockPaperScissorsError::AmountBelowMinimum.into()
+        );
+        assert!(mint_config.check_amount(10).is_ok());
+        assert!(mint_config.check_amount(100).is_ok());
+        assert_eq!(
+            mint_config.check_amount(101).unwrap_err(),
+            RockPaperScissorsError::AmountAboveMaximum.into()
+        );
+    }
+}
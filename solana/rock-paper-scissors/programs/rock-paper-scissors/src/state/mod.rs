@@ -1,5 +1,23 @@
 pub mod game;
+pub mod lobby;
+pub mod mint_config;
+pub mod player_profile;
+pub mod player_rating;
+pub mod referral;
+pub mod season;
 pub mod settings;
+pub mod side_pool;
+pub mod tournament;
+pub mod variant;
 
 pub use game::*;
+pub use lobby::*;
+pub use mint_config::*;
+pub use player_profile::*;
+pub use player_rating::*;
+pub use referral::*;
+pub use season::*;
 pub use settings::*;
+pub use side_pool::*;
+pub use tournament::*;
+pub use variant::*;
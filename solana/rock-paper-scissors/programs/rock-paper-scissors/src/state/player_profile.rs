@@ -0,0 +1,240 @@
+use anchor_lang::prelude::*;
+
+use crate::{Game, Player, Settlement, MAX_PROFILE_MINTS};
+
+/// Wager totals of a profile in one mint, `Pubkey::default()` for native SOL.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct MintStats {
+    pub mint: Pubkey,
+    /// Deposited into games, what reached the escrow for mints with transfer
+    /// fees.
+    pub wagered: u64,
+    /// Paid back at settlement, draw refunds included, so `won - wagered` is
+    /// the net result.
+    pub won: u64,
+}
+
+impl MintStats {
+    pub fn size() -> usize {
+        32 + // mint
+        8 + // wagered
+        8 // won
+    }
+}
+
+/// Results of one wallet across every settled game, created the first time
+/// it creates or joins a game. Counters saturate rather than fail a
+/// settlement.
+#[account]
+pub struct PlayerProfile {
+    pub bump: u8,
+    pub player: Pubkey,
+
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+    /// Games the player saw through, every commitment and reveal in time.
+    pub completed: u32,
+    /// Losses because the player ran out the penalization window, also
+    /// counted in `losses`.
+    pub forfeits: u32,
+    /// Wins because the opponent did, also counted in `wins`.
+    pub forfeit_wins: u32,
+
+    /// Positive during a winning streak, negative during a losing one, reset
+    /// by draws.
+    pub streak: i32,
+    pub best_streak: u32,
+
+    /// Up to `MAX_PROFILE_MINTS`, in the order first played.
+    pub mints: Vec<MintStats>,
+
+    pub created_at: i64,
+    pub last_settled_at: Option<i64>,
+}
+
+impl PlayerProfile {
+    pub fn size() -> usize {
+        8 + // Discriminator
+        1 + // bump
+        32 + // player
+        4 * 6 + // wins, losses, draws, completed, forfeits, forfeit_wins
+        4 + // streak
+        4 + // best_streak
+        (4 + MintStats::size() * MAX_PROFILE_MINTS) + // mints
+        8 + // created_at
+        (1 + 8) // last_settled_at
+    }
+
+    pub fn new(bump: u8, player: Pubkey, created_at: i64) -> Self {
+        Self {
+            bump,
+            player,
+            wins: 0,
+            losses: 0,
+            draws: 0,
+            completed: 0,
+            forfeits: 0,
+            forfeit_wins: 0,
+            streak: 0,
+            best_streak: 0,
+            mints: vec![],
+            created_at,
+            last_settled_at: None,
+        }
+    }
+
+    /// Only a profile created by this very instruction has no player yet.
+    pub fn is_initialized(&self) -> bool {
+        self.player != Pubkey::default()
+    }
+
+    pub fn mint_stats(&self, mint: &Pubkey) -> Option<&MintStats> {
+        self.mints.iter().find(|stats| stats.mint == *mint)
+    }
+
+    /// Records a settled game from the point of view of `player`, its seat.
+    #[allow(clippy::too_many_arguments)]
+    pub fn record(
+        &mut self,
+        player: &Player,
+        winner: &Option<Player>,
+        forfeiter: &Option<Player>,
+        mint: Pubkey,
+        wagered: u64,
+        won: u64,
+        now: i64,
+    ) {
+        match winner {
+            None => {
+                self.draws = self.draws.saturating_add(1);
+                self.streak = 0;
+            }
+            Some(winner) if winner == player => {
+                self.wins = self.wins.saturating_add(1);
+                self.streak = self.streak.max(0).saturating_add(1);
+                self.best_streak = self.best_streak.max(self.streak as u32);
+            }
+            Some(_) => {
+                self.losses = self.losses.saturating_add(1);
+                self.streak = self.streak.min(0).saturating_sub(1);
+            }
+        }
+        match forfeiter {
+            Some(forfeiter) if forfeiter == player => {
+                self.forfeits = self.forfeits.saturating_add(1);
+            }
+            Some(_) => {
+                self.forfeit_wins = self.forfeit_wins.saturating_add(1);
+                self.completed = self.completed.saturating_add(1);
+            }
+            None => self.completed = self.completed.saturating_add(1),
+        }
+
+        let is_full = self.mints.len() >= MAX_PROFILE_MINTS;
+        match self.mints.iter_mut().find(|stats| stats.mint == mint) {
+            Some(stats) => {
+                stats.wagered = stats.wagered.saturating_add(wagered);
+                stats.won = stats.won.saturating_add(won);
+            }
+            None if !is_full => self.mints.push(MintStats { mint, wagered, won }),
+            None => {}
+        }
+        self.last_settled_at = Some(now);
+    }
+
+    /// Records a game `settle` just decided on both players' profile PDAs,
+    /// skipping those that don't exist, since players of games created before
+    /// profiles might not have one. The caller checks the addresses.
+    /// `forfeiter` has to be read before settling, see `Game::forfeiter`.
+    pub fn record_settlement<'info>(
+        first_player_profile: &AccountInfo<'info>,
+        second_player_profile: &AccountInfo<'info>,
+        game: &Game,
+        settlement: &Settlement,
+        forfeiter: &Option<Player>,
+        now: i64,
+    ) -> Result<()> {
+        let winner = &settlement.winner;
+        for (info, player) in [
+            (first_player_profile, Player::First),
+            (second_player_profile, Player::Second),
+        ] {
+            if info.owner != &crate::ID || info.data_is_empty() {
+                continue;
+            }
+            let mut profile = Self::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+            let won = settlement.paid_out_to(&player, game.amount_won);
+            profile.record(
+                &player,
+                winner,
+                forfeiter,
+                game.mint,
+                game.deposit_of(&player),
+                won,
+                now,
+            );
+            profile.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_results_streaks_and_forfeits() {
+        let mint = Pubkey::new_unique();
+        let mut profile = PlayerProfile::new(255, Pubkey::new_unique(), 0);
+
+        profile.record(&Player::First, &Some(Player::First), &None, mint, 10, 19, 1);
+        profile.record(
+            &Player::Second,
+            &Some(Player::Second),
+            &Some(Player::First),
+            mint,
+            10,
+            19,
+            2,
+        );
+        assert_eq!(profile.streak, 2);
+        assert_eq!(profile.best_streak, 2);
+
+        profile.record(&Player::First, &None, &None, mint, 10, 10, 3);
+        assert_eq!(profile.streak, 0);
+        profile.record(
+            &Player::First,
+            &Some(Player::Second),
+            &Some(Player::First),
+            mint,
+            10,
+            0,
+            4,
+        );
+        assert_eq!(profile.streak, -1);
+        assert_eq!(profile.best_streak, 2);
+
+        assert_eq!((profile.wins, profile.losses, profile.draws), (2, 1, 1));
+        assert_eq!(
+            (profile.completed, profile.forfeits, profile.forfeit_wins),
+            (3, 1, 1)
+        );
+        assert_eq!(
+            profile.mint_stats(&mint),
+            Some(&MintStats {
+                mint,
+                wagered: 40,
+                won: 48,
+            })
+        );
+        assert_eq!(profile.last_settled_at, Some(4));
+
+        for _ in 0..MAX_PROFILE_MINTS {
+            profile.record(&Player::First, &None, &None, Pubkey::new_unique(), 1, 1, 5);
+        }
+        assert_eq!(profile.mints.len(), MAX_PROFILE_MINTS);
+        assert_eq!(profile.draws, 1 + MAX_PROFILE_MINTS as u32);
+    }
+}
@@ -0,0 +1,190 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    Player, DEFAULT_RATING, MAX_BPS, MIN_RATING, PROVISIONAL_GAMES, PROVISIONAL_K_FACTOR,
+    RATING_K_FACTOR,
+};
+
+/// Rating difference between neighbouring `EXPECTED_SCORE_BPS` entries.
+const EXPECTED_SCORE_STEP: u32 = 25;
+/// Elo expected score, in bps, of a player rated `n * EXPECTED_SCORE_STEP`
+/// points above their opponent, `1 / (1 + 10^(-diff / 400))`. Differences
+/// past the last entry score as the last entry.
+const EXPECTED_SCORE_BPS: [u16; 33] = [
+    5000, 5359, 5715, 6063, 6401, 6725, 7034, 7325, 7597, 7850, 8083, 8296, 8490, 8666, 8823, 8965,
+    9091, 9203, 9302, 9390, 9468, 9536, 9595, 9648, 9693, 9733, 9768, 9799, 9825, 9848, 9868, 9886,
+    9901,
+];
+
+/// Expected score, in bps, of a player rated `rating` against `opponent`,
+/// interpolated between table entries so the math stays in integers.
+pub fn expected_score_bps(rating: u32, opponent: u32) -> u32 {
+    let max_diff = EXPECTED_SCORE_STEP * (EXPECTED_SCORE_BPS.len() as u32 - 1);
+    let diff = rating.abs_diff(opponent).min(max_diff);
+    let index = (diff / EXPECTED_SCORE_STEP) as usize;
+    let low = EXPECTED_SCORE_BPS[index] as u32;
+    let high = EXPECTED_SCORE_BPS[(index + 1).min(EXPECTED_SCORE_BPS.len() - 1)] as u32;
+    let expected = low + (high - low) * (diff % EXPECTED_SCORE_STEP) / EXPECTED_SCORE_STEP;
+    if rating >= opponent {
+        expected
+    } else {
+        MAX_BPS as u32 - expected
+    }
+}
+
+/// Elo rating of one wallet, created the first time it creates or joins a
+/// game, like its `PlayerProfile`.
+#[account]
+pub struct PlayerRating {
+    pub bump: u8,
+    pub player: Pubkey,
+    pub rating: u32,
+    /// Rated games, the K-factor drops after `PROVISIONAL_GAMES`.
+    pub games: u32,
+    pub peak_rating: u32,
+    pub updated_at: i64,
+}
+
+impl PlayerRating {
+    pub fn size() -> usize {
+        8 + // Discriminator
+        1 + // bump
+        32 + // player
+        4 + // rating
+        4 + // games
+        4 + // peak_rating
+        8 // updated_at
+    }
+
+    pub fn new(bump: u8, player: Pubkey, created_at: i64) -> Self {
+        Self {
+            bump,
+            player,
+            rating: DEFAULT_RATING,
+            games: 0,
+            peak_rating: DEFAULT_RATING,
+            updated_at: created_at,
+        }
+    }
+
+    /// Only a rating created by this very instruction has no player yet.
+    pub fn is_initialized(&self) -> bool {
+        self.player != Pubkey::default()
+    }
+
+    pub fn k_factor(&self) -> u32 {
+        if self.games < PROVISIONAL_GAMES {
+            PROVISIONAL_K_FACTOR
+        } else {
+            RATING_K_FACTOR
+        }
+    }
+
+    /// Rating after scoring `score_bps` (win 10_000, draw 5_000, loss 0)
+    /// against `opponent`, rounded to the nearest point.
+    pub fn rating_after(&self, opponent: u32, score_bps: u32) -> u32 {
+        let expected = expected_score_bps(self.rating, opponent) as i64;
+        let change = self.k_factor() as i64 * (score_bps as i64 - expected);
+        let half = MAX_BPS as i64 / 2;
+        let rounded = if change >= 0 {
+            (change + half) / MAX_BPS as i64
+        } else {
+            (change - half) / MAX_BPS as i64
+        };
+        (self.rating as i64 + rounded).max(MIN_RATING as i64) as u32
+    }
+
+    /// Rates a decided game, both players from their ratings before it.
+    pub fn record_game(
+        first_player_rating: &mut Self,
+        second_player_rating: &mut Self,
+        winner: &Option<Player>,
+        now: i64,
+    ) {
+        let (first_player_score, second_player_score) = match winner {
+            Some(Player::First) => (MAX_BPS as u32, 0),
+            Some(Player::Second) => (0, MAX_BPS as u32),
+            None => (MAX_BPS as u32 / 2, MAX_BPS as u32 / 2),
+        };
+        let first_player_new =
+            first_player_rating.rating_after(second_player_rating.rating, first_player_score);
+        let second_player_new =
+            second_player_rating.rating_after(first_player_rating.rating, second_player_score);
+        first_player_rating.set_rating(first_player_new, now);
+        second_player_rating.set_rating(second_player_new, now);
+    }
+
+    /// Rates a game `settle` just decided on both players' rating PDAs,
+    /// returning each player's rating before and after. Players of games
+    /// created before ratings might not have one, those games aren't rated.
+    /// The caller checks the addresses.
+    pub fn record_settlement<'info>(
+        first_player_rating: &AccountInfo<'info>,
+        second_player_rating: &AccountInfo<'info>,
+        winner: &Option<Player>,
+        now: i64,
+    ) -> Result<Option<[(u32, u32); 2]>> {
+        if [first_player_rating, second_player_rating]
+            .iter()
+            .any(|info| info.owner != &crate::ID || info.data_is_empty())
+        {
+            return Ok(None);
+        }
+        let mut first_player =
+            Self::try_deserialize(&mut &first_player_rating.try_borrow_data()?[..])?;
+        let mut second_player =
+            Self::try_deserialize(&mut &second_player_rating.try_borrow_data()?[..])?;
+        let old_ratings = (first_player.rating, second_player.rating);
+        Self::record_game(&mut first_player, &mut second_player, winner, now);
+        first_player.try_serialize(&mut &mut first_player_rating.try_borrow_mut_data()?[..])?;
+        second_player.try_serialize(&mut &mut second_player_rating.try_borrow_mut_data()?[..])?;
+        Ok(Some([
+            (old_ratings.0, first_player.rating),
+            (old_ratings.1, second_player.rating),
+        ]))
+    }
+
+    fn set_rating(&mut self, rating: u32, now: i64) {
+        self.rating = rating;
+        self.peak_rating = self.peak_rating.max(rating);
+        self.games = self.games.saturating_add(1);
+        self.updated_at = now;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expected_scores_are_symmetric_and_bounded() {
+        assert_eq!(expected_score_bps(1_200, 1_200), 5_000);
+        assert_eq!(expected_score_bps(1_600, 1_200), 9_091);
+        assert_eq!(expected_score_bps(1_200, 1_600), 909);
+        // Interpolated halfway between the 0 and 25 points entries.
+        assert_eq!(expected_score_bps(1_212, 1_200), 5_172);
+        assert_eq!(expected_score_bps(3_000, 100), 9_901);
+    }
+
+    #[test]
+    fn rates_games_with_provisional_k_factor() {
+        let mut first = PlayerRating::new(255, Pubkey::new_unique(), 0);
+        let mut second = PlayerRating::new(255, Pubkey::new_unique(), 0);
+
+        PlayerRating::record_game(&mut first, &mut second, &Some(Player::First), 1);
+        assert_eq!((first.rating, second.rating), (1_220, 1_180));
+        assert_eq!(first.peak_rating, 1_220);
+        assert_eq!(second.peak_rating, DEFAULT_RATING);
+
+        // The favourite drawing loses points.
+        PlayerRating::record_game(&mut first, &mut second, &None, 2);
+        assert_eq!((first.rating, second.rating), (1_218, 1_182));
+        assert_eq!(first.games, 2);
+
+        first.games = PROVISIONAL_GAMES;
+        second.rating = MIN_RATING;
+        PlayerRating::record_game(&mut first, &mut second, &Some(Player::First), 3);
+        assert_eq!(first.rating, 1_218);
+        assert_eq!(second.rating, MIN_RATING);
+    }
+}
@@ -0,0 +1,164 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::error::RockPaperScissorsError;
+
+/// Who referred a player, registered once and for good.
+#[account]
+pub struct Referral {
+    pub bump: u8,
+    pub player: Pubkey,
+    pub referrer: Pubkey,
+    pub created_at: i64,
+}
+
+impl Referral {
+    pub fn size() -> usize {
+        8 + // Discriminator
+        1 + // bump
+        32 + // player
+        32 + // referrer
+        8 // created_at
+    }
+
+    /// Referrer stored in a player's referral PDA, `None` while it doesn't
+    /// exist. The caller checks the address.
+    pub fn referrer_of(info: &AccountInfo) -> Result<Option<Pubkey>> {
+        if info.owner != &crate::ID || info.data_is_empty() {
+            return Ok(None);
+        }
+        let data = info.try_borrow_data()?;
+        Ok(Some(Self::try_deserialize(&mut &data[..])?.referrer))
+    }
+}
+
+/// Rewards of one referrer. SOL shares are held by this account on top of
+/// its rent, token shares by any token account it's the authority of.
+#[account]
+pub struct ReferrerRewards {
+    pub bump: u8,
+    pub referrer: Pubkey,
+    pub referred_players: u32,
+    /// SOL shares received and claimed so far, token shares aren't tracked.
+    pub earned_lamports: u64,
+    pub claimed_lamports: u64,
+    pub created_at: i64,
+}
+
+impl ReferrerRewards {
+    pub fn size() -> usize {
+        8 + // Discriminator
+        1 + // bump
+        32 + // referrer
+        4 + // referred_players
+        8 + // earned_lamports
+        8 + // claimed_lamports
+        8 // created_at
+    }
+
+    pub fn new(bump: u8, referrer: Pubkey, created_at: i64) -> Self {
+        Self {
+            bump,
+            referrer,
+            referred_players: 0,
+            earned_lamports: 0,
+            claimed_lamports: 0,
+            created_at,
+        }
+    }
+
+    /// Only rewards created by this very instruction have no referrer yet.
+    pub fn is_initialized(&self) -> bool {
+        self.referrer != Pubkey::default()
+    }
+
+    /// Checks the rewards account passed for a player's referrer, `None` for
+    /// players without one.
+    pub fn of_referrer<'a, 'info>(
+        rewards: Option<&'a mut Account<'info, ReferrerRewards>>,
+        referrer: Option<Pubkey>,
+    ) -> Result<Option<&'a mut Account<'info, ReferrerRewards>>> {
+        let Some(referrer) = referrer else {
+            return Ok(None);
+        };
+        let rewards = rewards.ok_or(RockPaperScissorsError::ReferralMismatch)?;
+        require_keys_eq!(
+            rewards.referrer,
+            referrer,
+            RockPaperScissorsError::ReferralMismatch
+        );
+        Ok(Some(rewards))
+    }
+
+    pub fn record_earned(&mut self, lamports: u64) {
+        self.earned_lamports = self.earned_lamports.saturating_add(lamports);
+    }
+}
+
+/// Where a seat's referrer is paid from, snapshotted when the player takes
+/// the seat so settlement can't leave it out.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct GameReferral {
+    /// Paid the share of native rake through its `ReferrerRewards`.
+    pub referrer: Pubkey,
+    /// Token account of the referrer's rewards in the game's mint, paid the
+    /// share of token rake. `None` for native games.
+    pub vault: Option<Pubkey>,
+}
+
+impl GameReferral {
+    pub fn size() -> usize {
+        32 + // referrer
+        (1 + 32) // vault
+    }
+
+    /// Checks the token account passed as the vault of `rewards` in `mint`,
+    /// required so the referrer's share of token rake can't be left out.
+    pub fn check_vault(
+        vault: Option<&InterfaceAccount<'_, TokenAccount>>,
+        rewards: &Pubkey,
+        mint: &Pubkey,
+    ) -> Result<Pubkey> {
+        let vault = vault.ok_or(RockPaperScissorsError::ReferralMismatch)?;
+        require!(
+            vault.owner == *rewards && vault.mint == *mint,
+            RockPaperScissorsError::ReferralMismatch
+        );
+        Ok(vault.key())
+    }
+
+    /// Checks the vault passed at settlement is the one snapshotted.
+    pub fn vault_at_settlement<'a, 'info>(
+        referral: Option<&GameReferral>,
+        vault: Option<&'a mut Box<InterfaceAccount<'info, TokenAccount>>>,
+    ) -> Result<Option<&'a mut Box<InterfaceAccount<'info, TokenAccount>>>> {
+        let Some(expected) = referral.and_then(|referral| referral.vault) else {
+            return Ok(None);
+        };
+        let vault = vault.ok_or(RockPaperScissorsError::ReferralMismatch)?;
+        require_keys_eq!(
+            vault.key(),
+            expected,
+            RockPaperScissorsError::ReferralMismatch
+        );
+        Ok(Some(vault))
+    }
+
+    /// Checks the rewards account passed at settlement is the one
+    /// snapshotted.
+    pub fn rewards_at_settlement<'a, 'info>(
+        referral: Option<&GameReferral>,
+        rewards: Option<&'a mut Account<'info, ReferrerRewards>>,
+    ) -> Result<Option<&'a mut Account<'info, ReferrerRewards>>> {
+        let Some(referral) = referral else {
+            return Ok(None);
+        };
+        let rewards = rewards.ok_or(RockPaperScissorsError::ReferralMismatch)?;
+        require_keys_eq!(
+            rewards.referrer,
+            referral.referrer,
+            RockPaperScissorsError::ReferralMismatch
+        );
+        Ok(Some(rewards))
+    }
+}
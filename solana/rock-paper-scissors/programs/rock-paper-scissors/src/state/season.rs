@@ -0,0 +1,526 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RockPaperScissorsError, Game, Player, Settlement, MAX_BPS, MAX_LEADERBOARD_SIZE,
+    SEASON_CLAIM_PERIOD, SEASON_DRAW_POINTS, SEASON_RANKING_PERIOD, SEASON_WIN_POINTS,
+};
+
+/// What a season leaderboard is sorted by.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub enum SeasonRanking {
+    /// `SEASON_WIN_POINTS` per win and `SEASON_DRAW_POINTS` per draw, games
+    /// of every mint count.
+    Points,
+    /// Paid out minus deposited, only games wagering `mint` count,
+    /// `Pubkey::default()` for native SOL.
+    NetWinnings { mint: Pubkey },
+}
+
+impl SeasonRanking {
+    pub fn size() -> usize {
+        1 + // Variant
+        32 // mint
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct LeaderboardEntry {
+    pub player: Pubkey,
+    pub score: i64,
+}
+
+impl LeaderboardEntry {
+    pub fn size() -> usize {
+        32 + // player
+        8 // score
+    }
+}
+
+/// Admin run competition over the games settled between `start_at` and
+/// `end_at`. Games join a season when created with it, and a share of their
+/// player fees is held by the season account on top of its rent, paid out
+/// to the top of the leaderboard once the season ends.
+#[account]
+pub struct Season {
+    pub bump: u8,
+    pub season_id: u32,
+    pub start_at: i64,
+    pub end_at: i64,
+    pub ranking: SeasonRanking,
+    /// Share of each player fee of the season's games diverted to its
+    /// prize vault.
+    pub fee_share_bps: u16,
+    /// Share of the prize pool per leaderboard place, first place first.
+    pub prize_split_bps: Vec<u16>,
+
+    pub leaderboard_size: u8,
+    /// Best current scores first, ties kept in the order they were reached,
+    /// only positive scores place. Only the top is kept, so a player whose
+    /// net winnings fall isn't replaced by someone outside it until
+    /// `rank_season_entry` ranks them.
+    pub leaderboard: Vec<LeaderboardEntry>,
+
+    /// Vault balance locked in by the first claim after the season ends.
+    pub prize_pool: Option<u64>,
+    /// Bit `n` is set once place `n`'s prize is paid.
+    pub claimed_places: u16,
+    pub created_at: i64,
+}
+
+impl Season {
+    pub fn size(leaderboard_size: u8) -> usize {
+        8 + // Discriminator
+        1 + // bump
+        4 + // season_id
+        8 + // start_at
+        8 + // end_at
+        SeasonRanking::size() + // ranking
+        2 + // fee_share_bps
+        (4 + 2 * leaderboard_size as usize) + // prize_split_bps
+        1 + // leaderboard_size
+        (4 + LeaderboardEntry::size() * leaderboard_size as usize) + // leaderboard
+        (1 + 8) + // prize_pool
+        2 + // claimed_places
+        8 // created_at
+    }
+
+    pub fn is_valid_leaderboard_size(leaderboard_size: u8) -> bool {
+        (1..=MAX_LEADERBOARD_SIZE).contains(&leaderboard_size)
+    }
+
+    /// One share per paid place, at most one per leaderboard place, adding
+    /// up to the whole pool.
+    pub fn is_valid_prize_split(prize_split_bps: &[u16], leaderboard_size: u8) -> bool {
+        !prize_split_bps.is_empty()
+            && prize_split_bps.len() <= leaderboard_size as usize
+            && prize_split_bps.iter().map(|bps| *bps as u32).sum::<u32>() == MAX_BPS as u32
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        bump: u8,
+        season_id: u32,
+        start_at: i64,
+        end_at: i64,
+        ranking: SeasonRanking,
+        fee_share_bps: u16,
+        prize_split_bps: Vec<u16>,
+        leaderboard_size: u8,
+        created_at: i64,
+    ) -> Self {
+        Self {
+            bump,
+            season_id,
+            start_at,
+            end_at,
+            ranking,
+            fee_share_bps,
+            prize_split_bps,
+            leaderboard_size,
+            leaderboard: vec![],
+            prize_pool: None,
+            claimed_places: 0,
+            created_at,
+        }
+    }
+
+    pub fn is_active(&self, now: i64) -> bool {
+        self.start_at <= now && now < self.end_at
+    }
+
+    pub fn has_ended(&self, now: i64) -> bool {
+        now >= self.end_at
+    }
+
+    /// Standings are final `SEASON_RANKING_PERIOD` after the season ends.
+    pub fn claims_open_at(&self) -> i64 {
+        self.end_at.saturating_add(SEASON_RANKING_PERIOD)
+    }
+
+    pub fn claims_close_at(&self) -> i64 {
+        self.claims_open_at().saturating_add(SEASON_CLAIM_PERIOD)
+    }
+
+    /// Net winnings seasons only take games wagering their mint.
+    pub fn check_mint(&self, mint: &Pubkey) -> Result<()> {
+        match &self.ranking {
+            SeasonRanking::NetWinnings { mint: ranked } if ranked != mint => {
+                err!(RockPaperScissorsError::SeasonMintMismatch)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Part of `player_fee_lamports` going to the prize vault.
+    pub fn fee_share(&self, player_fee_lamports: u64) -> Result<u64> {
+        (player_fee_lamports as u128)
+            .checked_mul(self.fee_share_bps as u128)
+            .and_then(|value| value.checked_div(MAX_BPS as u128))
+            .and_then(|value| u64::try_from(value).ok())
+            .ok_or(RockPaperScissorsError::NumericOverflow.into())
+    }
+
+    /// Checks a game can be created in `season` by `player`, returning the
+    /// season's share of their player fee.
+    pub fn fee_share_on_create(
+        season: &Account<'_, Season>,
+        entry: Option<&Account<'_, SeasonEntry>>,
+        player: &Pubkey,
+        mint: &Pubkey,
+        player_fee_lamports: u64,
+        now: i64,
+    ) -> Result<u64> {
+        require!(
+            season.is_active(now),
+            RockPaperScissorsError::SeasonNotActive
+        );
+        season.check_mint(mint)?;
+        entry
+            .ok_or(RockPaperScissorsError::SeasonMismatch)?
+            .check(&season.key(), player)?;
+        season.fee_share(player_fee_lamports)
+    }
+
+    /// Checks the season passed when joining a game that counts towards one,
+    /// returning the season's share of the player fee, none once it's over.
+    pub fn fee_share_on_join(
+        season: Option<&Account<'_, Season>>,
+        entry: Option<&Account<'_, SeasonEntry>>,
+        counts_towards: Option<Pubkey>,
+        player: &Pubkey,
+        player_fee_lamports: u64,
+        now: i64,
+    ) -> Result<u64> {
+        let Some(counts_towards) = counts_towards else {
+            return Ok(0);
+        };
+        let season = season.ok_or(RockPaperScissorsError::SeasonMismatch)?;
+        require_keys_eq!(
+            season.key(),
+            counts_towards,
+            RockPaperScissorsError::SeasonMismatch
+        );
+        entry
+            .ok_or(RockPaperScissorsError::SeasonMismatch)?
+            .check(&season.key(), player)?;
+        if season.is_active(now) {
+            season.fee_share(player_fee_lamports)
+        } else {
+            Ok(0)
+        }
+    }
+
+    /// Moves `player` to where `score` ranks, dropping whoever falls off the
+    /// bottom of a full leaderboard. A score of zero or less takes the player
+    /// off it, so nobody is paid for losing.
+    pub fn rank(&mut self, player: Pubkey, score: i64) {
+        self.leaderboard.retain(|entry| entry.player != player);
+        if score <= 0 {
+            return;
+        }
+        let place = self
+            .leaderboard
+            .iter()
+            .position(|entry| entry.score < score)
+            .unwrap_or(self.leaderboard.len());
+        if place < self.leaderboard_size as usize {
+            self.leaderboard
+                .insert(place, LeaderboardEntry { player, score });
+            self.leaderboard.truncate(self.leaderboard_size as usize);
+        }
+    }
+
+    /// Ranks an entry missing from the leaderboard at its current score.
+    /// Settlements only rank the game's players, this lets a player take the
+    /// place of one whose score fell below theirs. Entries already on it are
+    /// at their current score, re-ranking them would only lose their ties.
+    pub fn rank_entry(&mut self, entry: &SeasonEntry, now: i64) -> Result<i64> {
+        require!(
+            now < self.claims_open_at(),
+            RockPaperScissorsError::SeasonStandingsFinal
+        );
+        require!(
+            self.place_of(&entry.player).is_none(),
+            RockPaperScissorsError::SeasonEntryAlreadyRanked
+        );
+        let score = entry.score(&self.ranking);
+        self.rank(entry.player, score);
+        Ok(score)
+    }
+
+    /// Zero based leaderboard place of `player`.
+    pub fn place_of(&self, player: &Pubkey) -> Option<usize> {
+        self.leaderboard
+            .iter()
+            .position(|entry| entry.player == *player)
+    }
+
+    /// Locks the prize pool in at the vault's balance on first use, so every
+    /// place is paid its share of the same pool.
+    pub fn lock_prize_pool(&mut self, vault_balance: u64) -> u64 {
+        *self.prize_pool.get_or_insert(vault_balance)
+    }
+
+    pub fn prize_for(&self, place: usize) -> Result<u64> {
+        let bps = self
+            .prize_split_bps
+            .get(place)
+            .ok_or(RockPaperScissorsError::NoPrizeToClaim)?;
+        (self.prize_pool.unwrap_or_default() as u128)
+            .checked_mul(*bps as u128)
+            .and_then(|value| value.checked_div(MAX_BPS as u128))
+            .and_then(|value| u64::try_from(value).ok())
+            .ok_or(RockPaperScissorsError::NumericOverflow.into())
+    }
+
+    pub fn is_prize_claimed(&self, place: usize) -> bool {
+        self.claimed_places & (1 << place) != 0
+    }
+
+    pub fn set_prize_claimed(&mut self, place: usize) {
+        self.claimed_places |= 1 << place;
+    }
+
+    /// Records a settled game counting towards a season in both players'
+    /// entries and the leaderboard. Returns both players' new scores, `None`
+    /// for games settled after the season ended, which don't count.
+    pub fn record_game(
+        season: Option<&mut Account<'_, Season>>,
+        first_player_entry: Option<&mut Account<'_, SeasonEntry>>,
+        second_player_entry: Option<&mut Account<'_, SeasonEntry>>,
+        game: &Game,
+        settlement: &Settlement,
+        now: i64,
+    ) -> Result<Option<(i64, i64)>> {
+        let Some(counts_towards) = game.season else {
+            return Ok(None);
+        };
+        let season = season.ok_or(RockPaperScissorsError::SeasonMismatch)?;
+        require_keys_eq!(
+            season.key(),
+            counts_towards,
+            RockPaperScissorsError::SeasonMismatch
+        );
+        let second_player = game
+            .second_player
+            .ok_or(RockPaperScissorsError::InvalidPlayer)?;
+        let first_player_entry =
+            first_player_entry.ok_or(RockPaperScissorsError::SeasonMismatch)?;
+        first_player_entry.check(&season.key(), &game.first_player)?;
+        let second_player_entry =
+            second_player_entry.ok_or(RockPaperScissorsError::SeasonMismatch)?;
+        second_player_entry.check(&season.key(), &second_player)?;
+        if season.has_ended(now) {
+            return Ok(None);
+        }
+
+        let mut record = |entry: &mut SeasonEntry, player: Player| {
+            let paid_out = settlement.paid_out_to(&player, game.amount_won) as i128;
+            let net_winnings = (paid_out - game.deposit_of(&player) as i128)
+                .clamp(i64::MIN as i128, i64::MAX as i128) as i64;
+            entry.record(&player, &settlement.winner, net_winnings);
+            let score = entry.score(&season.ranking);
+            season.rank(entry.player, score);
+            score
+        };
+        let first_player_score = record(first_player_entry, Player::First);
+        let second_player_score = record(second_player_entry, Player::Second);
+        Ok(Some((first_player_score, second_player_score)))
+    }
+}
+
+/// Totals of one player over a season, created when they join it. Counters
+/// saturate rather than fail a settlement.
+#[account]
+pub struct SeasonEntry {
+    pub bump: u8,
+    pub season: Pubkey,
+    pub player: Pubkey,
+
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+    pub points: u64,
+    /// Summed over every season game, only meaningful for net winnings
+    /// seasons, which take a single mint.
+    pub net_winnings: i64,
+
+    pub created_at: i64,
+}
+
+impl SeasonEntry {
+    pub fn size() -> usize {
+        8 + // Discriminator
+        1 + // bump
+        32 + // season
+        32 + // player
+        4 * 3 + // wins, losses, draws
+        8 + // points
+        8 + // net_winnings
+        8 // created_at
+    }
+
+    pub fn new(bump: u8, season: Pubkey, player: Pubkey, created_at: i64) -> Self {
+        Self {
+            bump,
+            season,
+            player,
+            wins: 0,
+            losses: 0,
+            draws: 0,
+            points: 0,
+            net_winnings: 0,
+            created_at,
+        }
+    }
+
+    /// Checks the entry belongs to `player` in `season`. Entries are only
+    /// ever created by `join_season`, so their fields can be trusted.
+    pub fn check(&self, season: &Pubkey, player: &Pubkey) -> Result<()> {
+        require!(
+            self.season == *season && self.player == *player,
+            RockPaperScissorsError::SeasonMismatch
+        );
+        Ok(())
+    }
+
+    /// Records a settled game from the point of view of `player`, its seat.
+    pub fn record(&mut self, player: &Player, winner: &Option<Player>, net_winnings: i64) {
+        match winner {
+            None => {
+                self.draws = self.draws.saturating_add(1);
+                self.points = self.points.saturating_add(SEASON_DRAW_POINTS);
+            }
+            Some(winner) if winner == player => {
+                self.wins = self.wins.saturating_add(1);
+                self.points = self.points.saturating_add(SEASON_WIN_POINTS);
+            }
+            Some(_) => self.losses = self.losses.saturating_add(1),
+        }
+        self.net_winnings = self.net_winnings.saturating_add(net_winnings);
+    }
+
+    pub fn score(&self, ranking: &SeasonRanking) -> i64 {
+        match ranking {
+            SeasonRanking::Points => i64::try_from(self.points).unwrap_or(i64::MAX),
+            SeasonRanking::NetWinnings { .. } => self.net_winnings,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn season(leaderboard_size: u8) -> Season {
+        Season::new(
+            255,
+            1,
+            0,
+            100,
+            SeasonRanking::Points,
+            2_000,
+            vec![7_000, 3_000],
+            leaderboard_size,
+            0,
+        )
+    }
+
+    #[test]
+    fn leaderboard_keeps_the_best_scores_in_order() {
+        let mut season = season(3);
+        let players: Vec<_> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        season.rank(players[0], 3);
+        season.rank(players[1], 6);
+        season.rank(players[2], 3);
+        // Ties rank behind whoever reached the score first.
+        assert_eq!(season.place_of(&players[0]), Some(1));
+        assert_eq!(season.place_of(&players[2]), Some(2));
+
+        // Full, so a better score pushes the last place off.
+        season.rank(players[3], 4);
+        assert_eq!(season.place_of(&players[3]), Some(1));
+        assert_eq!(season.place_of(&players[2]), None);
+        assert_eq!(season.leaderboard.len(), 3);
+
+        // Moving up doesn't duplicate the player.
+        season.rank(players[0], 9);
+        assert_eq!(season.place_of(&players[0]), Some(0));
+        assert_eq!(season.leaderboard.len(), 3);
+
+        // Nor does a score too low to stay on a full board.
+        season.rank(players[2], 1);
+        assert_eq!(season.place_of(&players[2]), None);
+
+        // Falling to zero or below leaves the board.
+        season.rank(players[1], 0);
+        assert_eq!(season.place_of(&players[1]), None);
+        assert_eq!(season.leaderboard.len(), 2);
+    }
+
+    #[test]
+    fn entries_left_off_can_be_ranked_until_claims_open() {
+        let mut season = season(1);
+        season.ranking = SeasonRanking::NetWinnings {
+            mint: Pubkey::default(),
+        };
+        let mut leader = SeasonEntry::new(255, Pubkey::new_unique(), Pubkey::new_unique(), 0);
+        let mut chaser = SeasonEntry::new(255, Pubkey::new_unique(), Pubkey::new_unique(), 0);
+        leader.record(&Player::First, &Some(Player::First), 100);
+        season.rank(leader.player, leader.net_winnings);
+        chaser.record(&Player::First, &Some(Player::First), 50);
+        season.rank(chaser.player, chaser.net_winnings);
+        assert_eq!(season.place_of(&chaser.player), None);
+
+        // The leader loses part of their winnings, settlement only re-ranks
+        // them.
+        leader.record(&Player::First, &Some(Player::Second), -80);
+        season.rank(leader.player, leader.net_winnings);
+        assert_eq!(season.place_of(&leader.player), Some(0));
+
+        assert_eq!(
+            season.rank_entry(&leader, 10).unwrap_err(),
+            RockPaperScissorsError::SeasonEntryAlreadyRanked.into()
+        );
+        assert_eq!(
+            season
+                .rank_entry(&chaser, season.claims_open_at())
+                .unwrap_err(),
+            RockPaperScissorsError::SeasonStandingsFinal.into()
+        );
+        assert_eq!(season.rank_entry(&chaser, season.end_at).unwrap(), 50);
+        assert_eq!(season.place_of(&chaser.player), Some(0));
+        assert_eq!(season.place_of(&leader.player), None);
+    }
+
+    #[test]
+    fn prizes_split_the_locked_pool() {
+        let mut season = season(3);
+        assert_eq!(season.fee_share(1_000).unwrap(), 200);
+        assert_eq!(season.lock_prize_pool(1_000), 1_000);
+        // Later claims pay out of the same pool.
+        assert_eq!(season.lock_prize_pool(300), 1_000);
+        assert_eq!(season.prize_for(0).unwrap(), 700);
+        assert_eq!(season.prize_for(1).unwrap(), 300);
+        assert!(season.prize_for(2).is_err());
+
+        season.set_prize_claimed(1);
+        assert!(season.is_prize_claimed(1));
+        assert!(!season.is_prize_claimed(0));
+    }
+
+    #[test]
+    fn entries_score_by_ranking() {
+        let mut entry = SeasonEntry::new(255, Pubkey::new_unique(), Pubkey::new_unique(), 0);
+        entry.record(&Player::First, &Some(Player::First), 90);
+        entry.record(&Player::First, &None, -10);
+        entry.record(&Player::First, &Some(Player::Second), -100);
+        assert_eq!(entry.score(&SeasonRanking::Points), 4);
+        assert_eq!(
+            entry.score(&SeasonRanking::NetWinnings {
+                mint: Pubkey::default()
+            }),
+            -20
+        );
+    }
+}
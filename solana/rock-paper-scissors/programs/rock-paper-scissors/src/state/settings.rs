@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use crate::{error::RockPaperScissorsError, MAX_BPS, PAUSE_ALL};
+
 #[account]
 pub struct Settings {
     pub bump: u8,
@@ -7,6 +9,28 @@ pub struct Settings {
     pub time_for_stale: i64,
     pub treasury: Pubkey,
     pub player_fee_lamports: u64,
+    /// Share of each escrow taken at settlement, in basis points.
+    pub rake_bps: u16,
+    /// Whether draws are raked too, decided games always are.
+    pub rake_draws: bool,
+    /// How long a settled game is kept before anyone can close it.
+    pub game_retention_period: i64,
+    /// Signs every settings change, separate from the fee `treasury`.
+    pub admin: Pubkey,
+    /// Admin proposed by the current one, who has yet to accept.
+    pub pending_admin: Option<Pubkey>,
+    /// Bitmask of `PAUSE_*` flags. Cancelling, unwinding and settling are
+    /// never paused so players can always recover their funds.
+    pub paused: u8,
+    /// Bounds on the windows a creator may pick instead of the defaults
+    /// above. Zeroed until the admin sets them, which disables picking.
+    pub min_time_for_penalization: i64,
+    pub max_time_for_penalization: i64,
+    pub min_time_for_stale: i64,
+    pub max_time_for_stale: i64,
+    /// Share of the fees and rake a referred player pays that goes to their
+    /// referrer instead of the treasury, in basis points.
+    pub referral_share_bps: u16,
 }
 
 impl Settings {
@@ -16,15 +40,31 @@ impl Settings {
         8 + // time_for_penalization
         8 + // time_for_stale
         32 + // treasury
-        8 // player_fee_lamports
+        8 + // player_fee_lamports
+        2 + // rake_bps
+        1 + // rake_draws
+        8 + // game_retention_period
+        32 + // admin
+        1 + 32 + // pending_admin
+        1 + // paused
+        8 + // min_time_for_penalization
+        8 + // max_time_for_penalization
+        8 + // min_time_for_stale
+        8 + // max_time_for_stale
+        2 // referral_share_bps
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         bump: u8,
         time_for_penalization: i64,
         time_for_stale: i64,
         treasury: Pubkey,
         player_fee_lamports: u64,
+        rake_bps: u16,
+        rake_draws: bool,
+        game_retention_period: i64,
+        admin: Pubkey,
     ) -> Self {
         Self {
             bump,
@@ -32,6 +72,177 @@ impl Settings {
             time_for_stale,
             treasury,
             player_fee_lamports,
+            rake_bps,
+            rake_draws,
+            game_retention_period,
+            admin,
+            pending_admin: None,
+            paused: 0,
+            min_time_for_penalization: 0,
+            max_time_for_penalization: 0,
+            min_time_for_stale: 0,
+            max_time_for_stale: 0,
+            referral_share_bps: 0,
+        }
+    }
+
+    pub fn is_valid_rake(rake_bps: u16) -> bool {
+        rake_bps <= MAX_BPS
+    }
+
+    pub fn is_valid_pause(paused: u8) -> bool {
+        paused & !PAUSE_ALL == 0
+    }
+
+    pub fn is_paused(&self, flag: u8) -> bool {
+        self.paused & flag != 0
+    }
+
+    pub fn is_valid_window_bounds(min: i64, max: i64) -> bool {
+        0 < min && min <= max
+    }
+
+    /// The creator's window if within bounds, the default otherwise.
+    pub fn pick_time_for_penalization(&self, requested: Option<i64>) -> Result<i64> {
+        Self::pick_window(
+            requested,
+            self.time_for_penalization,
+            self.min_time_for_penalization,
+            self.max_time_for_penalization,
+        )
+    }
+
+    pub fn pick_time_for_stale(&self, requested: Option<i64>) -> Result<i64> {
+        Self::pick_window(
+            requested,
+            self.time_for_stale,
+            self.min_time_for_stale,
+            self.max_time_for_stale,
+        )
+    }
+
+    fn pick_window(requested: Option<i64>, default: i64, min: i64, max: i64) -> Result<i64> {
+        match requested {
+            Some(window) => {
+                require!(
+                    Self::is_valid_window_bounds(min, max) && (min..=max).contains(&window),
+                    RockPaperScissorsError::WindowOutOfBounds
+                );
+                Ok(window)
+            }
+            None => Ok(default),
         }
     }
+
+    pub fn rake_terms(&self) -> RakeTerms {
+        RakeTerms {
+            rake_bps: self.rake_bps,
+            rake_draws: self.rake_draws,
+            referral_share_bps: self.referral_share_bps,
+        }
+    }
+
+    pub fn rake_for(&self, amount: u64) -> Result<u64> {
+        self.rake_terms().rake_for(amount)
+    }
+
+    /// Referrer's part of `amount`, a fee or rake paid by a referred player.
+    pub fn referral_share(&self, amount: u64) -> Result<u64> {
+        self.rake_terms().referral_share(amount)
+    }
+}
+
+/// Rake rules of the settings, copied onto games when they're created so
+/// changes don't reach wagers already in play.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct RakeTerms {
+    pub rake_bps: u16,
+    pub rake_draws: bool,
+    pub referral_share_bps: u16,
+}
+
+impl RakeTerms {
+    pub fn size() -> usize {
+        2 + // rake_bps
+        1 + // rake_draws
+        2 // referral_share_bps
+    }
+
+    pub fn rake_for(&self, amount: u64) -> Result<u64> {
+        Self::bps_of(amount, self.rake_bps)
+    }
+
+    /// Referrer's part of `amount`, a fee or rake paid by a referred player.
+    pub fn referral_share(&self, amount: u64) -> Result<u64> {
+        Self::bps_of(amount, self.referral_share_bps)
+    }
+
+    fn bps_of(amount: u64, bps: u16) -> Result<u64> {
+        let share = (amount as u128)
+            .checked_mul(bps as u128)
+            .ok_or(RockPaperScissorsError::NumericOverflow)?
+            / MAX_BPS as u128;
+        Ok(share as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pick_window_checks_bounds() {
+        let mut settings = Settings::new(
+            255,
+            50,
+            100,
+            Pubkey::new_unique(),
+            0,
+            0,
+            false,
+            0,
+            Pubkey::new_unique(),
+        );
+        assert_eq!(settings.pick_time_for_penalization(None).unwrap(), 50);
+        // Picking is disabled until the admin sets bounds.
+        assert_eq!(
+            settings.pick_time_for_penalization(Some(50)).unwrap_err(),
+            RockPaperScissorsError::WindowOutOfBounds.into()
+        );
+
+        settings.min_time_for_penalization = 10;
+        settings.max_time_for_penalization = 60;
+        assert_eq!(settings.pick_time_for_penalization(Some(10)).unwrap(), 10);
+        assert_eq!(settings.pick_time_for_penalization(Some(60)).unwrap(), 60);
+        assert_eq!(
+            settings.pick_time_for_penalization(Some(61)).unwrap_err(),
+            RockPaperScissorsError::WindowOutOfBounds.into()
+        );
+        assert_eq!(
+            settings.pick_time_for_stale(Some(10)).unwrap_err(),
+            RockPaperScissorsError::WindowOutOfBounds.into()
+        );
+    }
+
+    #[test]
+    fn referral_share_rounds_down() {
+        let mut settings = Settings::new(
+            255,
+            50,
+            100,
+            Pubkey::new_unique(),
+            0,
+            0,
+            false,
+            0,
+            Pubkey::new_unique(),
+        );
+        // Nothing is shared until the admin sets a share.
+        assert_eq!(settings.referral_share(1_000).unwrap(), 0);
+
+        settings.referral_share_bps = 2_500;
+        assert_eq!(settings.referral_share(1_000).unwrap(), 250);
+        assert_eq!(settings.referral_share(3).unwrap(), 0);
+        assert_eq!(settings.referral_share(u64::MAX).unwrap(), u64::MAX / 4);
+    }
 }
@@ -0,0 +1,258 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::RockPaperScissorsError, Game, Player};
+
+/// What a side bet backs.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub enum SideBetOutcome {
+    FirstPlayer,
+    SecondPlayer,
+    Draw,
+}
+
+impl SideBetOutcome {
+    /// `None` being a draw, as in `Settlement::winner`.
+    pub fn from_winner(winner: &Option<Player>) -> Self {
+        match winner {
+            Some(Player::First) => Self::FirstPlayer,
+            Some(Player::Second) => Self::SecondPlayer,
+            None => Self::Draw,
+        }
+    }
+}
+
+/// How a side pool ended.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub enum SidePoolResult {
+    /// The game settled with this outcome.
+    Settled(SideBetOutcome),
+    /// The game was unwound, every bet is refunded.
+    Refunded,
+}
+
+impl SidePoolResult {
+    pub fn size() -> usize {
+        1 + // Variant
+        1 // outcome
+    }
+}
+
+/// Spectators' pot on a started game, paid out pari-mutuel once it settles.
+/// Bets are held by this account in lamports on top of its rent, whatever
+/// the game wagers, and close with the game's first reveal.
+#[account]
+pub struct SidePool {
+    pub bump: u8,
+    pub game: Pubkey,
+    /// Paid for the rent, refunded by `close_side_pool`.
+    pub creator: Pubkey,
+
+    pub first_player_total: u64,
+    pub second_player_total: u64,
+    pub draw_total: u64,
+    /// Bets not claimed yet, the pool closes once there are none left.
+    pub open_bets: u32,
+
+    /// Set when the game settles or unwinds.
+    pub result: Option<SidePoolResult>,
+    pub created_at: i64,
+}
+
+impl SidePool {
+    pub fn size() -> usize {
+        8 + // Discriminator
+        1 + // bump
+        32 + // game
+        32 + // creator
+        8 + // first_player_total
+        8 + // second_player_total
+        8 + // draw_total
+        4 + // open_bets
+        (1 + SidePoolResult::size()) + // result
+        8 // created_at
+    }
+
+    pub fn new(bump: u8, game: Pubkey, creator: Pubkey, created_at: i64) -> Self {
+        Self {
+            bump,
+            game,
+            creator,
+            first_player_total: 0,
+            second_player_total: 0,
+            draw_total: 0,
+            open_bets: 0,
+            result: None,
+            created_at,
+        }
+    }
+
+    /// Bets are taken until either player reveals a choice.
+    pub fn is_open(&self, game: &Game) -> bool {
+        self.result.is_none() && !game.has_revealed()
+    }
+
+    pub fn total(&self) -> Result<u64> {
+        self.first_player_total
+            .checked_add(self.second_player_total)
+            .and_then(|total| total.checked_add(self.draw_total))
+            .ok_or(RockPaperScissorsError::NumericOverflow.into())
+    }
+
+    pub fn total_on(&self, outcome: &SideBetOutcome) -> u64 {
+        match outcome {
+            SideBetOutcome::FirstPlayer => self.first_player_total,
+            SideBetOutcome::SecondPlayer => self.second_player_total,
+            SideBetOutcome::Draw => self.draw_total,
+        }
+    }
+
+    pub fn record_bet(&mut self, outcome: &SideBetOutcome, amount: u64) -> Result<()> {
+        let total = match outcome {
+            SideBetOutcome::FirstPlayer => &mut self.first_player_total,
+            SideBetOutcome::SecondPlayer => &mut self.second_player_total,
+            SideBetOutcome::Draw => &mut self.draw_total,
+        };
+        *total = total
+            .checked_add(amount)
+            .ok_or(RockPaperScissorsError::NumericOverflow)?;
+        self.open_bets += 1;
+        Ok(())
+    }
+
+    /// Records how the game ended on its pool, which must be passed once the
+    /// game has one. `None` for games without a pool.
+    pub fn resolve<'a, 'info>(
+        pool: Option<&'a mut Account<'info, SidePool>>,
+        game: &Game,
+        result: SidePoolResult,
+    ) -> Result<Option<&'a mut Account<'info, SidePool>>> {
+        let Some(expected) = game.side_pool else {
+            return Ok(None);
+        };
+        let pool = pool.ok_or(RockPaperScissorsError::SidePoolMismatch)?;
+        require_keys_eq!(
+            pool.key(),
+            expected,
+            RockPaperScissorsError::SidePoolMismatch
+        );
+        pool.result = Some(result);
+        Ok(Some(pool))
+    }
+
+    /// What `bet` is paid back: its share of the whole pot if it backed the
+    /// outcome, nothing otherwise. Bets are refunded when the game unwound or
+    /// nobody backed the outcome.
+    pub fn payout_for(&self, bet: &SideBet) -> Result<u64> {
+        let outcome = match &self.result {
+            None => return err!(RockPaperScissorsError::SidePoolNotResolved),
+            Some(SidePoolResult::Refunded) => return Ok(bet.amount),
+            Some(SidePoolResult::Settled(outcome)) => outcome,
+        };
+        let winning_total = self.total_on(outcome);
+        if winning_total == 0 {
+            return Ok(bet.amount);
+        }
+        if &bet.outcome != outcome {
+            return Ok(0);
+        }
+        // Rounds down, the dust goes to the creator with the rent.
+        let payout = (bet.amount as u128)
+            .checked_mul(self.total()? as u128)
+            .ok_or(RockPaperScissorsError::NumericOverflow)?
+            / winning_total as u128;
+        Ok(payout as u64)
+    }
+}
+
+/// One spectator's bet in a side pool.
+#[account]
+pub struct SideBet {
+    pub bump: u8,
+    pub side_pool: Pubkey,
+    pub bettor: Pubkey,
+    pub outcome: SideBetOutcome,
+    pub amount: u64,
+    pub placed_at: i64,
+}
+
+impl SideBet {
+    pub fn size() -> usize {
+        8 + // Discriminator
+        1 + // bump
+        32 + // side_pool
+        32 + // bettor
+        1 + // outcome
+        8 + // amount
+        8 // placed_at
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bet(outcome: SideBetOutcome, amount: u64) -> SideBet {
+        SideBet {
+            bump: 255,
+            side_pool: Pubkey::new_unique(),
+            bettor: Pubkey::new_unique(),
+            outcome,
+            amount,
+            placed_at: 0,
+        }
+    }
+
+    #[test]
+    fn winners_split_the_pot_pro_rata() {
+        let mut pool = SidePool::new(255, Pubkey::new_unique(), Pubkey::new_unique(), 0);
+        pool.record_bet(&SideBetOutcome::FirstPlayer, 100).unwrap();
+        pool.record_bet(&SideBetOutcome::FirstPlayer, 200).unwrap();
+        pool.record_bet(&SideBetOutcome::SecondPlayer, 400).unwrap();
+        pool.record_bet(&SideBetOutcome::Draw, 50).unwrap();
+        assert_eq!(pool.open_bets, 4);
+        assert_eq!(
+            pool.payout_for(&bet(SideBetOutcome::FirstPlayer, 100))
+                .unwrap_err(),
+            RockPaperScissorsError::SidePoolNotResolved.into()
+        );
+
+        pool.result = Some(SidePoolResult::Settled(SideBetOutcome::FirstPlayer));
+        assert_eq!(
+            pool.payout_for(&bet(SideBetOutcome::FirstPlayer, 100))
+                .unwrap(),
+            250
+        );
+        assert_eq!(
+            pool.payout_for(&bet(SideBetOutcome::FirstPlayer, 200))
+                .unwrap(),
+            500
+        );
+        assert_eq!(
+            pool.payout_for(&bet(SideBetOutcome::SecondPlayer, 400))
+                .unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn bets_are_refunded_without_winners() {
+        let mut pool = SidePool::new(255, Pubkey::new_unique(), Pubkey::new_unique(), 0);
+        pool.record_bet(&SideBetOutcome::FirstPlayer, 100).unwrap();
+        pool.record_bet(&SideBetOutcome::SecondPlayer, 300).unwrap();
+
+        // Nobody backed a draw.
+        pool.result = Some(SidePoolResult::Settled(SideBetOutcome::Draw));
+        assert_eq!(
+            pool.payout_for(&bet(SideBetOutcome::SecondPlayer, 300))
+                .unwrap(),
+            300
+        );
+
+        pool.result = Some(SidePoolResult::Refunded);
+        assert_eq!(
+            pool.payout_for(&bet(SideBetOutcome::FirstPlayer, 100))
+                .unwrap(),
+            100
+        );
+    }
+}
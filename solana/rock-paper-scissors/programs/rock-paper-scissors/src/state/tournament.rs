@@ -0,0 +1,538 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RockPaperScissorsError, GameVariant, MAX_BPS, MAX_PRIZE_PLACES, MAX_TOURNAMENT_PLAYERS,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub enum TournamentState {
+    Registration,
+    Running,
+    Finished,
+}
+
+/// One pairing of the current round.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct TournamentMatch {
+    /// Higher seed, advances on a walkover when neither player showed up.
+    pub first_player: Pubkey,
+    pub second_player: Pubkey,
+    /// Game deciding the match, `None` until one is created and again after
+    /// a draw, which is replayed.
+    pub game: Option<Pubkey>,
+    pub winner: Option<Pubkey>,
+    /// Set when a draw is settled, giving the replay as long as a round to
+    /// be played, even past the round deadline.
+    pub replay_deadline: Option<i64>,
+}
+
+impl TournamentMatch {
+    pub fn size() -> usize {
+        32 + // first_player
+        32 + // second_player
+        (1 + 32) + // game
+        (1 + 32) + // winner
+        (1 + 8) // replay_deadline
+    }
+
+    pub fn new(first_player: Pubkey, second_player: Pubkey) -> Self {
+        Self {
+            first_player,
+            second_player,
+            game: None,
+            winner: None,
+            replay_deadline: None,
+        }
+    }
+
+    pub fn opponent_of(&self, player: &Pubkey) -> Option<Pubkey> {
+        if *player == self.first_player {
+            Some(self.second_player)
+        } else if *player == self.second_player {
+            Some(self.first_player)
+        } else {
+            None
+        }
+    }
+}
+
+/// Single elimination bracket whose matches are played as regular games.
+/// Entry fees are held by the tournament account on top of its rent and paid
+/// out to the top finishers once the final is decided.
+#[account]
+pub struct Tournament {
+    pub bump: u8,
+    pub organizer: Pubkey,
+    pub tournament_id: String,
+    /// Lamports each player pays to register.
+    pub entry_fee: u64,
+    /// Size of the bracket, a power of two.
+    pub max_players: u8,
+    /// Rules every match game is played with.
+    pub best_of: u8,
+    pub variant: GameVariant,
+    pub time_for_penalization: i64,
+    pub time_for_stale: i64,
+    /// Share of the prize pool per finishing place, champion first.
+    pub prize_split_bps: Vec<u16>,
+
+    pub state: TournamentState,
+    /// In registration order until the tournament starts, bracket order after.
+    pub players: Vec<Pubkey>,
+    /// Zero based, the final is round `log2(max_players) - 1`.
+    pub round: u8,
+    pub round_started_at: Option<i64>,
+    pub matches: Vec<TournamentMatch>,
+    /// Knocked out players, in the order they lost.
+    pub eliminated: Vec<Pubkey>,
+
+    pub prize_pool: u64,
+    /// Bit `n` is set once place `n`'s prize is paid.
+    pub claimed_places: u8,
+    pub created_at: i64,
+    /// When the final was decided.
+    pub finished_at: Option<i64>,
+}
+
+impl Tournament {
+    pub fn size(max_players: u8) -> usize {
+        let max_players = max_players as usize;
+        8 + // Discriminator
+        1 + // bump
+        32 + // organizer
+        (4 + 32) + // tournament_id
+        8 + // entry_fee
+        1 + // max_players
+        1 + // best_of
+        1 + // variant
+        8 + // time_for_penalization
+        8 + // time_for_stale
+        (4 + 2 * MAX_PRIZE_PLACES as usize) + // prize_split_bps
+        1 + // state
+        (4 + 32 * max_players) + // players
+        1 + // round
+        (1 + 8) + // round_started_at
+        (4 + TournamentMatch::size() * max_players / 2) + // matches
+        (4 + 32 * max_players) + // eliminated
+        8 + // prize_pool
+        1 + // claimed_places
+        8 + // created_at
+        (1 + 8) // finished_at
+    }
+
+    pub fn is_valid_max_players(max_players: u8) -> bool {
+        max_players.is_power_of_two() && (2..=MAX_TOURNAMENT_PLAYERS).contains(&max_players)
+    }
+
+    /// One share per paid place, at most one place per player, adding up to
+    /// the whole pool. Players knocked out in the same round, places `2^n` to
+    /// `2^(n+1) - 1`, are all paid the same, so paid places come in whole
+    /// rounds.
+    pub fn is_valid_prize_split(prize_split_bps: &[u16], max_players: u8) -> bool {
+        let places = prize_split_bps.len();
+        places.is_power_of_two()
+            && places <= MAX_PRIZE_PLACES as usize
+            && places <= max_players as usize
+            && (1..places)
+                .all(|place| prize_split_bps[place] == prize_split_bps[1 << place.ilog2()])
+            && prize_split_bps.iter().map(|bps| *bps as u32).sum::<u32>() == MAX_BPS as u32
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        bump: u8,
+        organizer: Pubkey,
+        tournament_id: String,
+        entry_fee: u64,
+        max_players: u8,
+        best_of: u8,
+        variant: GameVariant,
+        time_for_penalization: i64,
+        time_for_stale: i64,
+        prize_split_bps: Vec<u16>,
+        created_at: i64,
+    ) -> Self {
+        Self {
+            bump,
+            organizer,
+            tournament_id,
+            entry_fee,
+            max_players,
+            best_of,
+            variant,
+            time_for_penalization,
+            time_for_stale,
+            prize_split_bps,
+            state: TournamentState::Registration,
+            players: vec![],
+            round: 0,
+            round_started_at: None,
+            matches: vec![],
+            eliminated: vec![],
+            prize_pool: 0,
+            claimed_places: 0,
+            created_at,
+            finished_at: None,
+        }
+    }
+
+    /// Game id of a match game, unique per tournament, round and match while
+    /// fitting `Game::game_id`.
+    pub fn game_id(tournament: &Pubkey, round: u8, match_index: u8) -> String {
+        let prefix: String = tournament.as_ref()[..8]
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+        format!("{}-{}-{}", prefix, round, match_index)
+    }
+
+    pub fn is_registered(&self, player: &Pubkey) -> bool {
+        self.players.contains(player)
+    }
+
+    pub fn register(&mut self, player: Pubkey) -> Result<()> {
+        require!(
+            self.state == TournamentState::Registration,
+            RockPaperScissorsError::InvalidTournamentState
+        );
+        require!(
+            self.players.len() < self.max_players as usize,
+            RockPaperScissorsError::TournamentFull
+        );
+        require!(
+            !self.is_registered(&player),
+            RockPaperScissorsError::AlreadyRegistered
+        );
+        self.players.push(player);
+        Ok(())
+    }
+
+    pub fn leave(&mut self, player: &Pubkey) -> Result<()> {
+        require!(
+            self.state == TournamentState::Registration,
+            RockPaperScissorsError::InvalidTournamentState
+        );
+        let index = self
+            .players
+            .iter()
+            .position(|registered| registered == player)
+            .ok_or(RockPaperScissorsError::NotRegistered)?;
+        self.players.remove(index);
+        Ok(())
+    }
+
+    /// Orders the full field by `seeding`, a permutation of registration
+    /// indexes, and pairs neighbouring slots for the first round.
+    pub fn start(&mut self, seeding: &[u8], now: i64) -> Result<()> {
+        require!(
+            self.state == TournamentState::Registration,
+            RockPaperScissorsError::InvalidTournamentState
+        );
+        require!(
+            self.players.len() == self.max_players as usize,
+            RockPaperScissorsError::TournamentNotFull
+        );
+        require!(
+            seeding.len() == self.players.len(),
+            RockPaperScissorsError::InvalidSeeding
+        );
+        let mut seen = 0u32;
+        for index in seeding {
+            require!(
+                (*index as usize) < self.players.len() && seen & (1 << index) == 0,
+                RockPaperScissorsError::InvalidSeeding
+            );
+            seen |= 1 << index;
+        }
+
+        self.players = seeding
+            .iter()
+            .map(|index| self.players[*index as usize])
+            .collect();
+        self.prize_pool = self
+            .entry_fee
+            .checked_mul(self.max_players as u64)
+            .ok_or(RockPaperScissorsError::NumericOverflow)?;
+        self.matches = Self::pair(&self.players);
+        self.state = TournamentState::Running;
+        self.round = 0;
+        self.round_started_at = Some(now);
+        Ok(())
+    }
+
+    fn pair(players: &[Pubkey]) -> Vec<TournamentMatch> {
+        players
+            .chunks(2)
+            .map(|pair| TournamentMatch::new(pair[0], pair[1]))
+            .collect()
+    }
+
+    pub fn match_at(&self, match_index: u8) -> Result<&TournamentMatch> {
+        require!(
+            self.state == TournamentState::Running,
+            RockPaperScissorsError::InvalidTournamentState
+        );
+        self.matches
+            .get(match_index as usize)
+            .ok_or(RockPaperScissorsError::InvalidMatch.into())
+    }
+
+    fn match_at_mut(&mut self, match_index: u8) -> Result<&mut TournamentMatch> {
+        self.match_at(match_index)?;
+        Ok(&mut self.matches[match_index as usize])
+    }
+
+    /// Ties a newly created game to an undecided match it isn't already
+    /// being played in.
+    pub fn set_game(&mut self, match_index: u8, game: Pubkey) -> Result<()> {
+        let tournament_match = self.match_at_mut(match_index)?;
+        require!(
+            tournament_match.winner.is_none() && tournament_match.game.is_none(),
+            RockPaperScissorsError::InvalidMatch
+        );
+        tournament_match.game = Some(game);
+        Ok(())
+    }
+
+    /// Frees the match for a replay after its game ended without a winner.
+    pub fn clear_game(&mut self, match_index: u8, now: i64) -> Result<()> {
+        let time_for_stale = self.time_for_stale;
+        let tournament_match = self.match_at_mut(match_index)?;
+        tournament_match.game = None;
+        tournament_match.replay_deadline = Some(now + time_for_stale);
+        Ok(())
+    }
+
+    pub fn round_deadline(&self) -> Option<i64> {
+        self.round_started_at
+            .map(|round_started_at| round_started_at + self.time_for_stale)
+    }
+
+    /// After this, a match nobody finished can be decided by walkover, the
+    /// round deadline unless a replay was freed later.
+    pub fn match_deadline(&self, match_index: u8) -> Result<Option<i64>> {
+        let replay_deadline = self.match_at(match_index)?.replay_deadline;
+        Ok(self.round_deadline().max(replay_deadline))
+    }
+
+    /// Records the match winner, moving on to the next round once every
+    /// match of this one is decided, or finishing the tournament after the
+    /// final.
+    pub fn record_winner(&mut self, match_index: u8, winner: Pubkey, now: i64) -> Result<()> {
+        let tournament_match = self.match_at_mut(match_index)?;
+        require!(
+            tournament_match.winner.is_none(),
+            RockPaperScissorsError::InvalidMatch
+        );
+        let loser = tournament_match
+            .opponent_of(&winner)
+            .ok_or(RockPaperScissorsError::InvalidPlayer)?;
+        tournament_match.winner = Some(winner);
+        tournament_match.game = None;
+        self.eliminated.push(loser);
+
+        let winners: Option<Vec<Pubkey>> = self
+            .matches
+            .iter()
+            .map(|tournament_match| tournament_match.winner)
+            .collect();
+        if let Some(winners) = winners {
+            if winners.len() == 1 {
+                self.state = TournamentState::Finished;
+                self.finished_at = Some(now);
+            } else {
+                self.matches = Self::pair(&winners);
+                self.round += 1;
+                self.round_started_at = Some(now);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn champion(&self) -> Option<Pubkey> {
+        match self.state {
+            TournamentState::Finished => self.matches.first().and_then(|final_| final_.winner),
+            _ => None,
+        }
+    }
+
+    /// Zero based finishing place: the champion, then knocked out players,
+    /// later losers placing higher. Players knocked out in the same round
+    /// place in the reverse order they lost, which doesn't change their prize.
+    /// `None` before the tournament finishes.
+    pub fn place_of(&self, player: &Pubkey) -> Option<usize> {
+        if self.champion()? == *player {
+            return Some(0);
+        }
+        self.eliminated
+            .iter()
+            .rev()
+            .position(|eliminated| eliminated == player)
+            .map(|index| index + 1)
+    }
+
+    pub fn prize_for(&self, place: usize) -> Result<u64> {
+        let bps = self
+            .prize_split_bps
+            .get(place)
+            .ok_or(RockPaperScissorsError::NoPrizeToClaim)?;
+        (self.prize_pool as u128)
+            .checked_mul(*bps as u128)
+            .and_then(|value| value.checked_div(MAX_BPS as u128))
+            .and_then(|value| u64::try_from(value).ok())
+            .ok_or(RockPaperScissorsError::NumericOverflow.into())
+    }
+
+    pub fn is_prize_claimed(&self, place: usize) -> bool {
+        self.claimed_places & (1 << place) != 0
+    }
+
+    pub fn set_prize_claimed(&mut self, place: usize) {
+        self.claimed_places |= 1 << place;
+    }
+
+    /// Prizes of the paid places that weren't claimed.
+    pub fn unclaimed_prizes(&self) -> Result<u64> {
+        (0..self.prize_split_bps.len())
+            .filter(|place| !self.is_prize_claimed(*place))
+            .try_fold(0u64, |total, place| {
+                total
+                    .checked_add(self.prize_for(place)?)
+                    .ok_or(RockPaperScissorsError::NumericOverflow.into())
+            })
+    }
+
+    /// Nothing left to pay out: nobody is registered, or every paid place
+    /// claimed its prize. Prizes still unclaimed `claim_period` after the
+    /// final go back to the organizer with the account.
+    pub fn is_closable(&self, now: i64, claim_period: i64) -> bool {
+        match self.state {
+            TournamentState::Registration => self.players.is_empty(),
+            TournamentState::Running => false,
+            TournamentState::Finished => {
+                (0..self.prize_split_bps.len()).all(|place| self.is_prize_claimed(place))
+                    || self
+                        .finished_at
+                        .is_some_and(|finished_at| finished_at + claim_period <= now)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tournament(max_players: u8) -> Tournament {
+        Tournament::new(
+            255,
+            Pubkey::new_unique(),
+            "cup".to_string(),
+            100,
+            max_players,
+            1,
+            GameVariant::Classic,
+            50,
+            100,
+            vec![7_000, 3_000],
+            0,
+        )
+    }
+
+    #[test]
+    fn plays_out_a_bracket() {
+        let mut tournament = tournament(4);
+        let players: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        for player in &players {
+            tournament.register(*player).unwrap();
+        }
+        assert!(tournament.register(Pubkey::new_unique()).is_err());
+        assert!(tournament.start(&[0, 1, 2, 2], 10).is_err());
+
+        tournament.start(&[3, 0, 1, 2], 10).unwrap();
+        assert_eq!(tournament.prize_pool, 400);
+        assert_eq!(
+            tournament.matches,
+            vec![
+                TournamentMatch::new(players[3], players[0]),
+                TournamentMatch::new(players[1], players[2]),
+            ]
+        );
+
+        // Outsiders can't win a match.
+        assert!(tournament
+            .record_winner(0, Pubkey::new_unique(), 20)
+            .is_err());
+        tournament.record_winner(0, players[0], 20).unwrap();
+        assert_eq!(tournament.round, 0);
+        tournament.record_winner(1, players[1], 30).unwrap();
+        assert_eq!(tournament.round, 1);
+        assert_eq!(tournament.round_deadline(), Some(130));
+        assert_eq!(
+            tournament.matches,
+            vec![TournamentMatch::new(players[0], players[1])]
+        );
+        assert_eq!(tournament.place_of(&players[0]), None);
+
+        tournament.record_winner(0, players[1], 40).unwrap();
+        assert_eq!(tournament.state, TournamentState::Finished);
+        assert_eq!(tournament.champion(), Some(players[1]));
+        assert_eq!(tournament.place_of(&players[1]), Some(0));
+        assert_eq!(tournament.place_of(&players[0]), Some(1));
+        assert_eq!(tournament.prize_for(0).unwrap(), 280);
+        assert_eq!(tournament.prize_for(1).unwrap(), 120);
+        assert!(tournament.prize_for(2).is_err());
+
+        assert_eq!(tournament.finished_at, Some(40));
+        assert!(!tournament.is_closable(40, 100));
+        tournament.set_prize_claimed(0);
+        assert_eq!(tournament.unclaimed_prizes().unwrap(), 120);
+        assert!(!tournament.is_closable(139, 100));
+        // The runner-up's prize can be swept once the claim period is over.
+        assert!(tournament.is_closable(140, 100));
+        tournament.set_prize_claimed(1);
+        assert!(tournament.is_closable(40, 100));
+    }
+
+    #[test]
+    fn checks_sizes_and_splits() {
+        assert!(Tournament::is_valid_max_players(2));
+        assert!(Tournament::is_valid_max_players(MAX_TOURNAMENT_PLAYERS));
+        assert!(!Tournament::is_valid_max_players(6));
+        assert!(!Tournament::is_valid_max_players(1));
+
+        assert!(Tournament::is_valid_prize_split(&[10_000], 2));
+        assert!(!Tournament::is_valid_prize_split(&[5_000, 3_000, 2_000], 2));
+        assert!(!Tournament::is_valid_prize_split(&[5_000, 4_000], 4));
+        // Both semifinal losers are paid, and paid the same.
+        assert!(Tournament::is_valid_prize_split(
+            &[5_000, 3_000, 1_000, 1_000],
+            4
+        ));
+        assert!(!Tournament::is_valid_prize_split(&[6_000, 3_000, 1_000], 4));
+        assert!(!Tournament::is_valid_prize_split(
+            &[5_000, 3_000, 1_500, 500],
+            4
+        ));
+        assert!(Tournament::game_id(&Pubkey::new_unique(), 3, 7).len() <= 32);
+    }
+
+    #[test]
+    fn gives_late_replays_a_deadline_of_their_own() {
+        let mut tournament = tournament(2);
+        let players = [Pubkey::new_unique(), Pubkey::new_unique()];
+        for player in &players {
+            tournament.register(*player).unwrap();
+        }
+        tournament.start(&[0, 1], 10).unwrap();
+        assert_eq!(tournament.match_deadline(0).unwrap(), Some(110));
+
+        // The drawn game is settled after the round deadline, the replay
+        // still gets a full window before anyone can claim a walkover.
+        tournament.set_game(0, Pubkey::new_unique()).unwrap();
+        tournament.clear_game(0, 150).unwrap();
+        assert_eq!(tournament.round_deadline(), Some(110));
+        assert_eq!(tournament.match_deadline(0).unwrap(), Some(250));
+        tournament.set_game(0, Pubkey::new_unique()).unwrap();
+    }
+}
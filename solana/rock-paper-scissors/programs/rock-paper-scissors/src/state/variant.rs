@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+
+use crate::Choice;
+
+/// Which moves a variant allows and which move beats which, indexed by the
+/// `Choice` discriminant.
+pub struct Rules {
+    /// Number of moves in the variant, `Choice`s past it are rejected.
+    pub moves: u8,
+    /// `beats[a]` has bit `b` set when `a` beats `b`.
+    pub beats: &'static [u8],
+}
+
+impl Rules {
+    pub fn allows(&self, choice: &Choice) -> bool {
+        (choice.clone() as u8) < self.moves
+    }
+
+    pub fn beats(&self, choice: &Choice, other: &Choice) -> bool {
+        self.beats[choice.clone() as usize] & (1 << (other.clone() as u8)) != 0
+    }
+}
+
+const ROCK: u8 = 1 << Choice::Rock as u8;
+const PAPER: u8 = 1 << Choice::Paper as u8;
+const SCISSORS: u8 = 1 << Choice::Scissors as u8;
+const LIZARD: u8 = 1 << Choice::Lizard as u8;
+const SPOCK: u8 = 1 << Choice::Spock as u8;
+
+const CLASSIC_RULES: Rules = Rules {
+    moves: 3,
+    beats: &[
+        SCISSORS, // Rock
+        ROCK,     // Paper
+        PAPER,    // Scissors
+    ],
+};
+
+const LIZARD_SPOCK_RULES: Rules = Rules {
+    moves: 5,
+    beats: &[
+        SCISSORS | LIZARD, // Rock
+        ROCK | SPOCK,      // Paper
+        PAPER | LIZARD,    // Scissors
+        PAPER | SPOCK,     // Lizard
+        ROCK | SCISSORS,   // Spock
+    ],
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub enum GameVariant {
+    /// Rock, paper, scissors.
+    Classic,
+    /// Rock, paper, scissors, lizard, Spock.
+    LizardSpock,
+}
+
+impl GameVariant {
+    pub fn rules(&self) -> &'static Rules {
+        match self {
+            Self::Classic => &CLASSIC_RULES,
+            Self::LizardSpock => &LIZARD_SPOCK_RULES,
+        }
+    }
+}
@@ -3,6 +3,8 @@ use anchor_lang::{
     solana_program::{program::invoke, system_instruction},
 };
 
+use crate::error::RockPaperScissorsError;
+
 pub struct TransferLamports<'info> {
     /// CHECK: Cpi Checked.
     pub from: AccountInfo<'info>,
@@ -26,3 +28,59 @@ pub fn transfer_lamports<'info>(
     )?;
     Ok(())
 }
+
+/// Moves lamports out of an account owned by this program, which the system
+/// program can't debit.
+pub fn withdraw_lamports<'info>(
+    from: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let from_lamports = from
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(RockPaperScissorsError::NumericOverflow)?;
+    let to_lamports = to
+        .lamports()
+        .checked_add(amount)
+        .ok_or(RockPaperScissorsError::NumericOverflow)?;
+    **from.try_borrow_mut_lamports()? = from_lamports;
+    **to.try_borrow_mut_lamports()? = to_lamports;
+    Ok(())
+}
+
+/// Pays a player fee, each share to its account and the rest to the
+/// treasury.
+pub fn transfer_player_fee<'info>(
+    from: &AccountInfo<'info>,
+    treasury: &AccountInfo<'info>,
+    shares: &[(AccountInfo<'info>, u64)],
+    system_program: &AccountInfo<'info>,
+    player_fee_lamports: u64,
+) -> Result<()> {
+    let mut rest = player_fee_lamports;
+    for (to, amount) in shares {
+        if *amount == 0 {
+            continue;
+        }
+        rest = rest
+            .checked_sub(*amount)
+            .ok_or(RockPaperScissorsError::NumericOverflow)?;
+        transfer_lamports(
+            TransferLamports {
+                from: from.clone(),
+                to: to.clone(),
+                system_program: system_program.clone(),
+            },
+            *amount,
+        )?;
+    }
+    transfer_lamports(
+        TransferLamports {
+            from: from.clone(),
+            to: treasury.clone(),
+            system_program: system_program.clone(),
+        },
+        rest,
+    )
+}
@@ -0,0 +1,24 @@
+use anchor_lang::{prelude::*, solana_program::hash::hashv};
+
+/// Prefixes keep a leaf from ever being read as an inner node.
+const LEAF_PREFIX: [u8; 1] = [0];
+const NODE_PREFIX: [u8; 1] = [1];
+
+/// Leaf of a player in an invite list's merkle tree.
+pub fn invite_leaf(player: &Pubkey) -> [u8; 32] {
+    hashv(&[&LEAF_PREFIX, player.as_ref()]).to_bytes()
+}
+
+/// Parent of two nodes, hashed in sorted order so proofs don't need to
+/// carry which side each sibling is on.
+pub fn merkle_parent(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    hashv(&[&NODE_PREFIX, left, right]).to_bytes()
+}
+
+pub fn verify_merkle_proof(root: &[u8; 32], leaf: [u8; 32], proof: &[[u8; 32]]) -> bool {
+    proof
+        .iter()
+        .fold(leaf, |node, sibling| merkle_parent(&node, sibling))
+        == *root
+}
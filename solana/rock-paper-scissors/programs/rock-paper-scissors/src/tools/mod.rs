@@ -1,7 +1,9 @@
 pub mod lamports;
+pub mod merkle;
 pub mod spl;
 pub mod vec;
 
 pub use lamports::*;
+pub use merkle::*;
 pub use spl::*;
 pub use vec::*;
@@ -1,35 +1,48 @@
 // Allow deprecated transfer for classic SPL Tokens.
 #![allow(deprecated)]
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, solana_program::program::invoke};
 use anchor_spl::token_interface::{
-    spl_token_2022, transfer, transfer_checked, Mint, TokenAccount, TokenInterface, Transfer,
-    TransferChecked,
+    close_account,
+    spl_token_2022::{
+        self,
+        extension::{
+            default_account_state::DefaultAccountState,
+            transfer_fee::{instruction::harvest_withheld_tokens_to_mint, TransferFeeAmount},
+            BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+        },
+        onchain::invoke_transfer_checked,
+        state::{Account as Token2022Account, AccountState, Mint as Token2022Mint},
+    },
+    transfer, CloseAccount, Mint, TokenAccount, TokenInterface, Transfer,
 };
 
+use crate::error::RockPaperScissorsError;
+
+/// Token-2022 transfers go through `invoke_transfer_checked`, which resolves
+/// the mint's transfer hook accounts, if any, out of `remaining_accounts`.
+#[allow(clippy::too_many_arguments)]
 pub fn transfer_spl_compatible<'info>(
     token_program: &Interface<'info, TokenInterface>,
     from: &mut Box<InterfaceAccount<'info, TokenAccount>>,
     to: &mut Box<InterfaceAccount<'info, TokenAccount>>,
     authority: &AccountInfo<'info>,
-    mint: &Box<InterfaceAccount<'info, Mint>>,
+    mint: &InterfaceAccount<'info, Mint>,
     amount: u64,
     signer_seeds: Option<&[&[&[u8]]]>,
+    remaining_accounts: &[AccountInfo<'info>],
 ) -> Result<()> {
     if token_program.key() == spl_token_2022::ID {
-        let ctx = CpiContext::new(
-            token_program.to_account_info(),
-            TransferChecked {
-                from: from.to_account_info(),
-                to: to.to_account_info(),
-                authority: authority.to_account_info(),
-                mint: mint.to_account_info(),
-            },
-        );
-        if let Some(seeds) = signer_seeds {
-            transfer_checked(ctx.with_signer(seeds), amount, mint.decimals)?;
-        } else {
-            transfer_checked(ctx, amount, mint.decimals)?;
-        }
+        invoke_transfer_checked(
+            &token_program.key(),
+            from.to_account_info(),
+            mint.to_account_info(),
+            to.to_account_info(),
+            authority.to_account_info(),
+            remaining_accounts,
+            amount,
+            mint.decimals,
+            signer_seeds.unwrap_or_default(),
+        )?;
     } else {
         // Allow deprecated transfer for classic SPL Tokens.
         let ctx = CpiContext::new(
@@ -48,3 +61,75 @@ pub fn transfer_spl_compatible<'info>(
     }
     Ok(())
 }
+
+/// Rejects Token-2022 mints whose extensions let tokens be frozen in, pulled
+/// out of, or never leave an escrow.
+pub fn require_supported_mint(mint: &InterfaceAccount<Mint>) -> Result<()> {
+    let mint_info = mint.to_account_info();
+    if *mint_info.owner != spl_token_2022::ID {
+        return Ok(());
+    }
+    let data = mint_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<Token2022Mint>::unpack(&data)?;
+    for extension_type in mint.get_extension_types()? {
+        let is_supported = match extension_type {
+            ExtensionType::NonTransferable | ExtensionType::PermanentDelegate => false,
+            ExtensionType::DefaultAccountState => {
+                mint.get_extension::<DefaultAccountState>()?.state != AccountState::Frozen as u8
+            }
+            _ => true,
+        };
+        require!(
+            is_supported,
+            RockPaperScissorsError::UnsupportedMintExtension
+        );
+    }
+    Ok(())
+}
+
+/// Closes an emptied escrow, first harvesting any transfer fees withheld in
+/// it to the mint since Token-2022 won't close an account holding them.
+pub fn close_escrow_spl_compatible<'info>(
+    token_program: &Interface<'info, TokenInterface>,
+    escrow_token_account: &InterfaceAccount<'info, TokenAccount>,
+    mint: &InterfaceAccount<'info, Mint>,
+    destination: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    if token_program.key() == spl_token_2022::ID && has_withheld_fees(escrow_token_account)? {
+        invoke(
+            &harvest_withheld_tokens_to_mint(
+                &token_program.key(),
+                &mint.key(),
+                &[&escrow_token_account.key()],
+            )?,
+            &[
+                mint.to_account_info(),
+                escrow_token_account.to_account_info(),
+                token_program.to_account_info(),
+            ],
+        )?;
+    }
+    close_account(
+        CpiContext::new(
+            token_program.to_account_info(),
+            CloseAccount {
+                account: escrow_token_account.to_account_info(),
+                destination: destination.to_account_info(),
+                authority: authority.to_account_info(),
+            },
+        )
+        .with_signer(signer_seeds),
+    )
+}
+
+fn has_withheld_fees(token_account: &InterfaceAccount<TokenAccount>) -> Result<bool> {
+    let token_account_info = token_account.to_account_info();
+    let data = token_account_info.try_borrow_data()?;
+    let token_account = StateWithExtensions::<Token2022Account>::unpack(&data)?;
+    Ok(match token_account.get_extension::<TransferFeeAmount>() {
+        Ok(transfer_fee_amount) => u64::from(transfer_fee_amount.withheld_amount) > 0,
+        Err(_) => false,
+    })
+}
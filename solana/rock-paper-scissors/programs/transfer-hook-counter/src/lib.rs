@@ -0,0 +1,130 @@
+//! Minimal transfer hook for tests: every transfer of a hooked mint bumps a
+//! per-mint counter, which the hook lists as an extra account so callers have
+//! to forward it.
+use anchor_lang::{prelude::*, solana_program::program_error::ProgramError};
+use anchor_spl::token_interface::Mint;
+use spl_tlv_account_resolution::{
+    account::ExtraAccountMeta, seeds::Seed, state::ExtraAccountMetaList,
+};
+use spl_transfer_hook_interface::instruction::{ExecuteInstruction, TransferHookInstruction};
+
+declare_id!("AwchEaHs6pacBxGCkapTzMfnyS3KiDA7rY7SYtAc6txB");
+
+#[constant]
+pub const COUNTER: [u8; 7] = *b"counter";
+#[constant]
+pub const EXTRA_ACCOUNT_METAS: [u8; 19] = *b"extra-account-metas";
+
+#[program]
+pub mod transfer_hook_counter {
+    use super::*;
+
+    pub fn initialize_extra_account_meta_list(
+        ctx: Context<InitializeExtraAccountMetaList>,
+    ) -> Result<()> {
+        let mut data = ctx.accounts.extra_account_meta_list.try_borrow_mut_data()?;
+        ExtraAccountMetaList::init::<ExecuteInstruction>(&mut data, &extra_account_metas()?)?;
+        ctx.accounts.counter.set_inner(Counter {
+            bump: ctx.bumps.counter,
+            transfers: 0,
+        });
+        Ok(())
+    }
+
+    pub fn transfer_hook(ctx: Context<TransferHook>, _amount: u64) -> Result<()> {
+        let counter = &mut ctx.accounts.counter;
+        counter.transfers = counter.transfers.saturating_add(1);
+        Ok(())
+    }
+
+    /// Token-2022 calls the hook with the interface's `Execute` discriminator
+    /// rather than Anchor's, so route it to `transfer_hook` by hand.
+    pub fn fallback<'info>(
+        program_id: &Pubkey,
+        accounts: &'info [AccountInfo<'info>],
+        data: &[u8],
+    ) -> Result<()> {
+        match TransferHookInstruction::unpack(data)? {
+            TransferHookInstruction::Execute { amount } => {
+                __private::__global::transfer_hook(program_id, accounts, &amount.to_le_bytes())
+            }
+            _ => Err(ProgramError::InvalidInstructionData.into()),
+        }
+    }
+}
+
+/// The counter, derived from the mint at index 1 of `Execute`.
+fn extra_account_metas() -> Result<Vec<ExtraAccountMeta>> {
+    Ok(vec![ExtraAccountMeta::new_with_seeds(
+        &[
+            Seed::Literal {
+                bytes: COUNTER.to_vec(),
+            },
+            Seed::AccountKey { index: 1 },
+        ],
+        false,
+        true,
+    )?])
+}
+
+#[account]
+pub struct Counter {
+    pub bump: u8,
+    pub transfers: u64,
+}
+
+impl Counter {
+    pub fn size() -> usize {
+        8 + // Discriminator
+        1 + // Bump
+        8 // Transfers
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializeExtraAccountMetaList<'info> {
+    /// CHECK: Written as a TLV `ExtraAccountMetaList`, not an Anchor account.
+    #[account(
+        init,
+        payer = payer,
+        space = ExtraAccountMetaList::size_of(1)?,
+        seeds = [EXTRA_ACCOUNT_METAS.as_ref(), mint.key().as_ref()],
+        bump,
+    )]
+    pub extra_account_meta_list: AccountInfo<'info>,
+    #[account(
+        init,
+        payer = payer,
+        space = Counter::size(),
+        seeds = [COUNTER.as_ref(), mint.key().as_ref()],
+        bump,
+    )]
+    pub counter: Account<'info, Counter>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct TransferHook<'info> {
+    /// CHECK: Validated by Token-2022 before calling the hook.
+    pub source: AccountInfo<'info>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    /// CHECK: Validated by Token-2022 before calling the hook.
+    pub destination: AccountInfo<'info>,
+    /// CHECK: Validated by Token-2022 before calling the hook.
+    pub owner: AccountInfo<'info>,
+    /// CHECK: Checked against its seeds.
+    #[account(
+        seeds = [EXTRA_ACCOUNT_METAS.as_ref(), mint.key().as_ref()],
+        bump,
+    )]
+    pub extra_account_meta_list: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [COUNTER.as_ref(), mint.key().as_ref()],
+        bump = counter.bump,
+    )]
+    pub counter: Account<'info, Counter>,
+}
@@ -2,13 +2,15 @@ export const Choice = Object.freeze({
   Rock: 0,
   Paper: 1,
   Scissors: 2,
+  Lizard: 3,
+  Spock: 4,
 } as const);
 
 export type Choice = (typeof Choice)[keyof typeof Choice];
 
 export const choiceToString = (
   choice: Choice
-): "rock" | "paper" | "scissors" => {
+): "rock" | "paper" | "scissors" | "lizard" | "spock" => {
   switch (choice) {
     case Choice.Rock:
       return "rock";
@@ -16,5 +18,9 @@ export const choiceToString = (
       return "paper";
     case Choice.Scissors:
       return "scissors";
+    case Choice.Lizard:
+      return "lizard";
+    case Choice.Spock:
+      return "spock";
   }
 };
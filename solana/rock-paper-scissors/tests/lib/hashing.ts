@@ -1,6 +1,13 @@
+import { web3 } from "@coral-xyz/anchor";
 import bs58 from "bs58";
 import { getRandomValues, subtle } from "crypto";
 import { Choice } from "./choice";
+import { b } from "./encoding";
+
+/**
+ * Domain tag mixed into every bound commitment, mirrors `COMMITMENT_DOMAIN`.
+ */
+export const COMMITMENT_DOMAIN = b`rps_commitment_v1`;
 
 /**
  * Creates a 32 byte salt
@@ -20,7 +27,8 @@ export class GetHashError extends Error {
 }
 
 /**
- * Returns a 32 byte hash of the choice and salt
+ * Returns a 32 byte hash of the choice and salt, only valid for games
+ * created under the legacy commitment scheme.
  *
  * @param {Choice} choice - 0: Rock, 1: Paper, 2: Scissors
  * @param {Uint8Array} salt - 32 byte salt
@@ -34,3 +42,29 @@ export const getHashedSaltAndChoice = async (
   if (totalBytes.length !== 33) throw GetHashError.invalidLength();
   return new Uint8Array(await subtle.digest("SHA-256", totalBytes));
 };
+
+/**
+ * Returns the 32 byte commitment for a choice, bound to the game and player
+ *
+ * @param {web3.PublicKey} game - Game PDA
+ * @param {web3.PublicKey} player - Player committing the choice
+ * @param {Choice} choice - 0: Rock, 1: Paper, 2: Scissors, 3: Lizard, 4: Spock
+ * @param {Uint8Array} salt - 32 byte salt
+ * @returns {Promise<Uint8Array>} - 32 byte hash
+ */
+export const getCommitment = async (
+  game: web3.PublicKey,
+  player: web3.PublicKey,
+  choice: Choice,
+  salt: Uint8Array
+): Promise<Uint8Array> => {
+  if (salt.length !== 32) throw GetHashError.invalidLength();
+  const totalBytes = new Uint8Array([
+    ...COMMITMENT_DOMAIN,
+    ...game.toBuffer(),
+    ...player.toBuffer(),
+    choice,
+    ...salt,
+  ]);
+  return new Uint8Array(await subtle.digest("SHA-256", totalBytes));
+};
@@ -1,10 +1,32 @@
-import { Program, web3 } from "@coral-xyz/anchor";
+import { BN, Program, web3 } from "@coral-xyz/anchor";
 import { RockPaperScissors } from "../../target/types/rock_paper_scissors";
 import { b, encode } from "./encoding";
 
 export const buildSettingsPda = (program: Program<RockPaperScissors>) =>
   web3.PublicKey.findProgramAddressSync([b`settings`], program.programId);
 
+export const buildProgramDataPda = (programId: web3.PublicKey) =>
+  web3.PublicKey.findProgramAddressSync(
+    [programId.toBuffer()],
+    new web3.PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+  );
+
+export const buildEventAuthorityPda = (program: Program<RockPaperScissors>) =>
+  web3.PublicKey.findProgramAddressSync(
+    [b`__event_authority`],
+    program.programId
+  );
+
+export const buildLobbyPda = (
+  program: Program<RockPaperScissors>,
+  mint: web3.PublicKey,
+  amount: BN
+) =>
+  web3.PublicKey.findProgramAddressSync(
+    [b`lobby`, mint.toBuffer(), amount.toArrayLike(Buffer, "le", 8)],
+    program.programId
+  );
+
 export const buildGamePda = (
   program: Program<RockPaperScissors>,
   player: web3.PublicKey,
@@ -24,3 +46,112 @@ export const buildEscrowPda = (
     [b`game_escrow`, game.toBuffer(), player.toBuffer()],
     program.programId
   );
+
+/**
+ * `web3.PublicKey.default` for native SOL wagers
+ */
+export const buildMintConfigPda = (
+  program: Program<RockPaperScissors>,
+  mint: web3.PublicKey
+) =>
+  web3.PublicKey.findProgramAddressSync(
+    [b`mint_config`, buildSettingsPda(program)[0].toBuffer(), mint.toBuffer()],
+    program.programId
+  );
+
+export const buildTournamentPda = (
+  program: Program<RockPaperScissors>,
+  organizer: web3.PublicKey,
+  tournamentId: string
+) =>
+  web3.PublicKey.findProgramAddressSync(
+    [b`tournament`, organizer.toBuffer(), encode(tournamentId)],
+    program.programId
+  );
+
+/**
+ * Mirrors `Tournament::game_id`, unique per tournament, round and match
+ */
+export const buildTournamentGameId = (
+  tournament: web3.PublicKey,
+  round: number,
+  matchIndex: number
+) =>
+  `${tournament
+    .toBuffer()
+    .subarray(0, 8)
+    .toString("hex")}-${round}-${matchIndex}`;
+
+export const buildPlayerProfilePda = (
+  program: Program<RockPaperScissors>,
+  player: web3.PublicKey
+) =>
+  web3.PublicKey.findProgramAddressSync(
+    [b`player_profile`, player.toBuffer()],
+    program.programId
+  );
+
+export const buildPlayerRatingPda = (
+  program: Program<RockPaperScissors>,
+  player: web3.PublicKey
+) =>
+  web3.PublicKey.findProgramAddressSync(
+    [b`player_rating`, player.toBuffer()],
+    program.programId
+  );
+
+export const buildReferralPda = (
+  program: Program<RockPaperScissors>,
+  player: web3.PublicKey
+) =>
+  web3.PublicKey.findProgramAddressSync(
+    [b`referral`, player.toBuffer()],
+    program.programId
+  );
+
+export const buildReferrerRewardsPda = (
+  program: Program<RockPaperScissors>,
+  referrer: web3.PublicKey
+) =>
+  web3.PublicKey.findProgramAddressSync(
+    [b`referrer_rewards`, referrer.toBuffer()],
+    program.programId
+  );
+
+export const buildSidePoolPda = (
+  program: Program<RockPaperScissors>,
+  game: web3.PublicKey
+) =>
+  web3.PublicKey.findProgramAddressSync(
+    [b`side_pool`, game.toBuffer()],
+    program.programId
+  );
+
+export const buildSideBetPda = (
+  program: Program<RockPaperScissors>,
+  sidePool: web3.PublicKey,
+  bettor: web3.PublicKey
+) =>
+  web3.PublicKey.findProgramAddressSync(
+    [b`side_bet`, sidePool.toBuffer(), bettor.toBuffer()],
+    program.programId
+  );
+
+export const buildSeasonPda = (
+  program: Program<RockPaperScissors>,
+  seasonId: number
+) =>
+  web3.PublicKey.findProgramAddressSync(
+    [b`season`, new BN(seasonId).toArrayLike(Buffer, "le", 4)],
+    program.programId
+  );
+
+export const buildSeasonEntryPda = (
+  program: Program<RockPaperScissors>,
+  season: web3.PublicKey,
+  player: web3.PublicKey
+) =>
+  web3.PublicKey.findProgramAddressSync(
+    [b`season_entry`, season.toBuffer(), player.toBuffer()],
+    program.programId
+  );
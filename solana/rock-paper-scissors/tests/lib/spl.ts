@@ -0,0 +1,12 @@
+import { web3 } from "@coral-xyz/anchor";
+import { AccountLayout } from "@solana/spl-token";
+import { BanksClient } from "solana-bankrun";
+
+// Amount held by a token account, read straight from the bank
+export const getTokenAmount = async (
+  banksClient: BanksClient,
+  tokenAccount: web3.PublicKey
+) => {
+  const account = await banksClient.getAccount(tokenAccount);
+  return AccountLayout.decode(account.data).amount;
+};
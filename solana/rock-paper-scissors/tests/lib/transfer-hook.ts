@@ -0,0 +1,83 @@
+import { web3 } from "@coral-xyz/anchor";
+import {
+  ExtensionType,
+  TOKEN_2022_PROGRAM_ID,
+  createInitializeMint2Instruction,
+  createInitializeTransferHookInstruction,
+  getMintLen,
+} from "@solana/spl-token";
+import { Rent } from "solana-bankrun";
+import { b } from "./encoding";
+
+export const buildExtraAccountMetaListPda = (
+  hookProgramId: web3.PublicKey,
+  mint: web3.PublicKey
+) =>
+  web3.PublicKey.findProgramAddressSync(
+    [b`extra-account-metas`, mint.toBuffer()],
+    hookProgramId
+  );
+
+export const buildHookCounterPda = (
+  hookProgramId: web3.PublicKey,
+  mint: web3.PublicKey
+) =>
+  web3.PublicKey.findProgramAddressSync(
+    [b`counter`, mint.toBuffer()],
+    hookProgramId
+  );
+
+/**
+ * Accounts the program forwards to Token-2022 so it can call the hook
+ */
+export const buildTransferHookAccounts = (
+  hookProgramId: web3.PublicKey,
+  mint: web3.PublicKey
+): web3.AccountMeta[] => [
+  {
+    pubkey: buildExtraAccountMetaListPda(hookProgramId, mint)[0],
+    isSigner: false,
+    isWritable: false,
+  },
+  {
+    pubkey: buildHookCounterPda(hookProgramId, mint)[0],
+    isSigner: false,
+    isWritable: true,
+  },
+  { pubkey: hookProgramId, isSigner: false, isWritable: false },
+];
+
+/**
+ * Creates a Token-2022 mint whose transfers call `hookProgramId`
+ */
+export const buildCreateHookedMintIxs = (
+  rent: Rent,
+  payer: web3.PublicKey,
+  mint: web3.PublicKey,
+  decimals: number,
+  hookProgramId: web3.PublicKey
+) => {
+  const space = getMintLen([ExtensionType.TransferHook]);
+  return [
+    web3.SystemProgram.createAccount({
+      fromPubkey: payer,
+      newAccountPubkey: mint,
+      space,
+      lamports: Number(rent.minimumBalance(BigInt(space))),
+      programId: TOKEN_2022_PROGRAM_ID,
+    }),
+    createInitializeTransferHookInstruction(
+      mint,
+      payer,
+      hookProgramId,
+      TOKEN_2022_PROGRAM_ID
+    ),
+    createInitializeMint2Instruction(
+      mint,
+      decimals,
+      payer,
+      null,
+      TOKEN_2022_PROGRAM_ID
+    ),
+  ];
+};
@@ -5,17 +5,49 @@ import { BankrunProvider } from "anchor-bankrun";
 import toml from "toml";
 import fs from "fs/promises";
 import { IDL, RockPaperScissors } from "../target/types/rock_paper_scissors";
+import {
+  IDL as TRANSFER_HOOK_COUNTER_IDL,
+  TransferHookCounter,
+} from "../target/types/transfer_hook_counter";
 
-import { buildEscrowPda, buildGamePda, buildSettingsPda } from "./lib/pda";
+import {
+  buildEscrowPda,
+  buildEventAuthorityPda,
+  buildGamePda,
+  buildLobbyPda,
+  buildMintConfigPda,
+  buildPlayerProfilePda,
+  buildPlayerRatingPda,
+  buildProgramDataPda,
+  buildReferralPda,
+  buildReferrerRewardsPda,
+  buildSettingsPda,
+  buildSideBetPda,
+  buildSidePoolPda,
+  buildTournamentGameId,
+  buildSeasonEntryPda,
+  buildSeasonPda,
+  buildTournamentPda,
+} from "./lib/pda";
 import { readWalletFromFile, sendSignedVersionedTx } from "./lib/solana";
+import { getTokenAmount } from "./lib/spl";
 import {
   ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
   TOKEN_PROGRAM_ID,
+  createAssociatedTokenAccountIdempotentInstruction,
+  createMintToInstruction,
   getAssociatedTokenAddressSync,
 } from "@solana/spl-token";
 import { BN } from "bn.js";
 import { Choice, choiceToString } from "./lib/choice";
-import { getHashedSaltAndChoice, getSalt } from "./lib/hashing";
+import { getCommitment, getSalt } from "./lib/hashing";
+import {
+  buildCreateHookedMintIxs,
+  buildExtraAccountMetaListPda,
+  buildHookCounterPda,
+  buildTransferHookAccounts,
+} from "./lib/transfer-hook";
 import { buildAccounts as buildTestAccounts } from "./test-accounts";
 import { expect } from "chai";
 
@@ -30,6 +62,15 @@ const TIME_FOR_PENALIZATION = new anchor.BN(60 * 60 * 24 * 7);
 const TIME_FOR_STALE = new anchor.BN(60 * 60 * 24 * 7);
 const PREV_FEE_LAMPORTS = new anchor.BN(0.02 * anchor.web3.LAMPORTS_PER_SOL);
 const FEE_LAMPORTS = new anchor.BN(0.025 * anchor.web3.LAMPORTS_PER_SOL);
+const RAKE_BPS = 250;
+const RAKE_DRAWS = false;
+const GAME_RETENTION_PERIOD = new anchor.BN(60 * 60 * 24);
+const USDC_MIN_AMOUNT = new anchor.BN(1_000000);
+const USDC_MAX_AMOUNT = new anchor.BN(1_000_000_000000);
+const NATIVE_MIN_AMOUNT = new anchor.BN(0.01 * anchor.web3.LAMPORTS_PER_SOL);
+const NATIVE_MAX_AMOUNT = new anchor.BN(100 * anchor.web3.LAMPORTS_PER_SOL);
+const PAUSE_NEW_GAMES = 1 << 0;
+const REFERRAL_SHARE_BPS = 2_500;
 
 const readTomlFile = async (path: string) =>
   toml.parse(await fs.readFile(path, "utf-8"));
@@ -44,8 +85,16 @@ const FIRST_GAME = {
   secondPlayerChoice: Choice.Paper,
   secondPlayerSalt: getSalt(),
   amountToMatch: new BN(10_000000000),
+  bestOf: 1,
+  variant: { classic: {} },
 };
 
+/**
+ * [First Game - Side Pool]
+ * The admin bets on the first game from the sidelines.
+ */
+const SIDE_BET_AMOUNT = new BN(0.5 * anchor.web3.LAMPORTS_PER_SOL);
+
 /**
  * [Second Game - Forfeit Path]
  */
@@ -56,17 +105,188 @@ const SECOND_GAME = {
   secondPlayerChoice: Choice.Scissors,
   secondPlayerSalt: getSalt(),
   amountToMatch: new BN(10_000000000),
+  bestOf: 1,
+  variant: { classic: {} },
+};
+
+/**
+ * [Third Game - Best of Three Path]
+ * First round ties and is replayed, the first player takes the next two.
+ */
+const THIRD_GAME = {
+  gameId: "game3",
+  rounds: [
+    { firstPlayerChoice: Choice.Rock, secondPlayerChoice: Choice.Rock },
+    { firstPlayerChoice: Choice.Paper, secondPlayerChoice: Choice.Rock },
+    { firstPlayerChoice: Choice.Scissors, secondPlayerChoice: Choice.Paper },
+  ].map((round) => ({
+    ...round,
+    firstPlayerSalt: getSalt(),
+    secondPlayerSalt: getSalt(),
+  })),
+  amountToMatch: new BN(10_000000000),
+  bestOf: 3,
+  variant: { classic: {} },
+};
+
+/**
+ * [Fourth Game - Expiry Path]
+ * Nobody joins before the deadline, so anyone can expire it.
+ */
+const FOURTH_GAME = {
+  gameId: "game4",
+  firstPlayerChoice: Choice.Rock,
+  firstPlayerSalt: getSalt(),
+  amountToMatch: new BN(10_000000000),
+  bestOf: 1,
+  variant: { classic: {} },
+  joinWindow: 60 * 60,
+};
+
+/**
+ * [Fifth Game - Native SOL Path]
+ * Stakes lamports, held by the game account instead of token escrows.
+ */
+const FIFTH_GAME = {
+  gameId: "game5",
+  firstPlayerChoice: Choice.Scissors,
+  firstPlayerSalt: getSalt(),
+  secondPlayerChoice: Choice.Paper,
+  secondPlayerSalt: getSalt(),
+  amountToMatch: new BN(0.1 * anchor.web3.LAMPORTS_PER_SOL),
+  bestOf: 1,
+  variant: { classic: {} },
+};
+
+/**
+ * [Sixth Game - Transfer Hook Path]
+ * Wagers a Token-2022 mint whose transfers call a local hook program.
+ */
+const SIXTH_GAME = {
+  gameId: "game6",
+  firstPlayerChoice: Choice.Paper,
+  firstPlayerSalt: getSalt(),
+  secondPlayerChoice: Choice.Rock,
+  secondPlayerSalt: getSalt(),
+  amountToMatch: new BN(100_000000),
+  bestOf: 1,
+  variant: { classic: {} },
+  mint: anchor.web3.Keypair.generate(),
+  decimals: 6,
+};
+
+/**
+ * [Tournament]
+ * Both players enter a two player bracket, the first player wins the final.
+ */
+const TOURNAMENT = {
+  tournamentId: "cup1",
+  entryFee: new BN(0.05 * anchor.web3.LAMPORTS_PER_SOL),
+  maxPlayers: 2,
+  bestOf: 1,
+  variant: { classic: {} },
+  prizeSplitBps: [10_000],
+  firstPlayerChoice: Choice.Rock,
+  firstPlayerSalt: getSalt(),
+  secondPlayerChoice: Choice.Scissors,
+  secondPlayerSalt: getSalt(),
+};
+
+/**
+ * [Tournament - Replay Path]
+ * The final is drawn and only settled after the round deadline, the replay
+ * still gets a full window before anyone can claim a walkover.
+ */
+const REPLAYED_TOURNAMENT = {
+  tournamentId: "cup2",
+  choice: Choice.Rock,
+  firstPlayerSalt: getSalt(),
+  secondPlayerSalt: getSalt(),
+};
+
+/**
+ * [Season]
+ * Both players join a season ranked by points.
+ */
+const SEASON = {
+  seasonId: 1,
+  leaderboardSize: 3,
+  length: 7 * 24 * 60 * 60,
+  ranking: { points: {} },
+  feeShareBps: 2_000,
+  prizeSplitBps: [6_000, 3_000, 1_000],
+  // `SEASON_RANKING_PERIOD` and `SEASON_CLAIM_PERIOD`
+  rankingPeriod: 24 * 60 * 60,
+  claimPeriod: 30 * 24 * 60 * 60,
+};
+
+/**
+ * [Season Game]
+ * The first player wins a native game counting towards the season, then
+ * claims first place's prize. Nobody else places, so the rest is swept.
+ */
+const SEASON_GAME = {
+  gameId: "season1",
+  firstPlayerChoice: Choice.Paper,
+  firstPlayerSalt: getSalt(),
+  secondPlayerChoice: Choice.Rock,
+  secondPlayerSalt: getSalt(),
+  amountToMatch: new BN(0.1 * anchor.web3.LAMPORTS_PER_SOL),
+  bestOf: 1,
+  variant: { classic: {} },
+};
+
+/**
+ * [Referral Game]
+ * The referrer beats the player they referred in USDC, the referrer's
+ * rewards get a share of the referred player's fee and rake.
+ */
+const REFERRAL_GAME = {
+  gameId: "referral1",
+  firstPlayerChoice: Choice.Rock,
+  firstPlayerSalt: getSalt(),
+  secondPlayerChoice: Choice.Scissors,
+  secondPlayerSalt: getSalt(),
+  amountToMatch: new BN(100_000000),
+  bestOf: 1,
+  variant: { classic: {} },
+};
+
+/**
+ * [Referral Native Game]
+ * Same as the referral game in native SOL, shares are held by the rewards
+ * account itself.
+ */
+const REFERRAL_NATIVE_GAME = {
+  gameId: "referral2",
+  firstPlayerChoice: Choice.Rock,
+  firstPlayerSalt: getSalt(),
+  secondPlayerChoice: Choice.Scissors,
+  secondPlayerSalt: getSalt(),
+  amountToMatch: new BN(0.1 * anchor.web3.LAMPORTS_PER_SOL),
+  bestOf: 1,
+  variant: { classic: {} },
 };
 
 // Anchor + Bankrun Tooling
 let context: ProgramTestContext;
 let provider: BankrunProvider;
 let program: anchor.Program<RockPaperScissors>;
+let transferHookProgram: anchor.Program<TransferHookCounter>;
+
+/**
+ * Signer of the self-CPI events are emitted through
+ */
+let eventAuthority: anchor.web3.PublicKey;
 
 /**
  * Treasury authority
  */
 let authority: anchor.web3.Keypair;
+/**
+ * Treasury's USDC token account
+ */
+let treasuryAta: anchor.web3.PublicKey;
 /**
  * Global settings
  */
@@ -115,11 +335,107 @@ let secondGameFirstPlayerEscrowAta: anchor.web3.PublicKey;
  */
 let secondGameSecondPlayerEscrowAta: anchor.web3.PublicKey;
 
+/**
+ * Third game
+ */
+let thirdGamePda: anchor.web3.PublicKey;
+/**
+ * Third game - First player's escrow
+ */
+let thirdGameFirstPlayerEscrowAta: anchor.web3.PublicKey;
+/**
+ * Third game - Second player's escrow
+ */
+let thirdGameSecondPlayerEscrowAta: anchor.web3.PublicKey;
+
+/**
+ * Fourth game
+ */
+let fourthGamePda: anchor.web3.PublicKey;
+/**
+ * Fourth game - First player's escrow
+ */
+let fourthGameFirstPlayerEscrowAta: anchor.web3.PublicKey;
+
+/**
+ * Fifth game
+ */
+let fifthGamePda: anchor.web3.PublicKey;
+
+/**
+ * Sixth game
+ */
+let sixthGamePda: anchor.web3.PublicKey;
+/**
+ * Sixth game - Players' and treasury's hooked token accounts
+ */
+let sixthGameFirstPlayerAta: anchor.web3.PublicKey;
+let sixthGameSecondPlayerAta: anchor.web3.PublicKey;
+let sixthGameTreasuryAta: anchor.web3.PublicKey;
+/**
+ * Sixth game - Accounts forwarded to the transfer hook
+ */
+let sixthGameTransferHookAccounts: anchor.web3.AccountMeta[];
+
+/**
+ * Tournament
+ */
+let tournamentPda: anchor.web3.PublicKey;
+/**
+ * Tournament - The final's game
+ */
+let tournamentGamePda: anchor.web3.PublicKey;
+
+/**
+ * Season game
+ */
+let seasonGamePda: anchor.web3.PublicKey;
+
+/**
+ * Referral game
+ */
+let referralGamePda: anchor.web3.PublicKey;
+/**
+ * Referral game - First player's escrow
+ */
+let referralGameFirstPlayerEscrowAta: anchor.web3.PublicKey;
+/**
+ * Referral game - Second player's escrow
+ */
+let referralGameSecondPlayerEscrowAta: anchor.web3.PublicKey;
+/**
+ * Referral game - USDC account of the first player's referrer rewards
+ */
+let referrerVault: anchor.web3.PublicKey;
+
+/**
+ * Referral native game
+ */
+let referralNativeGamePda: anchor.web3.PublicKey;
+
+/**
+ * Moves the clock to `unixTimestamp`
+ */
+const setClockTimestamp = async (unixTimestamp: bigint) => {
+  const currentClock = await context.banksClient.getClock();
+  context.setClock(
+    new Clock(
+      currentClock.slot,
+      currentClock.epochStartTimestamp,
+      currentClock.epoch,
+      currentClock.leaderScheduleEpoch,
+      unixTimestamp
+    )
+  );
+};
+
 describe("Rock Paper Scissors - Test Suite", () => {
   before(async () => {
     const mainnetConnection = new anchor.web3.Connection(MAINNET_RPC);
     const anchorToml = (await readTomlFile("Anchor.toml")) as {
-      programs: { localnet: { rock_paper_scissors: string } };
+      programs: {
+        localnet: { rock_paper_scissors: string; transfer_hook_counter: string };
+      };
       provider: { wallet: string };
     };
     authority = await readWalletFromFile(anchorToml.provider.wallet);
@@ -137,6 +453,13 @@ describe("Rock Paper Scissors - Test Suite", () => {
       TOKEN_PROGRAM_ID,
       ASSOCIATED_TOKEN_PROGRAM_ID
     );
+    treasuryAta = getAssociatedTokenAddressSync(
+      USDC_MINT,
+      authority.publicKey,
+      false,
+      TOKEN_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID
+    );
     context = await startAnchor(
       "./",
       [],
@@ -146,6 +469,10 @@ describe("Rock Paper Scissors - Test Suite", () => {
         firstPlayerAta,
         secondPlayer.publicKey,
         secondPlayerAta,
+        treasuryAta,
+        buildProgramDataPda(
+          new anchor.web3.PublicKey(anchorToml.programs.localnet.rock_paper_scissors)
+        )[0],
         mainnetConnection
       )
     );
@@ -155,6 +482,12 @@ describe("Rock Paper Scissors - Test Suite", () => {
       anchorToml.programs.localnet.rock_paper_scissors,
       provider
     );
+    [eventAuthority] = buildEventAuthorityPda(program);
+    transferHookProgram = new anchor.Program<TransferHookCounter>(
+      TRANSFER_HOOK_COUNTER_IDL,
+      anchorToml.programs.localnet.transfer_hook_counter,
+      provider
+    );
   });
 
   it("[Settings] Initializes settings", async () => {
@@ -164,11 +497,18 @@ describe("Rock Paper Scissors - Test Suite", () => {
       .initializeSettings(
         TIME_FOR_PENALIZATION,
         TIME_FOR_STALE,
-        PREV_FEE_LAMPORTS
+        PREV_FEE_LAMPORTS,
+        RAKE_BPS,
+        RAKE_DRAWS,
+        GAME_RETENTION_PERIOD,
+        authority.publicKey
       )
       .accountsStrict({
+        eventAuthority,
+        program: program.programId,
         settings: settings,
         signer: authority.publicKey,
+        programData: buildProgramDataPda(program.programId)[0],
         systemProgram: anchor.web3.SystemProgram.programId,
       })
       .instruction();
@@ -187,8 +527,18 @@ describe("Rock Paper Scissors - Test Suite", () => {
 
   it("[Settings] Updates settings", async () => {
     const ix = await program.methods
-      .updateSettings(TIME_FOR_PENALIZATION, TIME_FOR_STALE, FEE_LAMPORTS)
+      .updateSettings(
+        TIME_FOR_PENALIZATION,
+        TIME_FOR_STALE,
+        FEE_LAMPORTS,
+        RAKE_BPS,
+        RAKE_DRAWS,
+        GAME_RETENTION_PERIOD,
+        authority.publicKey
+      )
       .accountsStrict({
+        eventAuthority,
+        program: program.programId,
         settings: settingsPda,
         signer: authority.publicKey,
       })
@@ -204,96 +554,165 @@ describe("Rock Paper Scissors - Test Suite", () => {
     console.log("txId:", txId);
   });
 
-  it("[First Game - Happy Path] First player: Initializes first game", async () => {
-    const [game] = buildGamePda(
-      program,
-      firstPlayer.publicKey,
-      FIRST_GAME.gameId
-    );
-    const [escrow] = buildEscrowPda(program, game, firstPlayer.publicKey);
-
-    const hash = await getHashedSaltAndChoice(
-      FIRST_GAME.firstPlayerChoice,
-      FIRST_GAME.firstPlayerSalt
-    );
-
+  it("[Settings] Bounds the windows creators may pick", async () => {
     const ix = await program.methods
-      .initializeGame(FIRST_GAME.gameId, FIRST_GAME.amountToMatch, [...hash])
+      .updateWindowBounds(
+        TIME_FOR_PENALIZATION.divn(7),
+        TIME_FOR_PENALIZATION.muln(2),
+        TIME_FOR_STALE.divn(7),
+        TIME_FOR_STALE.muln(2)
+      )
       .accountsStrict({
-        game,
-        mint: USDC_MINT,
-        player: firstPlayer.publicKey,
-        playerTokenAccount: firstPlayerAta,
+        eventAuthority,
+        program: program.programId,
         settings: settingsPda,
-        playerEscrowTokenAccount: escrow,
-        treasury: authority.publicKey,
-        tokenProgram: TOKEN_PROGRAM_ID,
-        systemProgram: anchor.web3.SystemProgram.programId,
+        signer: authority.publicKey,
       })
       .instruction();
 
     const txId = await sendSignedVersionedTx(
       provider,
-      firstPlayer.publicKey,
-      [firstPlayer],
+      authority.publicKey,
+      [authority],
       ...[ix]
     );
 
-    firstGameFirstPlayerEscrowAta = escrow;
-    firstGamePda = game;
+    const settings = await program.account.settings.fetch(settingsPda);
+    expect(settings.minTimeForPenalization.toString()).to.eq(
+      TIME_FOR_PENALIZATION.divn(7).toString()
+    );
 
     console.log("txId:", txId);
   });
 
-  it("[First Game - Happy Path] Second player: Joins first game", async () => {
-    const [escrow] = buildEscrowPda(
-      program,
-      firstGamePda,
-      secondPlayer.publicKey
-    );
+  it("[Settings] Hands admin rights over and back", async () => {
+    const newAdmin = anchor.web3.Keypair.generate();
 
-    const hash = await getHashedSaltAndChoice(
-      FIRST_GAME.secondPlayerChoice,
-      FIRST_GAME.secondPlayerSalt
-    );
+    const handOver = async (
+      from: anchor.web3.Keypair,
+      to: anchor.web3.Keypair
+    ) => {
+      const proposeIx = await program.methods
+        .proposeAdmin(to.publicKey)
+        .accountsStrict({
+          eventAuthority,
+          program: program.programId,
+          settings: settingsPda,
+          signer: from.publicKey,
+        })
+        .instruction();
+      const acceptIx = await program.methods
+        .acceptAdmin()
+        .accountsStrict({
+          eventAuthority,
+          program: program.programId,
+          settings: settingsPda,
+          signer: to.publicKey,
+        })
+        .instruction();
 
-    const ix = await program.methods
-      .joinGame([...hash])
-      .accountsStrict({
-        game: firstGamePda,
-        mint: USDC_MINT,
-        player: secondPlayer.publicKey,
-        playerEscrowTokenAccount: escrow,
-        playerTokenAccount: secondPlayerAta,
-        settings: settingsPda,
-        treasury: authority.publicKey,
-        systemProgram: anchor.web3.SystemProgram.programId,
-        tokenProgram: TOKEN_PROGRAM_ID,
-      })
-      .instruction();
+      return sendSignedVersionedTx(
+        provider,
+        authority.publicKey,
+        [authority, newAdmin],
+        proposeIx,
+        acceptIx
+      );
+    };
+
+    await handOver(authority, newAdmin);
+    let settings = await program.account.settings.fetch(settingsPda);
+    expect(settings.admin.toBase58()).to.eq(newAdmin.publicKey.toBase58());
+    expect(settings.treasury.toBase58()).to.eq(authority.publicKey.toBase58());
+
+    const txId = await handOver(newAdmin, authority);
+    settings = await program.account.settings.fetch(settingsPda);
+    expect(settings.admin.toBase58()).to.eq(authority.publicKey.toBase58());
+    expect(settings.pendingAdmin).to.be.null;
+
+    console.log("txId:", txId);
+  });
+
+  it("[Settings] Lists USDC and native SOL", async () => {
+    const ixs = await Promise.all(
+      [
+        { mint: USDC_MINT, minAmount: USDC_MIN_AMOUNT, maxAmount: USDC_MAX_AMOUNT },
+        {
+          mint: anchor.web3.PublicKey.default,
+          minAmount: NATIVE_MIN_AMOUNT,
+          maxAmount: NATIVE_MAX_AMOUNT,
+        },
+      ].map(({ mint, minAmount, maxAmount }) =>
+        program.methods
+          .initializeMintConfig(mint, true, minAmount, maxAmount, null)
+          .accountsStrict({
+            eventAuthority,
+            program: program.programId,
+            mintConfig: buildMintConfigPda(program, mint)[0],
+            settings: settingsPda,
+            signer: authority.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .instruction()
+      )
+    );
 
     const txId = await sendSignedVersionedTx(
       provider,
-      secondPlayer.publicKey,
-      [secondPlayer],
-      ...[ix]
+      authority.publicKey,
+      [authority],
+      ...ixs
     );
 
-    firstGameSecondPlayerEscrowAta = escrow;
-
     console.log("txId:", txId);
   });
 
-  it("[First Game - Happy Path] First player: Can't be cancelled since another player joined", async () => {
+  it("[Settings] Can't create a game below the mint's minimum", async () => {
+    const gameId = "dust";
+    const [game] = buildGamePda(program, firstPlayer.publicKey, gameId);
+    const [escrow] = buildEscrowPda(program, game, firstPlayer.publicKey);
+
+    const hash = await getCommitment(
+      game,
+      firstPlayer.publicKey,
+      Choice.Rock,
+      getSalt()
+    );
+
     const ix = await program.methods
-      .cancelGame()
+      .initializeGame(
+        gameId,
+        USDC_MIN_AMOUNT.subn(1),
+        [...hash],
+        1,
+        { classic: {} } as any,
+        null,
+        null,
+        null,
+        null,
+        null
+      )
       .accountsStrict({
-        game: firstGamePda,
-        player: firstPlayer.publicKey,
+        season: null,
+        seasonEntry: null,
+        referral: buildReferralPda(program, firstPlayer.publicKey)[0],
+        referrerRewards: null,
+        referrerVault: null,
+        eventAuthority,
+        program: program.programId,
+        game,
         mint: USDC_MINT,
-        playerEscrowTokenAccount: firstGameFirstPlayerEscrowAta,
+        player: firstPlayer.publicKey,
+        playerProfile: buildPlayerProfilePda(program, firstPlayer.publicKey)[0],
+        playerRating: buildPlayerRatingPda(program, firstPlayer.publicKey)[0],
         playerTokenAccount: firstPlayerAta,
+        settings: settingsPda,
+        mintConfig: buildMintConfigPda(program, USDC_MINT)[0],
+        lobby: buildLobbyPda(program, USDC_MINT, USDC_MIN_AMOUNT.subn(1))[0],
+        playerEscrowTokenAccount: escrow,
+        treasury: authority.publicKey,
         tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
       })
       .instruction();
 
@@ -304,11 +723,12 @@ describe("Rock Paper Scissors - Test Suite", () => {
         [firstPlayer],
         ...[ix]
       );
+      expect.fail("dust game was created");
     } catch (error) {
       if (error instanceof anchor.web3.SendTransactionError) {
         console.log(error.message);
         expect(error.message).to.equal(
-          "Error processing Instruction 0: custom program error: 0x1771"
+          "Error processing Instruction 0: custom program error: 0x1788"
         );
       } else {
         throw error;
@@ -316,139 +736,147 @@ describe("Rock Paper Scissors - Test Suite", () => {
     }
   });
 
-  it("[First Game - Happy Path] First player: Reveals for first game", async () => {
-    const ix = await program.methods
-      .revealChoice(
-        { [choiceToString(FIRST_GAME.firstPlayerChoice)]: {} } as any,
-        [...FIRST_GAME.firstPlayerSalt]
-      )
-      .accountsStrict({
-        game: firstGamePda,
-        player: firstPlayer.publicKey,
-      })
-      .instruction();
+  it("[Settings] Can't create a game while new games are paused", async () => {
+    const setPaused = (paused: number) =>
+      program.methods
+        .setPaused(paused)
+        .accountsStrict({
+          eventAuthority,
+          program: program.programId,
+          settings: settingsPda,
+          signer: authority.publicKey,
+        })
+        .instruction();
 
-    const txId = await sendSignedVersionedTx(
+    await sendSignedVersionedTx(
       provider,
-      firstPlayer.publicKey,
-      [firstPlayer],
-      ...[ix]
+      authority.publicKey,
+      [authority],
+      await setPaused(PAUSE_NEW_GAMES)
     );
 
-    console.log("txId:", txId);
-  });
+    const gameId = "paused";
+    const [game] = buildGamePda(program, firstPlayer.publicKey, gameId);
+    const hash = await getCommitment(
+      game,
+      firstPlayer.publicKey,
+      Choice.Rock,
+      getSalt()
+    );
 
-  it("[First Game - Happy Path] Permissionless: Can't be unwinded since at least one player revealed", async () => {
     const ix = await program.methods
-      .unwindGame()
+      .initializeNativeGame(
+        gameId,
+        NATIVE_MIN_AMOUNT,
+        [...hash],
+        1,
+        { classic: {} } as any,
+        null,
+        null,
+        null,
+        null,
+        null
+      )
       .accountsStrict({
-        game: firstGamePda,
-        mint: USDC_MINT,
-        firstPlayer: firstPlayer.publicKey,
-        firstPlayerEscrowTokenAccount: firstGameFirstPlayerEscrowAta,
-        firstPlayerTokenAccount: firstPlayerAta,
-        secondPlayer: secondPlayer.publicKey,
-        secondPlayerEscrowTokenAccount: firstGameSecondPlayerEscrowAta,
-        secondPlayerTokenAccount: secondPlayerAta,
-        settings: settingsPda,
-        signer: authority.publicKey,
-        tokenProgram: TOKEN_PROGRAM_ID,
+        season: null,
+        seasonEntry: null,
+        referral: buildReferralPda(program, firstPlayer.publicKey)[0],
+        referrerRewards: null,
+        eventAuthority,
+        program: program.programId,
+        game,
+        player: firstPlayer.publicKey,
+        playerProfile: buildPlayerProfilePda(program, firstPlayer.publicKey)[0],
+        playerRating: buildPlayerRatingPda(program, firstPlayer.publicKey)[0],
+        settings: settingsPda,
+        mintConfig: buildMintConfigPda(
+          program,
+          anchor.web3.PublicKey.default
+        )[0],
+        lobby: buildLobbyPda(
+          program,
+          anchor.web3.PublicKey.default,
+          NATIVE_MIN_AMOUNT
+        )[0],
+        treasury: authority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
       })
       .instruction();
 
     try {
       await sendSignedVersionedTx(
         provider,
-        authority.publicKey,
-        [authority],
-        ...[ix]
+        firstPlayer.publicKey,
+        [firstPlayer],
+        ix
       );
+      expect.fail("game was created while paused");
     } catch (error) {
       if (error instanceof anchor.web3.SendTransactionError) {
         console.log(error.message);
         expect(error.message).to.equal(
-          "Error processing Instruction 0: custom program error: 0x1775"
+          "Error processing Instruction 0: custom program error: 0x178d"
         );
       } else {
         throw error;
       }
     }
-  });
-
-  it("[First Game - Happy Path] Second player: Reveals for first game", async () => {
-    const ix = await program.methods
-      .revealChoice(
-        { [choiceToString(FIRST_GAME.secondPlayerChoice)]: {} } as any,
-        [...FIRST_GAME.secondPlayerSalt]
-      )
-      .accountsStrict({
-        game: firstGamePda,
-        player: secondPlayer.publicKey,
-      })
-      .instruction();
-
-    const txId = await sendSignedVersionedTx(
-      provider,
-      secondPlayer.publicKey,
-      [secondPlayer],
-      ...[ix]
-    );
-
-    console.log("txId:", txId);
-  });
-
-  it("[First Game - Happy Path] Permissionless: Settles first game", async () => {
-    const ix = await program.methods
-      .settleGame()
-      .accountsStrict({
-        game: firstGamePda,
-        mint: USDC_MINT,
-        firstPlayer: firstPlayer.publicKey,
-        firstPlayerEscrowTokenAccount: firstGameFirstPlayerEscrowAta,
-        firstPlayerTokenAccount: firstPlayerAta,
-        secondPlayer: secondPlayer.publicKey,
-        secondPlayerEscrowTokenAccount: firstGameSecondPlayerEscrowAta,
-        secondPlayerTokenAccount: secondPlayerAta,
-        settings: settingsPda,
-        signer: authority.publicKey,
-        tokenProgram: TOKEN_PROGRAM_ID,
-      })
-      .instruction();
 
     const txId = await sendSignedVersionedTx(
       provider,
       authority.publicKey,
       [authority],
-      ...[ix]
+      await setPaused(0)
     );
 
     console.log("txId:", txId);
-
-    const gameAccount = await program.account.game.fetch(firstGamePda);
-    expect(Object.keys(gameAccount.state)[0]).to.equal("secondPlayerWon");
   });
 
-  it("[Second Game - Forfeit Path] First player: Initializes second game", async () => {
+  it("[First Game - Happy Path] First player: Initializes first game", async () => {
     const [game] = buildGamePda(
       program,
       firstPlayer.publicKey,
-      SECOND_GAME.gameId
+      FIRST_GAME.gameId
     );
     const [escrow] = buildEscrowPda(program, game, firstPlayer.publicKey);
 
-    const hash = await getHashedSaltAndChoice(
-      SECOND_GAME.firstPlayerChoice,
-      SECOND_GAME.firstPlayerSalt
+    const hash = await getCommitment(
+      game,
+      firstPlayer.publicKey,
+      FIRST_GAME.firstPlayerChoice,
+      FIRST_GAME.firstPlayerSalt
     );
 
     const ix = await program.methods
-      .initializeGame(SECOND_GAME.gameId, SECOND_GAME.amountToMatch, [...hash])
+      .initializeGame(
+        FIRST_GAME.gameId,
+        FIRST_GAME.amountToMatch,
+        [...hash],
+        FIRST_GAME.bestOf,
+        FIRST_GAME.variant as any,
+        null,
+        null,
+        null,
+        null,
+        null
+      )
       .accountsStrict({
+        season: null,
+        seasonEntry: null,
+        referral: buildReferralPda(program, firstPlayer.publicKey)[0],
+        referrerRewards: null,
+        referrerVault: null,
+        eventAuthority,
+        program: program.programId,
         game,
         mint: USDC_MINT,
         player: firstPlayer.publicKey,
+        playerProfile: buildPlayerProfilePda(program, firstPlayer.publicKey)[0],
+        playerRating: buildPlayerRatingPda(program, firstPlayer.publicKey)[0],
         playerTokenAccount: firstPlayerAta,
         settings: settingsPda,
+        mintConfig: buildMintConfigPda(program, USDC_MINT)[0],
+        lobby: buildLobbyPda(program, USDC_MINT, FIRST_GAME.amountToMatch)[0],
         playerEscrowTokenAccount: escrow,
         treasury: authority.publicKey,
         tokenProgram: TOKEN_PROGRAM_ID,
@@ -463,33 +891,113 @@ describe("Rock Paper Scissors - Test Suite", () => {
       ...[ix]
     );
 
-    secondGamePda = game;
-    secondGameFirstPlayerEscrowAta = escrow;
+    firstGameFirstPlayerEscrowAta = escrow;
+    firstGamePda = game;
+
+    const lobby = await program.account.lobby.fetch(
+      buildLobbyPda(program, USDC_MINT, FIRST_GAME.amountToMatch)[0]
+    );
+    expect(lobby.games.map((game) => game.toBase58())).to.include(
+      game.toBase58()
+    );
 
     console.log("txId:", txId);
   });
 
-  it("[Second Game - Forfeit Path] Second player: Joins second game", async () => {
+  it("[First Game - Happy Path] Second player: Can't join with the first player's commitment", async () => {
     const [escrow] = buildEscrowPda(
       program,
-      secondGamePda,
+      firstGamePda,
       secondPlayer.publicKey
     );
 
-    const hash = await getHashedSaltAndChoice(
-      SECOND_GAME.secondPlayerChoice,
-      SECOND_GAME.secondPlayerSalt
+    const gameAccount = await program.account.game.fetch(firstGamePda);
+
+    const ix = await program.methods
+      .joinGame(gameAccount.firstPlayerHash, [])
+      .accountsStrict({
+        season: null,
+        seasonEntry: null,
+        referral: buildReferralPda(program, secondPlayer.publicKey)[0],
+        referrerRewards: null,
+        referrerVault: null,
+        eventAuthority,
+        program: program.programId,
+        game: firstGamePda,
+        lobby: gameAccount.lobby,
+        mint: USDC_MINT,
+        player: secondPlayer.publicKey,
+        playerProfile: buildPlayerProfilePda(
+          program,
+          secondPlayer.publicKey
+        )[0],
+        playerRating: buildPlayerRatingPda(program, secondPlayer.publicKey)[0],
+        playerEscrowTokenAccount: escrow,
+        playerTokenAccount: secondPlayerAta,
+        settings: settingsPda,
+        mintConfig: buildMintConfigPda(program, USDC_MINT)[0],
+        treasury: authority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .instruction();
+
+    try {
+      await sendSignedVersionedTx(
+        provider,
+        secondPlayer.publicKey,
+        [secondPlayer],
+        ...[ix]
+      );
+    } catch (error) {
+      if (error instanceof anchor.web3.SendTransactionError) {
+        console.log(error.message);
+        expect(error.message).to.equal(
+          "Error processing Instruction 0: custom program error: 0x1778"
+        );
+      } else {
+        throw error;
+      }
+    }
+  });
+
+  it("[First Game - Happy Path] Second player: Joins first game", async () => {
+    const [escrow] = buildEscrowPda(
+      program,
+      firstGamePda,
+      secondPlayer.publicKey
+    );
+
+    const hash = await getCommitment(
+      firstGamePda,
+      secondPlayer.publicKey,
+      FIRST_GAME.secondPlayerChoice,
+      FIRST_GAME.secondPlayerSalt
     );
 
     const ix = await program.methods
-      .joinGame([...hash])
+      .joinGame([...hash], [])
       .accountsStrict({
-        game: secondGamePda,
+        season: null,
+        seasonEntry: null,
+        referral: buildReferralPda(program, secondPlayer.publicKey)[0],
+        referrerRewards: null,
+        referrerVault: null,
+        eventAuthority,
+        program: program.programId,
+        game: firstGamePda,
+        lobby: (await program.account.game.fetch(firstGamePda)).lobby,
         mint: USDC_MINT,
         player: secondPlayer.publicKey,
+        playerProfile: buildPlayerProfilePda(
+          program,
+          secondPlayer.publicKey
+        )[0],
+        playerRating: buildPlayerRatingPda(program, secondPlayer.publicKey)[0],
         playerEscrowTokenAccount: escrow,
         playerTokenAccount: secondPlayerAta,
         settings: settingsPda,
+        mintConfig: buildMintConfigPda(program, USDC_MINT)[0],
         treasury: authority.publicKey,
         systemProgram: anchor.web3.SystemProgram.programId,
         tokenProgram: TOKEN_PROGRAM_ID,
@@ -503,84 +1011,3403 @@ describe("Rock Paper Scissors - Test Suite", () => {
       ...[ix]
     );
 
-    secondGameSecondPlayerEscrowAta = escrow;
+    const lobby = await program.account.lobby.fetch(
+      buildLobbyPda(program, USDC_MINT, FIRST_GAME.amountToMatch)[0]
+    );
+    expect(lobby.games.map((game) => game.toBase58())).not.to.include(
+      firstGamePda.toBase58()
+    );
+
+    firstGameSecondPlayerEscrowAta = escrow;
 
     console.log("txId:", txId);
   });
 
-  it("[Second Game - Forfeit Path] First player: Reveals for second game", async () => {
+  it("[First Game - Happy Path] First player: Can't be cancelled since another player joined", async () => {
+    const ix = await program.methods
+      .cancelGame()
+      .accountsStrict({
+        eventAuthority,
+        program: program.programId,
+        game: firstGamePda,
+        lobby: (await program.account.game.fetch(firstGamePda)).lobby,
+        player: firstPlayer.publicKey,
+        mint: USDC_MINT,
+        playerEscrowTokenAccount: firstGameFirstPlayerEscrowAta,
+        playerTokenAccount: firstPlayerAta,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .instruction();
+
+    try {
+      await sendSignedVersionedTx(
+        provider,
+        firstPlayer.publicKey,
+        [firstPlayer],
+        ...[ix]
+      );
+    } catch (error) {
+      if (error instanceof anchor.web3.SendTransactionError) {
+        console.log(error.message);
+        expect(error.message).to.equal(
+          "Error processing Instruction 0: custom program error: 0x1771"
+        );
+      } else {
+        throw error;
+      }
+    }
+  });
+
+  it("[First Game - Happy Path] First player: Can't reveal a move outside the classic variant", async () => {
     const ix = await program.methods
       .revealChoice(
-        { [choiceToString(SECOND_GAME.firstPlayerChoice)]: {} } as any,
-        [...SECOND_GAME.firstPlayerSalt]
+        { [choiceToString(Choice.Lizard)]: {} } as any,
+        [...FIRST_GAME.firstPlayerSalt]
       )
       .accountsStrict({
-        game: secondGamePda,
+        eventAuthority,
+        program: program.programId,
+        game: firstGamePda,
         player: firstPlayer.publicKey,
       })
       .instruction();
 
-    const txId = await sendSignedVersionedTx(
-      provider,
-      firstPlayer.publicKey,
-      [firstPlayer],
-      ...[ix]
-    );
-
-    console.log("txId:", txId);
+    try {
+      await sendSignedVersionedTx(
+        provider,
+        firstPlayer.publicKey,
+        [firstPlayer],
+        ...[ix]
+      );
+    } catch (error) {
+      if (error instanceof anchor.web3.SendTransactionError) {
+        console.log(error.message);
+        expect(error.message).to.equal(
+          "Error processing Instruction 0: custom program error: 0x177d"
+        );
+      } else {
+        throw error;
+      }
+    }
   });
 
-  it("[Second Game - Forfeit Path] Advance time for forfeit", async () => {
-    const currentClock = await context.banksClient.getClock();
-    const currentTimestamp = currentClock.unixTimestamp;
-    const newTimestamp =
-      currentTimestamp + BigInt(TIME_FOR_PENALIZATION.toNumber());
-    context.setClock(
-      new Clock(
-        currentClock.slot,
-        currentClock.epochStartTimestamp,
-        currentClock.epoch,
-        currentClock.leaderScheduleEpoch,
-        newTimestamp
+  it("[First Game - Happy Path] Spectator: Opens a side pool and bets on the second player", async () => {
+    const [sidePool] = buildSidePoolPda(program, firstGamePda);
+
+    const openIx = await program.methods
+      .openSidePool()
+      .accountsStrict({
+        eventAuthority,
+        program: program.programId,
+        game: firstGamePda,
+        sidePool,
+        creator: authority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .instruction();
+    const betIx = await program.methods
+      .placeSideBet({ secondPlayer: {} } as any, SIDE_BET_AMOUNT)
+      .accountsStrict({
+        eventAuthority,
+        program: program.programId,
+        game: firstGamePda,
+        sidePool,
+        sideBet: buildSideBetPda(program, sidePool, authority.publicKey)[0],
+        bettor: authority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .instruction();
+
+    console.log(
+      "txId:",
+      await sendSignedVersionedTx(
+        provider,
+        authority.publicKey,
+        [authority],
+        ...[openIx, betIx]
       )
     );
-    console.log(
-      `Current timestamp: ${
-        currentClock.unixTimestamp
-      }, New timestamp: ${newTimestamp}, diff: ${
-        newTimestamp - currentClock.unixTimestamp
-      }, Time for penalization: ${TIME_FOR_PENALIZATION}`
+
+    const gameAccount = await program.account.game.fetch(firstGamePda);
+    expect(gameAccount.sidePool.toBase58()).to.equal(sidePool.toBase58());
+    const sidePoolAccount = await program.account.sidePool.fetch(sidePool);
+    expect(sidePoolAccount.secondPlayerTotal.toString()).to.equal(
+      SIDE_BET_AMOUNT.toString()
     );
+    expect(sidePoolAccount.openBets).to.equal(1);
   });
 
-  it("[Second Game - Forfeit Path] Permissionless: Settles second game", async () => {
+  it("[First Game - Happy Path] First player: Reveals for first game", async () => {
     const ix = await program.methods
-      .settleGame()
+      .revealChoice(
+        { [choiceToString(FIRST_GAME.firstPlayerChoice)]: {} } as any,
+        [...FIRST_GAME.firstPlayerSalt]
+      )
       .accountsStrict({
-        game: secondGamePda,
-        mint: USDC_MINT,
-        firstPlayer: firstPlayer.publicKey,
-        firstPlayerEscrowTokenAccount: secondGameFirstPlayerEscrowAta,
-        firstPlayerTokenAccount: firstPlayerAta,
-        secondPlayer: secondPlayer.publicKey,
-        secondPlayerEscrowTokenAccount: secondGameSecondPlayerEscrowAta,
-        secondPlayerTokenAccount: secondPlayerAta,
-        settings: settingsPda,
-        signer: authority.publicKey,
-        tokenProgram: TOKEN_PROGRAM_ID,
+        eventAuthority,
+        program: program.programId,
+        game: firstGamePda,
+        player: firstPlayer.publicKey,
       })
       .instruction();
 
     const txId = await sendSignedVersionedTx(
       provider,
-      authority.publicKey,
-      [authority],
+      firstPlayer.publicKey,
+      [firstPlayer],
       ...[ix]
     );
 
     console.log("txId:", txId);
+  });
 
-    const gameAccount = await program.account.game.fetch(secondGamePda);
+  it("[First Game - Happy Path] Spectator: Can't bet once a player revealed", async () => {
+    const spectator = anchor.web3.Keypair.generate();
+    const [sidePool] = buildSidePoolPda(program, firstGamePda);
+
+    const fundIx = anchor.web3.SystemProgram.transfer({
+      fromPubkey: authority.publicKey,
+      toPubkey: spectator.publicKey,
+      lamports: anchor.web3.LAMPORTS_PER_SOL,
+    });
+    const betIx = await program.methods
+      .placeSideBet({ firstPlayer: {} } as any, SIDE_BET_AMOUNT)
+      .accountsStrict({
+        eventAuthority,
+        program: program.programId,
+        game: firstGamePda,
+        sidePool,
+        sideBet: buildSideBetPda(program, sidePool, spectator.publicKey)[0],
+        bettor: spectator.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .instruction();
+
+    try {
+      await sendSignedVersionedTx(
+        provider,
+        authority.publicKey,
+        [authority, spectator],
+        ...[fundIx, betIx]
+      );
+      expect.fail("side bet was placed after a reveal");
+    } catch (error) {
+      if (error instanceof anchor.web3.SendTransactionError) {
+        console.log(error.message);
+        expect(error.message).to.equal(
+          "Error processing Instruction 1: custom program error: 0x17ac"
+        );
+      } else {
+        throw error;
+      }
+    }
+  });
+
+  it("[First Game - Happy Path] Permissionless: Can't be unwinded since at least one player revealed", async () => {
+    const ix = await program.methods
+      .unwindGame()
+      .accountsStrict({
+        sidePool: null,
+        eventAuthority,
+        program: program.programId,
+        game: firstGamePda,
+        mint: USDC_MINT,
+        firstPlayer: firstPlayer.publicKey,
+        firstPlayerEscrowTokenAccount: firstGameFirstPlayerEscrowAta,
+        firstPlayerTokenAccount: firstPlayerAta,
+        secondPlayer: secondPlayer.publicKey,
+        secondPlayerEscrowTokenAccount: firstGameSecondPlayerEscrowAta,
+        secondPlayerTokenAccount: secondPlayerAta,
+        settings: settingsPda,
+        signer: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .instruction();
+
+    try {
+      await sendSignedVersionedTx(
+        provider,
+        authority.publicKey,
+        [authority],
+        ...[ix]
+      );
+    } catch (error) {
+      if (error instanceof anchor.web3.SendTransactionError) {
+        console.log(error.message);
+        expect(error.message).to.equal(
+          "Error processing Instruction 0: custom program error: 0x1775"
+        );
+      } else {
+        throw error;
+      }
+    }
+  });
+
+  it("[First Game - Happy Path] Second player: Reveals for first game", async () => {
+    const ix = await program.methods
+      .revealChoice(
+        { [choiceToString(FIRST_GAME.secondPlayerChoice)]: {} } as any,
+        [...FIRST_GAME.secondPlayerSalt]
+      )
+      .accountsStrict({
+        eventAuthority,
+        program: program.programId,
+        game: firstGamePda,
+        player: secondPlayer.publicKey,
+      })
+      .instruction();
+
+    const txId = await sendSignedVersionedTx(
+      provider,
+      secondPlayer.publicKey,
+      [secondPlayer],
+      ...[ix]
+    );
+
+    console.log("txId:", txId);
+  });
+
+  it("[First Game - Happy Path] Permissionless: Settles first game", async () => {
+    const ix = await program.methods
+      .settleGame()
+      .accountsStrict({
+        season: null,
+        firstPlayerSeasonEntry: null,
+        secondPlayerSeasonEntry: null,
+        firstPlayerReferrerVault: null,
+        secondPlayerReferrerVault: null,
+        sidePool: buildSidePoolPda(program, firstGamePda)[0],
+        eventAuthority,
+        program: program.programId,
+        game: firstGamePda,
+        mint: USDC_MINT,
+        firstPlayer: firstPlayer.publicKey,
+        firstPlayerEscrowTokenAccount: firstGameFirstPlayerEscrowAta,
+        firstPlayerTokenAccount: firstPlayerAta,
+        secondPlayer: secondPlayer.publicKey,
+        firstPlayerProfile: buildPlayerProfilePda(
+          program,
+          firstPlayer.publicKey
+        )[0],
+        secondPlayerProfile: buildPlayerProfilePda(
+          program,
+          secondPlayer.publicKey
+        )[0],
+        firstPlayerRating: buildPlayerRatingPda(
+          program,
+          firstPlayer.publicKey
+        )[0],
+        secondPlayerRating: buildPlayerRatingPda(
+          program,
+          secondPlayer.publicKey
+        )[0],
+        secondPlayerEscrowTokenAccount: firstGameSecondPlayerEscrowAta,
+        secondPlayerTokenAccount: secondPlayerAta,
+        settings: settingsPda,
+        treasuryTokenAccount: treasuryAta,
+        signer: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .instruction();
+
+    const txId = await sendSignedVersionedTx(
+      provider,
+      authority.publicKey,
+      [authority],
+      ...[ix]
+    );
+
+    console.log("txId:", txId);
+
+    const gameAccount = await program.account.game.fetch(firstGamePda);
+    expect(Object.keys(gameAccount.state)[0]).to.equal("secondPlayerWon");
+
+    const rake = FIRST_GAME.amountToMatch.muln(RAKE_BPS).divn(10_000);
+    expect(gameAccount.amountWon.toString()).to.equal(
+      FIRST_GAME.amountToMatch.sub(rake).muln(2).toString()
+    );
+
+    const winnerProfile = await program.account.playerProfile.fetch(
+      buildPlayerProfilePda(program, secondPlayer.publicKey)[0]
+    );
+    expect(winnerProfile.wins).to.equal(1);
+    expect(winnerProfile.streak).to.equal(1);
+    expect(winnerProfile.mints[0].won.toString()).to.equal(
+      gameAccount.amountWon.toString()
+    );
+    const loserProfile = await program.account.playerProfile.fetch(
+      buildPlayerProfilePda(program, firstPlayer.publicKey)[0]
+    );
+    expect(loserProfile.losses).to.equal(1);
+    expect(loserProfile.completed).to.equal(1);
+
+    // Both started at the default 1200, the provisional K-factor is 40
+    const winnerRating = await program.account.playerRating.fetch(
+      buildPlayerRatingPda(program, secondPlayer.publicKey)[0]
+    );
+    expect(winnerRating.rating).to.equal(1220);
+    const loserRating = await program.account.playerRating.fetch(
+      buildPlayerRatingPda(program, firstPlayer.publicKey)[0]
+    );
+    expect(loserRating.rating).to.equal(1180);
+  });
+
+  it("[First Game - Happy Path] Spectator: Claims the side bet and closes the pool", async () => {
+    const [sidePool] = buildSidePoolPda(program, firstGamePda);
+    const sidePoolAccount = await program.account.sidePool.fetch(sidePool);
+    expect(Object.keys(sidePoolAccount.result)[0]).to.equal("settled");
+
+    const claimIx = await program.methods
+      .claimSideBet()
+      .accountsStrict({
+        eventAuthority,
+        program: program.programId,
+        sidePool,
+        sideBet: buildSideBetPda(program, sidePool, authority.publicKey)[0],
+        bettor: authority.publicKey,
+      })
+      .instruction();
+    const closeIx = await program.methods
+      .closeSidePool()
+      .accountsStrict({
+        eventAuthority,
+        program: program.programId,
+        sidePool,
+        creator: authority.publicKey,
+        signer: authority.publicKey,
+      })
+      .instruction();
+
+    console.log(
+      "txId:",
+      await sendSignedVersionedTx(
+        provider,
+        authority.publicKey,
+        [authority],
+        ...[claimIx, closeIx]
+      )
+    );
+
+    expect(await context.banksClient.getAccount(sidePool)).to.equal(null);
+  });
+
+  it("[Second Game - Forfeit Path] First player: Initializes second game", async () => {
+    const [game] = buildGamePda(
+      program,
+      firstPlayer.publicKey,
+      SECOND_GAME.gameId
+    );
+    const [escrow] = buildEscrowPda(program, game, firstPlayer.publicKey);
+
+    const hash = await getCommitment(
+      game,
+      firstPlayer.publicKey,
+      SECOND_GAME.firstPlayerChoice,
+      SECOND_GAME.firstPlayerSalt
+    );
+
+    const ix = await program.methods
+      .initializeGame(
+        SECOND_GAME.gameId,
+        SECOND_GAME.amountToMatch,
+        [...hash],
+        SECOND_GAME.bestOf,
+        SECOND_GAME.variant as any,
+        null,
+        null,
+        null,
+        null,
+        null
+      )
+      .accountsStrict({
+        season: null,
+        seasonEntry: null,
+        referral: buildReferralPda(program, firstPlayer.publicKey)[0],
+        referrerRewards: null,
+        referrerVault: null,
+        eventAuthority,
+        program: program.programId,
+        game,
+        mint: USDC_MINT,
+        player: firstPlayer.publicKey,
+        playerProfile: buildPlayerProfilePda(program, firstPlayer.publicKey)[0],
+        playerRating: buildPlayerRatingPda(program, firstPlayer.publicKey)[0],
+        playerTokenAccount: firstPlayerAta,
+        settings: settingsPda,
+        mintConfig: buildMintConfigPda(program, USDC_MINT)[0],
+        lobby: buildLobbyPda(program, USDC_MINT, SECOND_GAME.amountToMatch)[0],
+        playerEscrowTokenAccount: escrow,
+        treasury: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .instruction();
+
+    const txId = await sendSignedVersionedTx(
+      provider,
+      firstPlayer.publicKey,
+      [firstPlayer],
+      ...[ix]
+    );
+
+    secondGamePda = game;
+    secondGameFirstPlayerEscrowAta = escrow;
+
+    console.log("txId:", txId);
+  });
+
+  it("[Second Game - Forfeit Path] Second player: Joins second game", async () => {
+    const [escrow] = buildEscrowPda(
+      program,
+      secondGamePda,
+      secondPlayer.publicKey
+    );
+
+    const hash = await getCommitment(
+      secondGamePda,
+      secondPlayer.publicKey,
+      SECOND_GAME.secondPlayerChoice,
+      SECOND_GAME.secondPlayerSalt
+    );
+
+    const ix = await program.methods
+      .joinGame([...hash], [])
+      .accountsStrict({
+        season: null,
+        seasonEntry: null,
+        referral: buildReferralPda(program, secondPlayer.publicKey)[0],
+        referrerRewards: null,
+        referrerVault: null,
+        eventAuthority,
+        program: program.programId,
+        game: secondGamePda,
+        lobby: (await program.account.game.fetch(secondGamePda)).lobby,
+        mint: USDC_MINT,
+        player: secondPlayer.publicKey,
+        playerProfile: buildPlayerProfilePda(
+          program,
+          secondPlayer.publicKey
+        )[0],
+        playerRating: buildPlayerRatingPda(program, secondPlayer.publicKey)[0],
+        playerEscrowTokenAccount: escrow,
+        playerTokenAccount: secondPlayerAta,
+        settings: settingsPda,
+        mintConfig: buildMintConfigPda(program, USDC_MINT)[0],
+        treasury: authority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .instruction();
+
+    const txId = await sendSignedVersionedTx(
+      provider,
+      secondPlayer.publicKey,
+      [secondPlayer],
+      ...[ix]
+    );
+
+    secondGameSecondPlayerEscrowAta = escrow;
+
+    console.log("txId:", txId);
+  });
+
+  it("[Second Game - Forfeit Path] First player: Reveals for second game", async () => {
+    const ix = await program.methods
+      .revealChoice(
+        { [choiceToString(SECOND_GAME.firstPlayerChoice)]: {} } as any,
+        [...SECOND_GAME.firstPlayerSalt]
+      )
+      .accountsStrict({
+        eventAuthority,
+        program: program.programId,
+        game: secondGamePda,
+        player: firstPlayer.publicKey,
+      })
+      .instruction();
+
+    const txId = await sendSignedVersionedTx(
+      provider,
+      firstPlayer.publicKey,
+      [firstPlayer],
+      ...[ix]
+    );
+
+    console.log("txId:", txId);
+  });
+
+  it("[Second Game - Forfeit Path] Advance time for forfeit", async () => {
+    const currentClock = await context.banksClient.getClock();
+    const currentTimestamp = currentClock.unixTimestamp;
+    const newTimestamp =
+      currentTimestamp + BigInt(TIME_FOR_PENALIZATION.toNumber());
+    context.setClock(
+      new Clock(
+        currentClock.slot,
+        currentClock.epochStartTimestamp,
+        currentClock.epoch,
+        currentClock.leaderScheduleEpoch,
+        newTimestamp
+      )
+    );
+    console.log(
+      `Current timestamp: ${
+        currentClock.unixTimestamp
+      }, New timestamp: ${newTimestamp}, diff: ${
+        newTimestamp - currentClock.unixTimestamp
+      }, Time for penalization: ${TIME_FOR_PENALIZATION}`
+    );
+  });
+
+  it("[Second Game - Forfeit Path] Permissionless: Settles second game", async () => {
+    const ix = await program.methods
+      .settleGame()
+      .accountsStrict({
+        season: null,
+        firstPlayerSeasonEntry: null,
+        secondPlayerSeasonEntry: null,
+        firstPlayerReferrerVault: null,
+        secondPlayerReferrerVault: null,
+        sidePool: null,
+        eventAuthority,
+        program: program.programId,
+        game: secondGamePda,
+        mint: USDC_MINT,
+        firstPlayer: firstPlayer.publicKey,
+        firstPlayerEscrowTokenAccount: secondGameFirstPlayerEscrowAta,
+        firstPlayerTokenAccount: firstPlayerAta,
+        secondPlayer: secondPlayer.publicKey,
+        firstPlayerProfile: buildPlayerProfilePda(
+          program,
+          firstPlayer.publicKey
+        )[0],
+        secondPlayerProfile: buildPlayerProfilePda(
+          program,
+          secondPlayer.publicKey
+        )[0],
+        firstPlayerRating: buildPlayerRatingPda(
+          program,
+          firstPlayer.publicKey
+        )[0],
+        secondPlayerRating: buildPlayerRatingPda(
+          program,
+          secondPlayer.publicKey
+        )[0],
+        secondPlayerEscrowTokenAccount: secondGameSecondPlayerEscrowAta,
+        secondPlayerTokenAccount: secondPlayerAta,
+        settings: settingsPda,
+        treasuryTokenAccount: treasuryAta,
+        signer: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .instruction();
+
+    const txId = await sendSignedVersionedTx(
+      provider,
+      authority.publicKey,
+      [authority],
+      ...[ix]
+    );
+
+    console.log("txId:", txId);
+
+    const gameAccount = await program.account.game.fetch(secondGamePda);
+    expect(Object.keys(gameAccount.state)[0]).to.equal("firstPlayerWon");
+  });
+
+  it("[Third Game - Best of Three Path] First player: Initializes third game", async () => {
+    const [game] = buildGamePda(
+      program,
+      firstPlayer.publicKey,
+      THIRD_GAME.gameId
+    );
+    const [escrow] = buildEscrowPda(program, game, firstPlayer.publicKey);
+
+    const [firstRound] = THIRD_GAME.rounds;
+    const hash = await getCommitment(
+      game,
+      firstPlayer.publicKey,
+      firstRound.firstPlayerChoice,
+      firstRound.firstPlayerSalt
+    );
+
+    const ix = await program.methods
+      .initializeGame(
+        THIRD_GAME.gameId,
+        THIRD_GAME.amountToMatch,
+        [...hash],
+        THIRD_GAME.bestOf,
+        THIRD_GAME.variant as any,
+        null,
+        null,
+        null,
+        null,
+        null
+      )
+      .accountsStrict({
+        season: null,
+        seasonEntry: null,
+        referral: buildReferralPda(program, firstPlayer.publicKey)[0],
+        referrerRewards: null,
+        referrerVault: null,
+        eventAuthority,
+        program: program.programId,
+        game,
+        mint: USDC_MINT,
+        player: firstPlayer.publicKey,
+        playerProfile: buildPlayerProfilePda(program, firstPlayer.publicKey)[0],
+        playerRating: buildPlayerRatingPda(program, firstPlayer.publicKey)[0],
+        playerTokenAccount: firstPlayerAta,
+        settings: settingsPda,
+        mintConfig: buildMintConfigPda(program, USDC_MINT)[0],
+        lobby: buildLobbyPda(program, USDC_MINT, THIRD_GAME.amountToMatch)[0],
+        playerEscrowTokenAccount: escrow,
+        treasury: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .instruction();
+
+    const txId = await sendSignedVersionedTx(
+      provider,
+      firstPlayer.publicKey,
+      [firstPlayer],
+      ...[ix]
+    );
+
+    thirdGamePda = game;
+    thirdGameFirstPlayerEscrowAta = escrow;
+
+    console.log("txId:", txId);
+  });
+
+  it("[Third Game - Best of Three Path] Second player: Joins third game", async () => {
+    const [escrow] = buildEscrowPda(
+      program,
+      thirdGamePda,
+      secondPlayer.publicKey
+    );
+
+    const [firstRound] = THIRD_GAME.rounds;
+    const hash = await getCommitment(
+      thirdGamePda,
+      secondPlayer.publicKey,
+      firstRound.secondPlayerChoice,
+      firstRound.secondPlayerSalt
+    );
+
+    const ix = await program.methods
+      .joinGame([...hash], [])
+      .accountsStrict({
+        season: null,
+        seasonEntry: null,
+        referral: buildReferralPda(program, secondPlayer.publicKey)[0],
+        referrerRewards: null,
+        referrerVault: null,
+        eventAuthority,
+        program: program.programId,
+        game: thirdGamePda,
+        lobby: (await program.account.game.fetch(thirdGamePda)).lobby,
+        mint: USDC_MINT,
+        player: secondPlayer.publicKey,
+        playerProfile: buildPlayerProfilePda(
+          program,
+          secondPlayer.publicKey
+        )[0],
+        playerRating: buildPlayerRatingPda(program, secondPlayer.publicKey)[0],
+        playerEscrowTokenAccount: escrow,
+        playerTokenAccount: secondPlayerAta,
+        settings: settingsPda,
+        mintConfig: buildMintConfigPda(program, USDC_MINT)[0],
+        treasury: authority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .instruction();
+
+    const txId = await sendSignedVersionedTx(
+      provider,
+      secondPlayer.publicKey,
+      [secondPlayer],
+      ...[ix]
+    );
+
+    thirdGameSecondPlayerEscrowAta = escrow;
+
+    console.log("txId:", txId);
+  });
+
+  it("[Third Game - Best of Three Path] Both players: Play every round", async () => {
+    for (const [index, round] of THIRD_GAME.rounds.entries()) {
+      if (index > 0) {
+        const firstPlayerHash = await getCommitment(
+          thirdGamePda,
+          firstPlayer.publicKey,
+          round.firstPlayerChoice,
+          round.firstPlayerSalt
+        );
+        const secondPlayerHash = await getCommitment(
+          thirdGamePda,
+          secondPlayer.publicKey,
+          round.secondPlayerChoice,
+          round.secondPlayerSalt
+        );
+        const firstPlayerCommitIx = await program.methods
+          .commitChoice([...firstPlayerHash])
+          .accountsStrict({
+            eventAuthority,
+            program: program.programId,
+            game: thirdGamePda,
+            player: firstPlayer.publicKey,
+          })
+          .instruction();
+        const secondPlayerCommitIx = await program.methods
+          .commitChoice([...secondPlayerHash])
+          .accountsStrict({
+            eventAuthority,
+            program: program.programId,
+            game: thirdGamePda,
+            player: secondPlayer.publicKey,
+          })
+          .instruction();
+        await sendSignedVersionedTx(
+          provider,
+          firstPlayer.publicKey,
+          [firstPlayer, secondPlayer],
+          ...[firstPlayerCommitIx, secondPlayerCommitIx]
+        );
+      }
+
+      const firstPlayerRevealIx = await program.methods
+        .revealChoice(
+          { [choiceToString(round.firstPlayerChoice)]: {} } as any,
+          [...round.firstPlayerSalt]
+        )
+        .accountsStrict({
+          eventAuthority,
+          program: program.programId,
+          game: thirdGamePda,
+          player: firstPlayer.publicKey,
+        })
+        .instruction();
+      const secondPlayerRevealIx = await program.methods
+        .revealChoice(
+          { [choiceToString(round.secondPlayerChoice)]: {} } as any,
+          [...round.secondPlayerSalt]
+        )
+        .accountsStrict({
+          eventAuthority,
+          program: program.programId,
+          game: thirdGamePda,
+          player: secondPlayer.publicKey,
+        })
+        .instruction();
+
+      const txId = await sendSignedVersionedTx(
+        provider,
+        firstPlayer.publicKey,
+        [firstPlayer, secondPlayer],
+        ...[firstPlayerRevealIx, secondPlayerRevealIx]
+      );
+
+      console.log(`Round ${index + 1} txId:`, txId);
+    }
+
+    const gameAccount = await program.account.game.fetch(thirdGamePda);
+    expect(gameAccount.rounds.length).to.equal(THIRD_GAME.rounds.length);
+    expect(gameAccount.firstPlayerWins).to.equal(2);
+    expect(gameAccount.secondPlayerWins).to.equal(0);
+  });
+
+  it("[Third Game - Best of Three Path] Permissionless: Settles third game", async () => {
+    const ix = await program.methods
+      .settleGame()
+      .accountsStrict({
+        season: null,
+        firstPlayerSeasonEntry: null,
+        secondPlayerSeasonEntry: null,
+        firstPlayerReferrerVault: null,
+        secondPlayerReferrerVault: null,
+        sidePool: null,
+        eventAuthority,
+        program: program.programId,
+        game: thirdGamePda,
+        mint: USDC_MINT,
+        firstPlayer: firstPlayer.publicKey,
+        firstPlayerEscrowTokenAccount: thirdGameFirstPlayerEscrowAta,
+        firstPlayerTokenAccount: firstPlayerAta,
+        secondPlayer: secondPlayer.publicKey,
+        firstPlayerProfile: buildPlayerProfilePda(
+          program,
+          firstPlayer.publicKey
+        )[0],
+        secondPlayerProfile: buildPlayerProfilePda(
+          program,
+          secondPlayer.publicKey
+        )[0],
+        firstPlayerRating: buildPlayerRatingPda(
+          program,
+          firstPlayer.publicKey
+        )[0],
+        secondPlayerRating: buildPlayerRatingPda(
+          program,
+          secondPlayer.publicKey
+        )[0],
+        secondPlayerEscrowTokenAccount: thirdGameSecondPlayerEscrowAta,
+        secondPlayerTokenAccount: secondPlayerAta,
+        settings: settingsPda,
+        treasuryTokenAccount: treasuryAta,
+        signer: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .instruction();
+
+    const txId = await sendSignedVersionedTx(
+      provider,
+      authority.publicKey,
+      [authority],
+      ...[ix]
+    );
+
+    console.log("txId:", txId);
+
+    const gameAccount = await program.account.game.fetch(thirdGamePda);
+    expect(Object.keys(gameAccount.state)[0]).to.equal("firstPlayerWon");
+  });
+
+  it("[Fourth Game - Expiry Path] First player: Initializes fourth game with a join deadline", async () => {
+    const [game] = buildGamePda(
+      program,
+      firstPlayer.publicKey,
+      FOURTH_GAME.gameId
+    );
+    const [escrow] = buildEscrowPda(program, game, firstPlayer.publicKey);
+
+    const hash = await getCommitment(
+      game,
+      firstPlayer.publicKey,
+      FOURTH_GAME.firstPlayerChoice,
+      FOURTH_GAME.firstPlayerSalt
+    );
+
+    const currentClock = await context.banksClient.getClock();
+    const joinDeadline = new BN(
+      (currentClock.unixTimestamp + BigInt(FOURTH_GAME.joinWindow)).toString()
+    );
+
+    const ix = await program.methods
+      .initializeGame(
+        FOURTH_GAME.gameId,
+        FOURTH_GAME.amountToMatch,
+        [...hash],
+        FOURTH_GAME.bestOf,
+        FOURTH_GAME.variant as any,
+        joinDeadline,
+        null,
+        null,
+        null,
+        null
+      )
+      .accountsStrict({
+        season: null,
+        seasonEntry: null,
+        referral: buildReferralPda(program, firstPlayer.publicKey)[0],
+        referrerRewards: null,
+        referrerVault: null,
+        eventAuthority,
+        program: program.programId,
+        game,
+        mint: USDC_MINT,
+        player: firstPlayer.publicKey,
+        playerProfile: buildPlayerProfilePda(program, firstPlayer.publicKey)[0],
+        playerRating: buildPlayerRatingPda(program, firstPlayer.publicKey)[0],
+        playerTokenAccount: firstPlayerAta,
+        settings: settingsPda,
+        mintConfig: buildMintConfigPda(program, USDC_MINT)[0],
+        lobby: buildLobbyPda(program, USDC_MINT, FOURTH_GAME.amountToMatch)[0],
+        playerEscrowTokenAccount: escrow,
+        treasury: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .instruction();
+
+    const txId = await sendSignedVersionedTx(
+      provider,
+      firstPlayer.publicKey,
+      [firstPlayer],
+      ...[ix]
+    );
+
+    fourthGamePda = game;
+    fourthGameFirstPlayerEscrowAta = escrow;
+
+    console.log("txId:", txId);
+  });
+
+  it("[Fourth Game - Expiry Path] Advance time past the join deadline", async () => {
+    const currentClock = await context.banksClient.getClock();
+    const newTimestamp =
+      currentClock.unixTimestamp + BigInt(FOURTH_GAME.joinWindow);
+    context.setClock(
+      new Clock(
+        currentClock.slot,
+        currentClock.epochStartTimestamp,
+        currentClock.epoch,
+        currentClock.leaderScheduleEpoch,
+        newTimestamp
+      )
+    );
+  });
+
+  it("[Fourth Game - Expiry Path] Permissionless: Expires fourth game", async () => {
+    const ix = await program.methods
+      .expireGame()
+      .accountsStrict({
+        eventAuthority,
+        program: program.programId,
+        game: fourthGamePda,
+        lobby: (await program.account.game.fetch(fourthGamePda)).lobby,
+        mint: USDC_MINT,
+        firstPlayer: firstPlayer.publicKey,
+        firstPlayerEscrowTokenAccount: fourthGameFirstPlayerEscrowAta,
+        firstPlayerTokenAccount: firstPlayerAta,
+        signer: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .instruction();
+
+    const txId = await sendSignedVersionedTx(
+      provider,
+      authority.publicKey,
+      [authority],
+      ...[ix]
+    );
+
+    console.log("txId:", txId);
+
+    const gameAccount = await context.banksClient.getAccount(fourthGamePda);
+    expect(gameAccount).to.equal(null);
+  });
+
+  it("[Fifth Game - Native SOL Path] First player: Initializes fifth game", async () => {
+    const [game] = buildGamePda(
+      program,
+      firstPlayer.publicKey,
+      FIFTH_GAME.gameId
+    );
+
+    const hash = await getCommitment(
+      game,
+      firstPlayer.publicKey,
+      FIFTH_GAME.firstPlayerChoice,
+      FIFTH_GAME.firstPlayerSalt
+    );
+
+    const ix = await program.methods
+      .initializeNativeGame(
+        FIFTH_GAME.gameId,
+        FIFTH_GAME.amountToMatch,
+        [...hash],
+        FIFTH_GAME.bestOf,
+        FIFTH_GAME.variant as any,
+        null,
+        null,
+        null,
+        null,
+        null
+      )
+      .accountsStrict({
+        season: null,
+        seasonEntry: null,
+        referral: buildReferralPda(program, firstPlayer.publicKey)[0],
+        referrerRewards: null,
+        eventAuthority,
+        program: program.programId,
+        game,
+        player: firstPlayer.publicKey,
+        playerProfile: buildPlayerProfilePda(program, firstPlayer.publicKey)[0],
+        playerRating: buildPlayerRatingPda(program, firstPlayer.publicKey)[0],
+        settings: settingsPda,
+        mintConfig: buildMintConfigPda(
+          program,
+          anchor.web3.PublicKey.default
+        )[0],
+        lobby: buildLobbyPda(
+          program,
+          anchor.web3.PublicKey.default,
+          FIFTH_GAME.amountToMatch
+        )[0],
+        treasury: authority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .instruction();
+
+    const txId = await sendSignedVersionedTx(
+      provider,
+      firstPlayer.publicKey,
+      [firstPlayer],
+      ...[ix]
+    );
+
+    fifthGamePda = game;
+
+    console.log("txId:", txId);
+
+    const gameAccount = await program.account.game.fetch(fifthGamePda);
+    expect(Object.keys(gameAccount.wagerMode)[0]).to.equal("native");
+  });
+
+  it("[Fifth Game - Native SOL Path] Second player: Joins fifth game", async () => {
+    const hash = await getCommitment(
+      fifthGamePda,
+      secondPlayer.publicKey,
+      FIFTH_GAME.secondPlayerChoice,
+      FIFTH_GAME.secondPlayerSalt
+    );
+
+    const ix = await program.methods
+      .joinNativeGame([...hash], [])
+      .accountsStrict({
+        season: null,
+        seasonEntry: null,
+        referral: buildReferralPda(program, secondPlayer.publicKey)[0],
+        referrerRewards: null,
+        eventAuthority,
+        program: program.programId,
+        game: fifthGamePda,
+        lobby: (await program.account.game.fetch(fifthGamePda)).lobby,
+        player: secondPlayer.publicKey,
+        playerProfile: buildPlayerProfilePda(
+          program,
+          secondPlayer.publicKey
+        )[0],
+        playerRating: buildPlayerRatingPda(program, secondPlayer.publicKey)[0],
+        settings: settingsPda,
+        mintConfig: buildMintConfigPda(
+          program,
+          anchor.web3.PublicKey.default
+        )[0],
+        treasury: authority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .instruction();
+
+    const txId = await sendSignedVersionedTx(
+      provider,
+      secondPlayer.publicKey,
+      [secondPlayer],
+      ...[ix]
+    );
+
+    console.log("txId:", txId);
+  });
+
+  it("[Fifth Game - Native SOL Path] Both players: Reveal for fifth game", async () => {
+    for (const [player, choice, salt] of [
+      [firstPlayer, FIFTH_GAME.firstPlayerChoice, FIFTH_GAME.firstPlayerSalt],
+      [secondPlayer, FIFTH_GAME.secondPlayerChoice, FIFTH_GAME.secondPlayerSalt],
+    ] as const) {
+      const ix = await program.methods
+        .revealChoice({ [choiceToString(choice)]: {} } as any, [...salt])
+        .accountsStrict({
+          eventAuthority,
+          program: program.programId,
+          game: fifthGamePda,
+          player: player.publicKey,
+        })
+        .instruction();
+
+      const txId = await sendSignedVersionedTx(
+        provider,
+        player.publicKey,
+        [player],
+        ...[ix]
+      );
+
+      console.log("txId:", txId);
+    }
+  });
+
+  it("[Fifth Game - Native SOL Path] Permissionless: Settles fifth game in lamports", async () => {
+    const balanceBefore = await context.banksClient.getBalance(
+      firstPlayer.publicKey
+    );
+
+    const ix = await program.methods
+      .settleNativeGame()
+      .accountsStrict({
+        season: null,
+        firstPlayerSeasonEntry: null,
+        secondPlayerSeasonEntry: null,
+        firstPlayerReferrerRewards: null,
+        secondPlayerReferrerRewards: null,
+        sidePool: null,
+        eventAuthority,
+        program: program.programId,
+        game: fifthGamePda,
+        settings: settingsPda,
+        firstPlayer: firstPlayer.publicKey,
+        secondPlayer: secondPlayer.publicKey,
+        firstPlayerProfile: buildPlayerProfilePda(
+          program,
+          firstPlayer.publicKey
+        )[0],
+        secondPlayerProfile: buildPlayerProfilePda(
+          program,
+          secondPlayer.publicKey
+        )[0],
+        firstPlayerRating: buildPlayerRatingPda(
+          program,
+          firstPlayer.publicKey
+        )[0],
+        secondPlayerRating: buildPlayerRatingPda(
+          program,
+          secondPlayer.publicKey
+        )[0],
+        treasury: authority.publicKey,
+        signer: authority.publicKey,
+      })
+      .instruction();
+
+    const txId = await sendSignedVersionedTx(
+      provider,
+      authority.publicKey,
+      [authority],
+      ...[ix]
+    );
+
+    console.log("txId:", txId);
+
+    const gameAccount = await program.account.game.fetch(fifthGamePda);
+    expect(Object.keys(gameAccount.state)[0]).to.equal("firstPlayerWon");
+
+    const rake = FIFTH_GAME.amountToMatch.muln(RAKE_BPS).divn(10_000);
+    const amountWon = FIFTH_GAME.amountToMatch.sub(rake).muln(2);
+    expect(gameAccount.amountWon.toString()).to.equal(amountWon.toString());
+
+    const balanceAfter = await context.banksClient.getBalance(
+      firstPlayer.publicKey
+    );
+    expect((balanceAfter - balanceBefore).toString()).to.equal(
+      amountWon.toString()
+    );
+  });
+
+  it("[Sixth Game - Transfer Hook Path] Creates a Token-2022 mint with a transfer hook", async () => {
+    const mint = SIXTH_GAME.mint.publicKey;
+    const ataOf = (owner: anchor.web3.PublicKey) =>
+      getAssociatedTokenAddressSync(
+        mint,
+        owner,
+        false,
+        TOKEN_2022_PROGRAM_ID,
+        ASSOCIATED_TOKEN_PROGRAM_ID
+      );
+    sixthGameFirstPlayerAta = ataOf(firstPlayer.publicKey);
+    sixthGameSecondPlayerAta = ataOf(secondPlayer.publicKey);
+    sixthGameTreasuryAta = ataOf(authority.publicKey);
+
+    const createAtaIxs = [
+      [sixthGameFirstPlayerAta, firstPlayer.publicKey],
+      [sixthGameSecondPlayerAta, secondPlayer.publicKey],
+      [sixthGameTreasuryAta, authority.publicKey],
+    ].map(([ata, owner]) =>
+      createAssociatedTokenAccountIdempotentInstruction(
+        authority.publicKey,
+        ata,
+        owner,
+        mint,
+        TOKEN_2022_PROGRAM_ID,
+        ASSOCIATED_TOKEN_PROGRAM_ID
+      )
+    );
+    const mintToIxs = [sixthGameFirstPlayerAta, sixthGameSecondPlayerAta].map(
+      (ata) =>
+        createMintToInstruction(
+          mint,
+          ata,
+          authority.publicKey,
+          BigInt(SIXTH_GAME.amountToMatch.toString()),
+          [],
+          TOKEN_2022_PROGRAM_ID
+        )
+    );
+    const initializeHookIx = await transferHookProgram.methods
+      .initializeExtraAccountMetaList()
+      .accountsStrict({
+        extraAccountMetaList: buildExtraAccountMetaListPda(
+          transferHookProgram.programId,
+          mint
+        )[0],
+        counter: buildHookCounterPda(transferHookProgram.programId, mint)[0],
+        mint,
+        payer: authority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .instruction();
+
+    const mintConfigIx = await program.methods
+      .initializeMintConfig(
+        mint,
+        true,
+        SIXTH_GAME.amountToMatch,
+        SIXTH_GAME.amountToMatch,
+        null
+      )
+      .accountsStrict({
+        eventAuthority,
+        program: program.programId,
+        mintConfig: buildMintConfigPda(program, mint)[0],
+        settings: settingsPda,
+        signer: authority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .instruction();
+
+    const txId = await sendSignedVersionedTx(
+      provider,
+      authority.publicKey,
+      [authority, SIXTH_GAME.mint],
+      ...buildCreateHookedMintIxs(
+        await context.banksClient.getRent(),
+        authority.publicKey,
+        mint,
+        SIXTH_GAME.decimals,
+        transferHookProgram.programId
+      ),
+      ...createAtaIxs,
+      ...mintToIxs,
+      initializeHookIx,
+      mintConfigIx
+    );
+
+    sixthGameTransferHookAccounts = buildTransferHookAccounts(
+      transferHookProgram.programId,
+      mint
+    );
+
+    console.log("txId:", txId);
+  });
+
+  it("[Sixth Game - Transfer Hook Path] First player: Initializes sixth game", async () => {
+    const [game] = buildGamePda(
+      program,
+      firstPlayer.publicKey,
+      SIXTH_GAME.gameId
+    );
+    const [escrow] = buildEscrowPda(program, game, firstPlayer.publicKey);
+
+    const hash = await getCommitment(
+      game,
+      firstPlayer.publicKey,
+      SIXTH_GAME.firstPlayerChoice,
+      SIXTH_GAME.firstPlayerSalt
+    );
+
+    const ix = await program.methods
+      .initializeGame(
+        SIXTH_GAME.gameId,
+        SIXTH_GAME.amountToMatch,
+        [...hash],
+        SIXTH_GAME.bestOf,
+        SIXTH_GAME.variant as any,
+        null,
+        null,
+        null,
+        null,
+        null
+      )
+      .accountsStrict({
+        season: null,
+        seasonEntry: null,
+        referral: buildReferralPda(program, firstPlayer.publicKey)[0],
+        referrerRewards: null,
+        referrerVault: null,
+        eventAuthority,
+        program: program.programId,
+        game,
+        mint: SIXTH_GAME.mint.publicKey,
+        player: firstPlayer.publicKey,
+        playerProfile: buildPlayerProfilePda(program, firstPlayer.publicKey)[0],
+        playerRating: buildPlayerRatingPda(program, firstPlayer.publicKey)[0],
+        playerTokenAccount: sixthGameFirstPlayerAta,
+        settings: settingsPda,
+        mintConfig: buildMintConfigPda(program, SIXTH_GAME.mint.publicKey)[0],
+        lobby: buildLobbyPda(
+          program,
+          SIXTH_GAME.mint.publicKey,
+          SIXTH_GAME.amountToMatch
+        )[0],
+        playerEscrowTokenAccount: escrow,
+        treasury: authority.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .remainingAccounts(sixthGameTransferHookAccounts)
+      .instruction();
+
+    const txId = await sendSignedVersionedTx(
+      provider,
+      firstPlayer.publicKey,
+      [firstPlayer],
+      ...[ix]
+    );
+
+    sixthGamePda = game;
+
+    console.log("txId:", txId);
+  });
+
+  it("[Sixth Game - Transfer Hook Path] Second player: Joins sixth game", async () => {
+    const [escrow] = buildEscrowPda(
+      program,
+      sixthGamePda,
+      secondPlayer.publicKey
+    );
+
+    const hash = await getCommitment(
+      sixthGamePda,
+      secondPlayer.publicKey,
+      SIXTH_GAME.secondPlayerChoice,
+      SIXTH_GAME.secondPlayerSalt
+    );
+
+    const ix = await program.methods
+      .joinGame([...hash], [])
+      .accountsStrict({
+        season: null,
+        seasonEntry: null,
+        referral: buildReferralPda(program, secondPlayer.publicKey)[0],
+        referrerRewards: null,
+        referrerVault: null,
+        eventAuthority,
+        program: program.programId,
+        game: sixthGamePda,
+        lobby: (await program.account.game.fetch(sixthGamePda)).lobby,
+        mint: SIXTH_GAME.mint.publicKey,
+        player: secondPlayer.publicKey,
+        playerProfile: buildPlayerProfilePda(
+          program,
+          secondPlayer.publicKey
+        )[0],
+        playerRating: buildPlayerRatingPda(program, secondPlayer.publicKey)[0],
+        playerEscrowTokenAccount: escrow,
+        playerTokenAccount: sixthGameSecondPlayerAta,
+        settings: settingsPda,
+        mintConfig: buildMintConfigPda(program, SIXTH_GAME.mint.publicKey)[0],
+        treasury: authority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .remainingAccounts(sixthGameTransferHookAccounts)
+      .instruction();
+
+    const txId = await sendSignedVersionedTx(
+      provider,
+      secondPlayer.publicKey,
+      [secondPlayer],
+      ...[ix]
+    );
+
+    console.log("txId:", txId);
+  });
+
+  it("[Sixth Game - Transfer Hook Path] Both players: Reveal for sixth game", async () => {
+    for (const [player, choice, salt] of [
+      [firstPlayer, SIXTH_GAME.firstPlayerChoice, SIXTH_GAME.firstPlayerSalt],
+      [secondPlayer, SIXTH_GAME.secondPlayerChoice, SIXTH_GAME.secondPlayerSalt],
+    ] as const) {
+      const ix = await program.methods
+        .revealChoice({ [choiceToString(choice)]: {} } as any, [...salt])
+        .accountsStrict({
+          eventAuthority,
+          program: program.programId,
+          game: sixthGamePda,
+          player: player.publicKey,
+        })
+        .instruction();
+
+      const txId = await sendSignedVersionedTx(
+        provider,
+        player.publicKey,
+        [player],
+        ...[ix]
+      );
+
+      console.log("txId:", txId);
+    }
+  });
+
+  it("[Sixth Game - Transfer Hook Path] Permissionless: Settles sixth game through the hook", async () => {
+    const ix = await program.methods
+      .settleGame()
+      .accountsStrict({
+        season: null,
+        firstPlayerSeasonEntry: null,
+        secondPlayerSeasonEntry: null,
+        firstPlayerReferrerVault: null,
+        secondPlayerReferrerVault: null,
+        sidePool: null,
+        eventAuthority,
+        program: program.programId,
+        game: sixthGamePda,
+        mint: SIXTH_GAME.mint.publicKey,
+        firstPlayer: firstPlayer.publicKey,
+        firstPlayerEscrowTokenAccount: buildEscrowPda(
+          program,
+          sixthGamePda,
+          firstPlayer.publicKey
+        )[0],
+        firstPlayerTokenAccount: sixthGameFirstPlayerAta,
+        secondPlayer: secondPlayer.publicKey,
+        firstPlayerProfile: buildPlayerProfilePda(
+          program,
+          firstPlayer.publicKey
+        )[0],
+        secondPlayerProfile: buildPlayerProfilePda(
+          program,
+          secondPlayer.publicKey
+        )[0],
+        firstPlayerRating: buildPlayerRatingPda(
+          program,
+          firstPlayer.publicKey
+        )[0],
+        secondPlayerRating: buildPlayerRatingPda(
+          program,
+          secondPlayer.publicKey
+        )[0],
+        secondPlayerEscrowTokenAccount: buildEscrowPda(
+          program,
+          sixthGamePda,
+          secondPlayer.publicKey
+        )[0],
+        secondPlayerTokenAccount: sixthGameSecondPlayerAta,
+        settings: settingsPda,
+        treasuryTokenAccount: sixthGameTreasuryAta,
+        signer: authority.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .remainingAccounts(sixthGameTransferHookAccounts)
+      .instruction();
+
+    const txId = await sendSignedVersionedTx(
+      provider,
+      authority.publicKey,
+      [authority],
+      ...[ix]
+    );
+
+    console.log("txId:", txId);
+
+    const gameAccount = await program.account.game.fetch(sixthGamePda);
+    expect(Object.keys(gameAccount.state)[0]).to.equal("firstPlayerWon");
+
+    // Two deposits, two payouts to the winner and a rake out of each escrow
+    const counter = await transferHookProgram.account.counter.fetch(
+      buildHookCounterPda(
+        transferHookProgram.programId,
+        SIXTH_GAME.mint.publicKey
+      )[0]
+    );
+    expect(counter.transfers.toNumber()).to.equal(6);
+  });
+
+  it("[Tournament] Organizer: Creates a tournament", async () => {
+    const [tournament] = buildTournamentPda(
+      program,
+      authority.publicKey,
+      TOURNAMENT.tournamentId
+    );
+
+    const ix = await program.methods
+      .createTournament(
+        TOURNAMENT.tournamentId,
+        TOURNAMENT.entryFee,
+        TOURNAMENT.maxPlayers,
+        TOURNAMENT.bestOf,
+        TOURNAMENT.variant as any,
+        TOURNAMENT.prizeSplitBps,
+        null,
+        null
+      )
+      .accountsStrict({
+        eventAuthority,
+        program: program.programId,
+        tournament,
+        settings: settingsPda,
+        organizer: authority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .instruction();
+
+    const txId = await sendSignedVersionedTx(
+      provider,
+      authority.publicKey,
+      [authority],
+      ...[ix]
+    );
+
+    tournamentPda = tournament;
+
+    console.log("txId:", txId);
+
+    const tournamentAccount = await program.account.tournament.fetch(
+      tournamentPda
+    );
+    expect(Object.keys(tournamentAccount.state)[0]).to.equal("registration");
+  });
+
+  it("[Tournament] Both players: Register and the organizer starts the bracket", async () => {
+    for (const player of [firstPlayer, secondPlayer]) {
+      const ix = await program.methods
+        .registerForTournament()
+        .accountsStrict({
+          eventAuthority,
+          program: program.programId,
+          tournament: tournamentPda,
+          settings: settingsPda,
+          player: player.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .instruction();
+
+      const txId = await sendSignedVersionedTx(
+        provider,
+        player.publicKey,
+        [player],
+        ...[ix]
+      );
+
+      console.log("txId:", txId);
+    }
+
+    const ix = await program.methods
+      .startTournament(Buffer.from([0, 1]))
+      .accountsStrict({
+        eventAuthority,
+        program: program.programId,
+        tournament: tournamentPda,
+        organizer: authority.publicKey,
+      })
+      .instruction();
+
+    const txId = await sendSignedVersionedTx(
+      provider,
+      authority.publicKey,
+      [authority],
+      ...[ix]
+    );
+
+    console.log("txId:", txId);
+
+    const tournamentAccount = await program.account.tournament.fetch(
+      tournamentPda
+    );
+    expect(Object.keys(tournamentAccount.state)[0]).to.equal("running");
+    expect(tournamentAccount.prizePool.toString()).to.equal(
+      TOURNAMENT.entryFee.muln(TOURNAMENT.maxPlayers).toString()
+    );
+    expect(tournamentAccount.matches[0].firstPlayer.toBase58()).to.equal(
+      firstPlayer.publicKey.toBase58()
+    );
+  });
+
+  it("[Tournament] Both players: Play the final", async () => {
+    const gameId = buildTournamentGameId(tournamentPda, 0, 0);
+    [tournamentGamePda] = buildGamePda(program, firstPlayer.publicKey, gameId);
+
+    const createIx = await program.methods
+      .createTournamentGame(
+        0,
+        gameId,
+        [
+          ...(await getCommitment(
+            tournamentGamePda,
+            firstPlayer.publicKey,
+            TOURNAMENT.firstPlayerChoice,
+            TOURNAMENT.firstPlayerSalt
+          )),
+        ]
+      )
+      .accountsStrict({
+        eventAuthority,
+        program: program.programId,
+        tournament: tournamentPda,
+        game: tournamentGamePda,
+        player: firstPlayer.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .instruction();
+
+    console.log(
+      "txId:",
+      await sendSignedVersionedTx(
+        provider,
+        firstPlayer.publicKey,
+        [firstPlayer],
+        ...[createIx]
+      )
+    );
+
+    const joinIx = await program.methods
+      .joinTournamentGame(0, [
+        ...(await getCommitment(
+          tournamentGamePda,
+          secondPlayer.publicKey,
+          TOURNAMENT.secondPlayerChoice,
+          TOURNAMENT.secondPlayerSalt
+        )),
+      ])
+      .accountsStrict({
+        eventAuthority,
+        program: program.programId,
+        tournament: tournamentPda,
+        game: tournamentGamePda,
+        player: secondPlayer.publicKey,
+      })
+      .instruction();
+
+    console.log(
+      "txId:",
+      await sendSignedVersionedTx(
+        provider,
+        secondPlayer.publicKey,
+        [secondPlayer],
+        ...[joinIx]
+      )
+    );
+
+    for (const [player, choice, salt] of [
+      [firstPlayer, TOURNAMENT.firstPlayerChoice, TOURNAMENT.firstPlayerSalt],
+      [secondPlayer, TOURNAMENT.secondPlayerChoice, TOURNAMENT.secondPlayerSalt],
+    ] as const) {
+      const ix = await program.methods
+        .revealChoice({ [choiceToString(choice)]: {} } as any, [...salt])
+        .accountsStrict({
+          eventAuthority,
+          program: program.programId,
+          game: tournamentGamePda,
+          player: player.publicKey,
+        })
+        .instruction();
+
+      const txId = await sendSignedVersionedTx(
+        provider,
+        player.publicKey,
+        [player],
+        ...[ix]
+      );
+
+      console.log("txId:", txId);
+    }
+  });
+
+  it("[Tournament] Permissionless: Settles the final and crowns the champion", async () => {
+    const ix = await program.methods
+      .settleTournamentGame(0)
+      .accountsStrict({
+        eventAuthority,
+        program: program.programId,
+        tournament: tournamentPda,
+        game: tournamentGamePda,
+        settings: settingsPda,
+        firstPlayer: firstPlayer.publicKey,
+        signer: authority.publicKey,
+      })
+      .instruction();
+
+    const txId = await sendSignedVersionedTx(
+      provider,
+      authority.publicKey,
+      [authority],
+      ...[ix]
+    );
+
+    console.log("txId:", txId);
+
+    const gameAccount = await context.banksClient.getAccount(tournamentGamePda);
+    expect(gameAccount).to.equal(null);
+
+    const tournamentAccount = await program.account.tournament.fetch(
+      tournamentPda
+    );
+    expect(Object.keys(tournamentAccount.state)[0]).to.equal("finished");
+    expect(tournamentAccount.matches[0].winner.toBase58()).to.equal(
+      firstPlayer.publicKey.toBase58()
+    );
+  });
+
+  it("[Tournament] Champion: Claims the prize and the organizer closes the tournament", async () => {
+    const balanceBefore = await context.banksClient.getBalance(
+      firstPlayer.publicKey
+    );
+
+    const claimIx = await program.methods
+      .claimTournamentPrize()
+      .accountsStrict({
+        eventAuthority,
+        program: program.programId,
+        tournament: tournamentPda,
+        player: firstPlayer.publicKey,
+      })
+      .instruction();
+
+    // Fees are paid by the organizer so the balance change is the prize alone
+    console.log(
+      "txId:",
+      await sendSignedVersionedTx(
+        provider,
+        authority.publicKey,
+        [authority, firstPlayer],
+        ...[claimIx]
+      )
+    );
+
+    const balanceAfter = await context.banksClient.getBalance(
+      firstPlayer.publicKey
+    );
+    expect((balanceAfter - balanceBefore).toString()).to.equal(
+      TOURNAMENT.entryFee.muln(TOURNAMENT.maxPlayers).toString()
+    );
+
+    const closeIx = await program.methods
+      .closeTournament()
+      .accountsStrict({
+        eventAuthority,
+        program: program.programId,
+        tournament: tournamentPda,
+        settings: settingsPda,
+        organizer: authority.publicKey,
+      })
+      .instruction();
+
+    console.log(
+      "txId:",
+      await sendSignedVersionedTx(
+        provider,
+        authority.publicKey,
+        [authority],
+        ...[closeIx]
+      )
+    );
+
+    const tournamentAccount = await context.banksClient.getAccount(
+      tournamentPda
+    );
+    expect(tournamentAccount).to.equal(null);
+  });
+
+  it("[Tournament - Replay Path] Both players: Draw the final", async () => {
+    const [tournament] = buildTournamentPda(
+      program,
+      authority.publicKey,
+      REPLAYED_TOURNAMENT.tournamentId
+    );
+    tournamentPda = tournament;
+
+    const createIx = await program.methods
+      .createTournament(
+        REPLAYED_TOURNAMENT.tournamentId,
+        TOURNAMENT.entryFee,
+        TOURNAMENT.maxPlayers,
+        TOURNAMENT.bestOf,
+        TOURNAMENT.variant as any,
+        TOURNAMENT.prizeSplitBps,
+        null,
+        null
+      )
+      .accountsStrict({
+        eventAuthority,
+        program: program.programId,
+        tournament,
+        settings: settingsPda,
+        organizer: authority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .instruction();
+    const startIx = await program.methods
+      .startTournament(Buffer.from([0, 1]))
+      .accountsStrict({
+        eventAuthority,
+        program: program.programId,
+        tournament,
+        organizer: authority.publicKey,
+      })
+      .instruction();
+
+    console.log(
+      "txId:",
+      await sendSignedVersionedTx(
+        provider,
+        authority.publicKey,
+        [authority],
+        ...[createIx]
+      )
+    );
+    for (const player of [firstPlayer, secondPlayer]) {
+      const ix = await program.methods
+        .registerForTournament()
+        .accountsStrict({
+          eventAuthority,
+          program: program.programId,
+          tournament,
+          settings: settingsPda,
+          player: player.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .instruction();
+
+      console.log(
+        "txId:",
+        await sendSignedVersionedTx(
+          provider,
+          player.publicKey,
+          [player],
+          ...[ix]
+        )
+      );
+    }
+    console.log(
+      "txId:",
+      await sendSignedVersionedTx(
+        provider,
+        authority.publicKey,
+        [authority],
+        ...[startIx]
+      )
+    );
+
+    const gameId = buildTournamentGameId(tournament, 0, 0);
+    [tournamentGamePda] = buildGamePda(program, firstPlayer.publicKey, gameId);
+    const createGameIx = await program.methods
+      .createTournamentGame(
+        0,
+        gameId,
+        [
+          ...(await getCommitment(
+            tournamentGamePda,
+            firstPlayer.publicKey,
+            REPLAYED_TOURNAMENT.choice,
+            REPLAYED_TOURNAMENT.firstPlayerSalt
+          )),
+        ]
+      )
+      .accountsStrict({
+        eventAuthority,
+        program: program.programId,
+        tournament,
+        game: tournamentGamePda,
+        player: firstPlayer.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .instruction();
+    const joinGameIx = await program.methods
+      .joinTournamentGame(0, [
+        ...(await getCommitment(
+          tournamentGamePda,
+          secondPlayer.publicKey,
+          REPLAYED_TOURNAMENT.choice,
+          REPLAYED_TOURNAMENT.secondPlayerSalt
+        )),
+      ])
+      .accountsStrict({
+        eventAuthority,
+        program: program.programId,
+        tournament,
+        game: tournamentGamePda,
+        player: secondPlayer.publicKey,
+      })
+      .instruction();
+
+    console.log(
+      "txId:",
+      await sendSignedVersionedTx(
+        provider,
+        firstPlayer.publicKey,
+        [firstPlayer],
+        ...[createGameIx]
+      )
+    );
+    console.log(
+      "txId:",
+      await sendSignedVersionedTx(
+        provider,
+        secondPlayer.publicKey,
+        [secondPlayer],
+        ...[joinGameIx]
+      )
+    );
+
+    for (const [player, salt] of [
+      [firstPlayer, REPLAYED_TOURNAMENT.firstPlayerSalt],
+      [secondPlayer, REPLAYED_TOURNAMENT.secondPlayerSalt],
+    ] as const) {
+      const ix = await program.methods
+        .revealChoice(
+          { [choiceToString(REPLAYED_TOURNAMENT.choice)]: {} } as any,
+          [...salt]
+        )
+        .accountsStrict({
+          eventAuthority,
+          program: program.programId,
+          game: tournamentGamePda,
+          player: player.publicKey,
+        })
+        .instruction();
+
+      console.log(
+        "txId:",
+        await sendSignedVersionedTx(
+          provider,
+          player.publicKey,
+          [player],
+          ...[ix]
+        )
+      );
+    }
+  });
+
+  it("[Tournament - Replay Path] Permissionless: Settles the draw after the round deadline", async () => {
+    const tournamentBefore = await program.account.tournament.fetch(
+      tournamentPda
+    );
+    const timeForStale = BigInt(tournamentBefore.timeForStale.toString());
+    const settledAt =
+      BigInt(tournamentBefore.roundStartedAt.toString()) +
+      timeForStale +
+      BigInt(1);
+    await setClockTimestamp(settledAt);
+
+    const ix = await program.methods
+      .settleTournamentGame(0)
+      .accountsStrict({
+        eventAuthority,
+        program: program.programId,
+        tournament: tournamentPda,
+        game: tournamentGamePda,
+        settings: settingsPda,
+        firstPlayer: firstPlayer.publicKey,
+        signer: authority.publicKey,
+      })
+      .instruction();
+
+    console.log(
+      "txId:",
+      await sendSignedVersionedTx(
+        provider,
+        authority.publicKey,
+        [authority],
+        ...[ix]
+      )
+    );
+
+    const tournamentAccount = await program.account.tournament.fetch(
+      tournamentPda
+    );
+    expect(Object.keys(tournamentAccount.state)[0]).to.equal("running");
+    expect(tournamentAccount.matches[0].game).to.equal(null);
+    expect(tournamentAccount.matches[0].replayDeadline.toString()).to.equal(
+      (settledAt + timeForStale).toString()
+    );
+  });
+
+  it("[Tournament - Replay Path] Permissionless: Can't claim a walkover before the replay deadline", async () => {
+    const ix = await program.methods
+      .claimTournamentWalkover(0)
+      .accountsStrict({
+        eventAuthority,
+        program: program.programId,
+        tournament: tournamentPda,
+        game: null,
+        firstPlayer: null,
+        signer: authority.publicKey,
+      })
+      .instruction();
+
+    try {
+      await sendSignedVersionedTx(
+        provider,
+        authority.publicKey,
+        [authority],
+        ...[ix]
+      );
+      expect.fail("walkover was claimed before the replay deadline");
+    } catch (error) {
+      if (error instanceof anchor.web3.SendTransactionError) {
+        console.log(error.message);
+        expect(error.message).to.equal(
+          "Error processing Instruction 0: custom program error: 0x179c"
+        );
+      } else {
+        throw error;
+      }
+    }
+  });
+
+  it("[Tournament - Replay Path] Permissionless: Claims a walkover for the higher seed once the replay deadline passes", async () => {
+    const tournamentBefore = await program.account.tournament.fetch(
+      tournamentPda
+    );
+    await setClockTimestamp(
+      BigInt(tournamentBefore.matches[0].replayDeadline.toString())
+    );
+
+    const ix = await program.methods
+      .claimTournamentWalkover(0)
+      .accountsStrict({
+        eventAuthority,
+        program: program.programId,
+        tournament: tournamentPda,
+        game: null,
+        firstPlayer: null,
+        signer: authority.publicKey,
+      })
+      .instruction();
+
+    console.log(
+      "txId:",
+      await sendSignedVersionedTx(
+        provider,
+        authority.publicKey,
+        [authority],
+        ...[ix]
+      )
+    );
+
+    const tournamentAccount = await program.account.tournament.fetch(
+      tournamentPda
+    );
+    expect(Object.keys(tournamentAccount.state)[0]).to.equal("finished");
+    expect(tournamentAccount.matches[0].winner.toBase58()).to.equal(
+      firstPlayer.publicKey.toBase58()
+    );
+  });
+
+  it("[Season] Admin: Creates a season", async () => {
+    const [season] = buildSeasonPda(program, SEASON.seasonId);
+    const { unixTimestamp } = await context.banksClient.getClock();
+
+    const ix = await program.methods
+      .createSeason(
+        SEASON.seasonId,
+        SEASON.leaderboardSize,
+        new BN(unixTimestamp.toString()),
+        new BN((unixTimestamp + BigInt(SEASON.length)).toString()),
+        SEASON.ranking as any,
+        SEASON.feeShareBps,
+        SEASON.prizeSplitBps
+      )
+      .accountsStrict({
+        eventAuthority,
+        program: program.programId,
+        season,
+        settings: settingsPda,
+        signer: authority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .instruction();
+
+    console.log(
+      "txId:",
+      await sendSignedVersionedTx(
+        provider,
+        authority.publicKey,
+        [authority],
+        ...[ix]
+      )
+    );
+
+    const seasonAccount = await program.account.season.fetch(season);
+    expect(seasonAccount.leaderboard).to.deep.equal([]);
+    expect(seasonAccount.prizePool).to.equal(null);
+  });
+
+  it("[Season] Both players: Join the season", async () => {
+    const [season] = buildSeasonPda(program, SEASON.seasonId);
+
+    for (const player of [firstPlayer, secondPlayer]) {
+      const [seasonEntry] = buildSeasonEntryPda(
+        program,
+        season,
+        player.publicKey
+      );
+      const ix = await program.methods
+        .joinSeason()
+        .accountsStrict({
+          eventAuthority,
+          program: program.programId,
+          season,
+          seasonEntry,
+          player: player.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .instruction();
+
+      console.log(
+        "txId:",
+        await sendSignedVersionedTx(
+          provider,
+          player.publicKey,
+          [player],
+          ...[ix]
+        )
+      );
+
+      const entryAccount = await program.account.seasonEntry.fetch(
+        seasonEntry
+      );
+      expect(entryAccount.player.toString()).to.equal(
+        player.publicKey.toString()
+      );
+      expect(entryAccount.points.toString()).to.equal("0");
+    }
+  });
+
+  it("[Season Game] Both players: Play a game counting towards the season", async () => {
+    const [season] = buildSeasonPda(program, SEASON.seasonId);
+    const [game] = buildGamePda(
+      program,
+      firstPlayer.publicKey,
+      SEASON_GAME.gameId
+    );
+    seasonGamePda = game;
+
+    const initIx = await program.methods
+      .initializeNativeGame(
+        SEASON_GAME.gameId,
+        SEASON_GAME.amountToMatch,
+        [
+          ...(await getCommitment(
+            game,
+            firstPlayer.publicKey,
+            SEASON_GAME.firstPlayerChoice,
+            SEASON_GAME.firstPlayerSalt
+          )),
+        ],
+        SEASON_GAME.bestOf,
+        SEASON_GAME.variant as any,
+        null,
+        null,
+        null,
+        null,
+        null
+      )
+      .accountsStrict({
+        season,
+        seasonEntry: buildSeasonEntryPda(
+          program,
+          season,
+          firstPlayer.publicKey
+        )[0],
+        referral: buildReferralPda(program, firstPlayer.publicKey)[0],
+        referrerRewards: null,
+        eventAuthority,
+        program: program.programId,
+        game,
+        player: firstPlayer.publicKey,
+        playerProfile: buildPlayerProfilePda(program, firstPlayer.publicKey)[0],
+        playerRating: buildPlayerRatingPda(program, firstPlayer.publicKey)[0],
+        settings: settingsPda,
+        mintConfig: buildMintConfigPda(
+          program,
+          anchor.web3.PublicKey.default
+        )[0],
+        lobby: buildLobbyPda(
+          program,
+          anchor.web3.PublicKey.default,
+          SEASON_GAME.amountToMatch
+        )[0],
+        treasury: authority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .instruction();
+
+    console.log(
+      "txId:",
+      await sendSignedVersionedTx(
+        provider,
+        firstPlayer.publicKey,
+        [firstPlayer],
+        ...[initIx]
+      )
+    );
+
+    const joinIx = await program.methods
+      .joinNativeGame(
+        [
+          ...(await getCommitment(
+            game,
+            secondPlayer.publicKey,
+            SEASON_GAME.secondPlayerChoice,
+            SEASON_GAME.secondPlayerSalt
+          )),
+        ],
+        []
+      )
+      .accountsStrict({
+        season,
+        seasonEntry: buildSeasonEntryPda(
+          program,
+          season,
+          secondPlayer.publicKey
+        )[0],
+        referral: buildReferralPda(program, secondPlayer.publicKey)[0],
+        referrerRewards: null,
+        eventAuthority,
+        program: program.programId,
+        game,
+        lobby: (await program.account.game.fetch(game)).lobby,
+        player: secondPlayer.publicKey,
+        playerProfile: buildPlayerProfilePda(
+          program,
+          secondPlayer.publicKey
+        )[0],
+        playerRating: buildPlayerRatingPda(program, secondPlayer.publicKey)[0],
+        settings: settingsPda,
+        mintConfig: buildMintConfigPda(
+          program,
+          anchor.web3.PublicKey.default
+        )[0],
+        treasury: authority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .instruction();
+
+    console.log(
+      "txId:",
+      await sendSignedVersionedTx(
+        provider,
+        secondPlayer.publicKey,
+        [secondPlayer],
+        ...[joinIx]
+      )
+    );
+
+    for (const [player, choice, salt] of [
+      [firstPlayer, SEASON_GAME.firstPlayerChoice, SEASON_GAME.firstPlayerSalt],
+      [
+        secondPlayer,
+        SEASON_GAME.secondPlayerChoice,
+        SEASON_GAME.secondPlayerSalt,
+      ],
+    ] as const) {
+      const ix = await program.methods
+        .revealChoice({ [choiceToString(choice)]: {} } as any, [...salt])
+        .accountsStrict({
+          eventAuthority,
+          program: program.programId,
+          game,
+          player: player.publicKey,
+        })
+        .instruction();
+
+      console.log(
+        "txId:",
+        await sendSignedVersionedTx(
+          provider,
+          player.publicKey,
+          [player],
+          ...[ix]
+        )
+      );
+    }
+
+    const gameAccount = await program.account.game.fetch(game);
+    expect(gameAccount.season.toBase58()).to.equal(season.toBase58());
+  });
+
+  it("[Season Game] Permissionless: Settles the game onto the leaderboard", async () => {
+    const [season] = buildSeasonPda(program, SEASON.seasonId);
+    const [firstPlayerSeasonEntry] = buildSeasonEntryPda(
+      program,
+      season,
+      firstPlayer.publicKey
+    );
+    const [secondPlayerSeasonEntry] = buildSeasonEntryPda(
+      program,
+      season,
+      secondPlayer.publicKey
+    );
+
+    const ix = await program.methods
+      .settleNativeGame()
+      .accountsStrict({
+        season,
+        firstPlayerSeasonEntry,
+        secondPlayerSeasonEntry,
+        firstPlayerReferrerRewards: null,
+        secondPlayerReferrerRewards: null,
+        sidePool: null,
+        eventAuthority,
+        program: program.programId,
+        game: seasonGamePda,
+        settings: settingsPda,
+        firstPlayer: firstPlayer.publicKey,
+        secondPlayer: secondPlayer.publicKey,
+        firstPlayerProfile: buildPlayerProfilePda(
+          program,
+          firstPlayer.publicKey
+        )[0],
+        secondPlayerProfile: buildPlayerProfilePda(
+          program,
+          secondPlayer.publicKey
+        )[0],
+        firstPlayerRating: buildPlayerRatingPda(
+          program,
+          firstPlayer.publicKey
+        )[0],
+        secondPlayerRating: buildPlayerRatingPda(
+          program,
+          secondPlayer.publicKey
+        )[0],
+        treasury: authority.publicKey,
+        signer: authority.publicKey,
+      })
+      .instruction();
+
+    console.log(
+      "txId:",
+      await sendSignedVersionedTx(
+        provider,
+        authority.publicKey,
+        [authority],
+        ...[ix]
+      )
+    );
+
+    const firstPlayerEntry = await program.account.seasonEntry.fetch(
+      firstPlayerSeasonEntry
+    );
+    expect(firstPlayerEntry.wins).to.equal(1);
+    expect(firstPlayerEntry.points.toString()).to.equal("3");
+    const secondPlayerEntry = await program.account.seasonEntry.fetch(
+      secondPlayerSeasonEntry
+    );
+    expect(secondPlayerEntry.losses).to.equal(1);
+    expect(secondPlayerEntry.points.toString()).to.equal("0");
+
+    // Only positive scores place.
+    const seasonAccount = await program.account.season.fetch(season);
+    expect(seasonAccount.leaderboard.length).to.equal(1);
+    expect(seasonAccount.leaderboard[0].player.toBase58()).to.equal(
+      firstPlayer.publicKey.toBase58()
+    );
+    expect(seasonAccount.leaderboard[0].score.toString()).to.equal("3");
+  });
+
+  it("[Season Game] Permissionless: Ranks entries, once each", async () => {
+    const [season] = buildSeasonPda(program, SEASON.seasonId);
+    const rankIx = (player: anchor.web3.PublicKey) =>
+      program.methods
+        .rankSeasonEntry()
+        .accountsStrict({
+          eventAuthority,
+          program: program.programId,
+          season,
+          seasonEntry: buildSeasonEntryPda(program, season, player)[0],
+          signer: authority.publicKey,
+        })
+        .instruction();
+
+    // Without points, the second player is checked but doesn't place.
+    console.log(
+      "txId:",
+      await sendSignedVersionedTx(
+        provider,
+        authority.publicKey,
+        [authority],
+        ...[await rankIx(secondPlayer.publicKey)]
+      )
+    );
+    const seasonAccount = await program.account.season.fetch(season);
+    expect(seasonAccount.leaderboard.length).to.equal(1);
+
+    try {
+      await sendSignedVersionedTx(
+        provider,
+        authority.publicKey,
+        [authority],
+        ...[await rankIx(firstPlayer.publicKey)]
+      );
+      expect.fail("ranked entry was ranked again");
+    } catch (error) {
+      if (error instanceof anchor.web3.SendTransactionError) {
+        console.log(error.message);
+        expect(error.message).to.equal(
+          "Error processing Instruction 0: custom program error: 0x17b3"
+        );
+      } else {
+        throw error;
+      }
+    }
+  });
+
+  it("[Season Game] First player: Can't claim while standings are being ranked", async () => {
+    const [season] = buildSeasonPda(program, SEASON.seasonId);
+    const seasonAccount = await program.account.season.fetch(season);
+    await setClockTimestamp(BigInt(seasonAccount.endAt.toString()));
+
+    const ix = await program.methods
+      .claimSeasonPrize()
+      .accountsStrict({
+        eventAuthority,
+        program: program.programId,
+        season,
+        player: firstPlayer.publicKey,
+      })
+      .instruction();
+
+    try {
+      await sendSignedVersionedTx(
+        provider,
+        authority.publicKey,
+        [authority, firstPlayer],
+        ...[ix]
+      );
+      expect.fail("prize was claimed during the ranking period");
+    } catch (error) {
+      if (error instanceof anchor.web3.SendTransactionError) {
+        console.log(error.message);
+        expect(error.message).to.equal(
+          "Error processing Instruction 0: custom program error: 0x17a6"
+        );
+      } else {
+        throw error;
+      }
+    }
+  });
+
+  it("[Season Game] First player: Claims first place's prize", async () => {
+    const [season] = buildSeasonPda(program, SEASON.seasonId);
+    const seasonAccountBefore = await program.account.season.fetch(season);
+    await setClockTimestamp(
+      BigInt(seasonAccountBefore.endAt.toString()) +
+        BigInt(SEASON.rankingPeriod)
+    );
+
+    const { data } = await context.banksClient.getAccount(season);
+    const rent = (await context.banksClient.getRent()).minimumBalance(
+      BigInt(data.length)
+    );
+    const vaultBalance = (await context.banksClient.getBalance(season)) - rent;
+    const balanceBefore = await context.banksClient.getBalance(
+      firstPlayer.publicKey
+    );
+
+    const ix = await program.methods
+      .claimSeasonPrize()
+      .accountsStrict({
+        eventAuthority,
+        program: program.programId,
+        season,
+        player: firstPlayer.publicKey,
+      })
+      .instruction();
+
+    // Fees are paid by the admin so the balance change is the prize alone
+    console.log(
+      "txId:",
+      await sendSignedVersionedTx(
+        provider,
+        authority.publicKey,
+        [authority, firstPlayer],
+        ...[ix]
+      )
+    );
+
+    const seasonAccount = await program.account.season.fetch(season);
+    expect(seasonAccount.prizePool.toString()).to.equal(
+      vaultBalance.toString()
+    );
+    const prize =
+      (vaultBalance * BigInt(SEASON.prizeSplitBps[0])) / BigInt(10_000);
+    const balanceAfter = await context.banksClient.getBalance(
+      firstPlayer.publicKey
+    );
+    expect((balanceAfter - balanceBefore).toString()).to.equal(
+      prize.toString()
+    );
+  });
+
+  it("[Season Game] Admin: Can't sweep prizes before claims are over", async () => {
+    const [season] = buildSeasonPda(program, SEASON.seasonId);
+    const ix = await program.methods
+      .sweepSeasonPrizes()
+      .accountsStrict({
+        eventAuthority,
+        program: program.programId,
+        season,
+        settings: settingsPda,
+        treasury: authority.publicKey,
+        signer: authority.publicKey,
+      })
+      .instruction();
+
+    try {
+      await sendSignedVersionedTx(
+        provider,
+        authority.publicKey,
+        [authority],
+        ...[ix]
+      );
+      expect.fail("prizes were swept while they could still be claimed");
+    } catch (error) {
+      if (error instanceof anchor.web3.SendTransactionError) {
+        console.log(error.message);
+        expect(error.message).to.equal(
+          "Error processing Instruction 0: custom program error: 0x17b5"
+        );
+      } else {
+        throw error;
+      }
+    }
+  });
+
+  it("[Season Game] Admin: Sweeps the prizes left once claims are over", async () => {
+    const [season] = buildSeasonPda(program, SEASON.seasonId);
+    const seasonAccount = await program.account.season.fetch(season);
+    await setClockTimestamp(
+      BigInt(seasonAccount.endAt.toString()) +
+        BigInt(SEASON.rankingPeriod) +
+        BigInt(SEASON.claimPeriod)
+    );
+
+    const { data } = await context.banksClient.getAccount(season);
+    const rent = (await context.banksClient.getRent()).minimumBalance(
+      BigInt(data.length)
+    );
+    // Everything but first place's prize, the other places never filled
+    const prizePool = BigInt(seasonAccount.prizePool.toString());
+    const unclaimed = (await context.banksClient.getBalance(season)) - rent;
+    expect(unclaimed.toString()).to.equal(
+      (
+        prizePool -
+        (prizePool * BigInt(SEASON.prizeSplitBps[0])) / BigInt(10_000)
+      ).toString()
+    );
+
+    const ix = await program.methods
+      .sweepSeasonPrizes()
+      .accountsStrict({
+        eventAuthority,
+        program: program.programId,
+        season,
+        settings: settingsPda,
+        treasury: authority.publicKey,
+        signer: authority.publicKey,
+      })
+      .instruction();
+
+    console.log(
+      "txId:",
+      await sendSignedVersionedTx(
+        provider,
+        authority.publicKey,
+        [authority],
+        ...[ix]
+      )
+    );
+
+    expect((await context.banksClient.getBalance(season)).toString()).to.equal(
+      rent.toString()
+    );
+  });
+
+  it("[Referral] Admin: Shares a quarter of fees and rake with referrers", async () => {
+    const ix = await program.methods
+      .setReferralShare(REFERRAL_SHARE_BPS)
+      .accountsStrict({
+        eventAuthority,
+        program: program.programId,
+        settings: settingsPda,
+        signer: authority.publicKey,
+      })
+      .instruction();
+
+    console.log(
+      "txId:",
+      await sendSignedVersionedTx(
+        provider,
+        authority.publicKey,
+        [authority],
+        ...[ix]
+      )
+    );
+
+    const settingsAccount = await program.account.settings.fetch(settingsPda);
+    expect(settingsAccount.referralShareBps).to.equal(REFERRAL_SHARE_BPS);
+  });
+
+  it("[Referral] Second player: Can't refer themselves", async () => {
+    const ix = await program.methods
+      .registerReferrer()
+      .accountsStrict({
+        eventAuthority,
+        program: program.programId,
+        referral: buildReferralPda(program, secondPlayer.publicKey)[0],
+        referrerRewards: buildReferrerRewardsPda(
+          program,
+          secondPlayer.publicKey
+        )[0],
+        referrer: secondPlayer.publicKey,
+        player: secondPlayer.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .instruction();
+
+    try {
+      await sendSignedVersionedTx(
+        provider,
+        secondPlayer.publicKey,
+        [secondPlayer],
+        ...[ix]
+      );
+      expect.fail("self-referral was registered");
+    } catch (error) {
+      if (error instanceof anchor.web3.SendTransactionError) {
+        console.log(error.message);
+        expect(error.message).to.equal(
+          "Error processing Instruction 0: custom program error: 0x17a9"
+        );
+      } else {
+        throw error;
+      }
+    }
+  });
+
+  it("[Referral] Second player: Registers the first player as referrer", async () => {
+    const [referral] = buildReferralPda(program, secondPlayer.publicKey);
+    const [referrerRewards] = buildReferrerRewardsPda(
+      program,
+      firstPlayer.publicKey
+    );
+
+    const ix = await program.methods
+      .registerReferrer()
+      .accountsStrict({
+        eventAuthority,
+        program: program.programId,
+        referral,
+        referrerRewards,
+        referrer: firstPlayer.publicKey,
+        player: secondPlayer.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .instruction();
+
+    console.log(
+      "txId:",
+      await sendSignedVersionedTx(
+        provider,
+        secondPlayer.publicKey,
+        [secondPlayer],
+        ...[ix]
+      )
+    );
+
+    const referralAccount = await program.account.referral.fetch(referral);
+    expect(referralAccount.referrer.toString()).to.equal(
+      firstPlayer.publicKey.toString()
+    );
+    const rewardsAccount = await program.account.referrerRewards.fetch(
+      referrerRewards
+    );
+    expect(rewardsAccount.referredPlayers).to.equal(1);
+    expect(rewardsAccount.earnedLamports.toString()).to.equal("0");
+  });
+
+  it("[Referral Game] First player: Initializes a game against the player they referred", async () => {
+    const [game] = buildGamePda(
+      program,
+      firstPlayer.publicKey,
+      REFERRAL_GAME.gameId
+    );
+    const [escrow] = buildEscrowPda(program, game, firstPlayer.publicKey);
+
+    const hash = await getCommitment(
+      game,
+      firstPlayer.publicKey,
+      REFERRAL_GAME.firstPlayerChoice,
+      REFERRAL_GAME.firstPlayerSalt
+    );
+
+    const ix = await program.methods
+      .initializeGame(
+        REFERRAL_GAME.gameId,
+        REFERRAL_GAME.amountToMatch,
+        [...hash],
+        REFERRAL_GAME.bestOf,
+        REFERRAL_GAME.variant as any,
+        null,
+        null,
+        null,
+        null,
+        null
+      )
+      .accountsStrict({
+        season: null,
+        seasonEntry: null,
+        referral: buildReferralPda(program, firstPlayer.publicKey)[0],
+        referrerRewards: null,
+        referrerVault: null,
+        eventAuthority,
+        program: program.programId,
+        game,
+        mint: USDC_MINT,
+        player: firstPlayer.publicKey,
+        playerProfile: buildPlayerProfilePda(program, firstPlayer.publicKey)[0],
+        playerRating: buildPlayerRatingPda(program, firstPlayer.publicKey)[0],
+        playerTokenAccount: firstPlayerAta,
+        settings: settingsPda,
+        mintConfig: buildMintConfigPda(program, USDC_MINT)[0],
+        lobby: buildLobbyPda(
+          program,
+          USDC_MINT,
+          REFERRAL_GAME.amountToMatch
+        )[0],
+        playerEscrowTokenAccount: escrow,
+        treasury: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .instruction();
+
+    console.log(
+      "txId:",
+      await sendSignedVersionedTx(
+        provider,
+        firstPlayer.publicKey,
+        [firstPlayer],
+        ...[ix]
+      )
+    );
+
+    referralGamePda = game;
+    referralGameFirstPlayerEscrowAta = escrow;
+    const [referrerRewards] = buildReferrerRewardsPda(
+      program,
+      firstPlayer.publicKey
+    );
+    referrerVault = getAssociatedTokenAddressSync(
+      USDC_MINT,
+      referrerRewards,
+      true,
+      TOKEN_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID
+    );
+
+    // The first player has no referrer
+    const gameAccount = await program.account.game.fetch(game);
+    expect(gameAccount.firstPlayerReferral).to.equal(null);
+  });
+
+  const buildReferralGameJoinIx = async (
+    referrerVault: anchor.web3.PublicKey | null
+  ) => {
+    const [escrow] = buildEscrowPda(
+      program,
+      referralGamePda,
+      secondPlayer.publicKey
+    );
+    const hash = await getCommitment(
+      referralGamePda,
+      secondPlayer.publicKey,
+      REFERRAL_GAME.secondPlayerChoice,
+      REFERRAL_GAME.secondPlayerSalt
+    );
+
+    return program.methods
+      .joinGame([...hash], [])
+      .accountsStrict({
+        season: null,
+        seasonEntry: null,
+        referral: buildReferralPda(program, secondPlayer.publicKey)[0],
+        referrerRewards: buildReferrerRewardsPda(
+          program,
+          firstPlayer.publicKey
+        )[0],
+        referrerVault,
+        eventAuthority,
+        program: program.programId,
+        game: referralGamePda,
+        lobby: (await program.account.game.fetch(referralGamePda)).lobby,
+        mint: USDC_MINT,
+        player: secondPlayer.publicKey,
+        playerProfile: buildPlayerProfilePda(
+          program,
+          secondPlayer.publicKey
+        )[0],
+        playerRating: buildPlayerRatingPda(program, secondPlayer.publicKey)[0],
+        playerEscrowTokenAccount: escrow,
+        playerTokenAccount: secondPlayerAta,
+        settings: settingsPda,
+        mintConfig: buildMintConfigPda(program, USDC_MINT)[0],
+        treasury: authority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .instruction();
+  };
+
+  it("[Referral Game] Second player: Can't join without the referrer's vault", async () => {
+    try {
+      await sendSignedVersionedTx(
+        provider,
+        secondPlayer.publicKey,
+        [secondPlayer],
+        ...[await buildReferralGameJoinIx(null)]
+      );
+      expect.fail("joined leaving the referrer's share of the rake out");
+    } catch (error) {
+      if (error instanceof anchor.web3.SendTransactionError) {
+        console.log(error.message);
+        expect(error.message).to.equal(
+          "Error processing Instruction 0: custom program error: 0x17aa"
+        );
+      } else {
+        throw error;
+      }
+    }
+  });
+
+  it("[Referral Game] Second player: Joins, sharing their fee with the referrer", async () => {
+    const [referrerRewards] = buildReferrerRewardsPda(
+      program,
+      firstPlayer.publicKey
+    );
+    const rewardsBefore = await program.account.referrerRewards.fetch(
+      referrerRewards
+    );
+
+    // Anyone can open the rewards' token account
+    const createVaultIx = createAssociatedTokenAccountIdempotentInstruction(
+      secondPlayer.publicKey,
+      referrerVault,
+      referrerRewards,
+      USDC_MINT,
+      TOKEN_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID
+    );
+
+    console.log(
+      "txId:",
+      await sendSignedVersionedTx(
+        provider,
+        secondPlayer.publicKey,
+        [secondPlayer],
+        ...[createVaultIx, await buildReferralGameJoinIx(referrerVault)]
+      )
+    );
+
+    referralGameSecondPlayerEscrowAta = buildEscrowPda(
+      program,
+      referralGamePda,
+      secondPlayer.publicKey
+    )[0];
+
+    const gameAccount = await program.account.game.fetch(referralGamePda);
+    expect(gameAccount.secondPlayerReferral.referrer.toBase58()).to.equal(
+      firstPlayer.publicKey.toBase58()
+    );
+    expect(gameAccount.secondPlayerReferral.vault.toBase58()).to.equal(
+      referrerVault.toBase58()
+    );
+
+    const rewards = await program.account.referrerRewards.fetch(
+      referrerRewards
+    );
+    expect(
+      rewards.earnedLamports.sub(rewardsBefore.earnedLamports).toString()
+    ).to.equal(FEE_LAMPORTS.muln(REFERRAL_SHARE_BPS).divn(10_000).toString());
+  });
+
+  it("[Referral Game] Both players: Reveal for the referral game", async () => {
+    for (const [player, choice, salt] of [
+      [
+        firstPlayer,
+        REFERRAL_GAME.firstPlayerChoice,
+        REFERRAL_GAME.firstPlayerSalt,
+      ],
+      [
+        secondPlayer,
+        REFERRAL_GAME.secondPlayerChoice,
+        REFERRAL_GAME.secondPlayerSalt,
+      ],
+    ] as const) {
+      const ix = await program.methods
+        .revealChoice({ [choiceToString(choice)]: {} } as any, [...salt])
+        .accountsStrict({
+          eventAuthority,
+          program: program.programId,
+          game: referralGamePda,
+          player: player.publicKey,
+        })
+        .instruction();
+
+      console.log(
+        "txId:",
+        await sendSignedVersionedTx(
+          provider,
+          player.publicKey,
+          [player],
+          ...[ix]
+        )
+      );
+    }
+  });
+
+  const buildReferralGameSettleIx = (
+    secondPlayerReferrerVault: anchor.web3.PublicKey | null
+  ) =>
+    program.methods
+      .settleGame()
+      .accountsStrict({
+        season: null,
+        firstPlayerSeasonEntry: null,
+        secondPlayerSeasonEntry: null,
+        firstPlayerReferrerVault: null,
+        secondPlayerReferrerVault,
+        sidePool: null,
+        eventAuthority,
+        program: program.programId,
+        game: referralGamePda,
+        mint: USDC_MINT,
+        firstPlayer: firstPlayer.publicKey,
+        firstPlayerEscrowTokenAccount: referralGameFirstPlayerEscrowAta,
+        firstPlayerTokenAccount: firstPlayerAta,
+        secondPlayer: secondPlayer.publicKey,
+        firstPlayerProfile: buildPlayerProfilePda(
+          program,
+          firstPlayer.publicKey
+        )[0],
+        secondPlayerProfile: buildPlayerProfilePda(
+          program,
+          secondPlayer.publicKey
+        )[0],
+        firstPlayerRating: buildPlayerRatingPda(
+          program,
+          firstPlayer.publicKey
+        )[0],
+        secondPlayerRating: buildPlayerRatingPda(
+          program,
+          secondPlayer.publicKey
+        )[0],
+        secondPlayerEscrowTokenAccount: referralGameSecondPlayerEscrowAta,
+        secondPlayerTokenAccount: secondPlayerAta,
+        settings: settingsPda,
+        treasuryTokenAccount: treasuryAta,
+        signer: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .instruction();
+
+  it("[Referral Game] Permissionless: Can't settle without the snapshotted referrer vault", async () => {
+    try {
+      await sendSignedVersionedTx(
+        provider,
+        authority.publicKey,
+        [authority],
+        ...[await buildReferralGameSettleIx(null)]
+      );
+      expect.fail("settled leaving the referrer's share of the rake out");
+    } catch (error) {
+      if (error instanceof anchor.web3.SendTransactionError) {
+        console.log(error.message);
+        expect(error.message).to.equal(
+          "Error processing Instruction 0: custom program error: 0x17aa"
+        );
+      } else {
+        throw error;
+      }
+    }
+  });
+
+  it("[Referral Game] Permissionless: Settles, paying the referrer's share of the rake to its vault", async () => {
+    console.log(
+      "txId:",
+      await sendSignedVersionedTx(
+        provider,
+        authority.publicKey,
+        [authority],
+        ...[await buildReferralGameSettleIx(referrerVault)]
+      )
+    );
+
+    const gameAccount = await program.account.game.fetch(referralGamePda);
+    expect(Object.keys(gameAccount.state)[0]).to.equal("firstPlayerWon");
+
+    // Only the referred player's escrow is shared
+    const rake = REFERRAL_GAME.amountToMatch.muln(RAKE_BPS).divn(10_000);
+    expect(
+      (await getTokenAmount(context.banksClient, referrerVault)).toString()
+    ).to.equal(rake.muln(REFERRAL_SHARE_BPS).divn(10_000).toString());
+  });
+
+  it("[Referral Native Game] Both players: Play a native game, sharing the referred player's fee", async () => {
+    const [game] = buildGamePda(
+      program,
+      firstPlayer.publicKey,
+      REFERRAL_NATIVE_GAME.gameId
+    );
+    referralNativeGamePda = game;
+    const [referrerRewards] = buildReferrerRewardsPda(
+      program,
+      firstPlayer.publicKey
+    );
+    const rewardsBalanceBefore = await context.banksClient.getBalance(
+      referrerRewards
+    );
+
+    const initIx = await program.methods
+      .initializeNativeGame(
+        REFERRAL_NATIVE_GAME.gameId,
+        REFERRAL_NATIVE_GAME.amountToMatch,
+        [
+          ...(await getCommitment(
+            game,
+            firstPlayer.publicKey,
+            REFERRAL_NATIVE_GAME.firstPlayerChoice,
+            REFERRAL_NATIVE_GAME.firstPlayerSalt
+          )),
+        ],
+        REFERRAL_NATIVE_GAME.bestOf,
+        REFERRAL_NATIVE_GAME.variant as any,
+        null,
+        null,
+        null,
+        null,
+        null
+      )
+      .accountsStrict({
+        season: null,
+        seasonEntry: null,
+        referral: buildReferralPda(program, firstPlayer.publicKey)[0],
+        referrerRewards: null,
+        eventAuthority,
+        program: program.programId,
+        game,
+        player: firstPlayer.publicKey,
+        playerProfile: buildPlayerProfilePda(program, firstPlayer.publicKey)[0],
+        playerRating: buildPlayerRatingPda(program, firstPlayer.publicKey)[0],
+        settings: settingsPda,
+        mintConfig: buildMintConfigPda(
+          program,
+          anchor.web3.PublicKey.default
+        )[0],
+        lobby: buildLobbyPda(
+          program,
+          anchor.web3.PublicKey.default,
+          REFERRAL_NATIVE_GAME.amountToMatch
+        )[0],
+        treasury: authority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .instruction();
+
+    console.log(
+      "txId:",
+      await sendSignedVersionedTx(
+        provider,
+        firstPlayer.publicKey,
+        [firstPlayer],
+        ...[initIx]
+      )
+    );
+
+    const joinIx = await program.methods
+      .joinNativeGame(
+        [
+          ...(await getCommitment(
+            game,
+            secondPlayer.publicKey,
+            REFERRAL_NATIVE_GAME.secondPlayerChoice,
+            REFERRAL_NATIVE_GAME.secondPlayerSalt
+          )),
+        ],
+        []
+      )
+      .accountsStrict({
+        season: null,
+        seasonEntry: null,
+        referral: buildReferralPda(program, secondPlayer.publicKey)[0],
+        referrerRewards,
+        eventAuthority,
+        program: program.programId,
+        game,
+        lobby: (await program.account.game.fetch(game)).lobby,
+        player: secondPlayer.publicKey,
+        playerProfile: buildPlayerProfilePda(
+          program,
+          secondPlayer.publicKey
+        )[0],
+        playerRating: buildPlayerRatingPda(program, secondPlayer.publicKey)[0],
+        settings: settingsPda,
+        mintConfig: buildMintConfigPda(
+          program,
+          anchor.web3.PublicKey.default
+        )[0],
+        treasury: authority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .instruction();
+
+    console.log(
+      "txId:",
+      await sendSignedVersionedTx(
+        provider,
+        secondPlayer.publicKey,
+        [secondPlayer],
+        ...[joinIx]
+      )
+    );
+
+    for (const [player, choice, salt] of [
+      [
+        firstPlayer,
+        REFERRAL_NATIVE_GAME.firstPlayerChoice,
+        REFERRAL_NATIVE_GAME.firstPlayerSalt,
+      ],
+      [
+        secondPlayer,
+        REFERRAL_NATIVE_GAME.secondPlayerChoice,
+        REFERRAL_NATIVE_GAME.secondPlayerSalt,
+      ],
+    ] as const) {
+      const ix = await program.methods
+        .revealChoice({ [choiceToString(choice)]: {} } as any, [...salt])
+        .accountsStrict({
+          eventAuthority,
+          program: program.programId,
+          game,
+          player: player.publicKey,
+        })
+        .instruction();
+
+      console.log(
+        "txId:",
+        await sendSignedVersionedTx(
+          provider,
+          player.publicKey,
+          [player],
+          ...[ix]
+        )
+      );
+    }
+
+    const rewardsBalance = await context.banksClient.getBalance(
+      referrerRewards
+    );
+    expect((rewardsBalance - rewardsBalanceBefore).toString()).to.equal(
+      FEE_LAMPORTS.muln(REFERRAL_SHARE_BPS).divn(10_000).toString()
+    );
+  });
+
+  it("[Referral Native Game] Permissionless: Settles, paying the referrer's share of the rake to its rewards", async () => {
+    const [referrerRewards] = buildReferrerRewardsPda(
+      program,
+      firstPlayer.publicKey
+    );
+    const rewardsBefore = await program.account.referrerRewards.fetch(
+      referrerRewards
+    );
+    const rewardsBalanceBefore = await context.banksClient.getBalance(
+      referrerRewards
+    );
+
+    const ix = await program.methods
+      .settleNativeGame()
+      .accountsStrict({
+        season: null,
+        firstPlayerSeasonEntry: null,
+        secondPlayerSeasonEntry: null,
+        firstPlayerReferrerRewards: null,
+        secondPlayerReferrerRewards: referrerRewards,
+        sidePool: null,
+        eventAuthority,
+        program: program.programId,
+        game: referralNativeGamePda,
+        settings: settingsPda,
+        firstPlayer: firstPlayer.publicKey,
+        secondPlayer: secondPlayer.publicKey,
+        firstPlayerProfile: buildPlayerProfilePda(
+          program,
+          firstPlayer.publicKey
+        )[0],
+        secondPlayerProfile: buildPlayerProfilePda(
+          program,
+          secondPlayer.publicKey
+        )[0],
+        firstPlayerRating: buildPlayerRatingPda(
+          program,
+          firstPlayer.publicKey
+        )[0],
+        secondPlayerRating: buildPlayerRatingPda(
+          program,
+          secondPlayer.publicKey
+        )[0],
+        treasury: authority.publicKey,
+        signer: authority.publicKey,
+      })
+      .instruction();
+
+    console.log(
+      "txId:",
+      await sendSignedVersionedTx(
+        provider,
+        authority.publicKey,
+        [authority],
+        ...[ix]
+      )
+    );
+
+    const gameAccount = await program.account.game.fetch(
+      referralNativeGamePda
+    );
     expect(Object.keys(gameAccount.state)[0]).to.equal("firstPlayerWon");
+
+    const rake = REFERRAL_NATIVE_GAME.amountToMatch
+      .muln(RAKE_BPS)
+      .divn(10_000);
+    const share = rake.muln(REFERRAL_SHARE_BPS).divn(10_000);
+    const rewardsBalance = await context.banksClient.getBalance(
+      referrerRewards
+    );
+    expect((rewardsBalance - rewardsBalanceBefore).toString()).to.equal(
+      share.toString()
+    );
+    const rewards = await program.account.referrerRewards.fetch(
+      referrerRewards
+    );
+    expect(
+      rewards.earnedLamports.sub(rewardsBefore.earnedLamports).toString()
+    ).to.equal(share.toString());
+  });
+
+  it("[Referral] First player: Claims the SOL rewards", async () => {
+    const [referrerRewards] = buildReferrerRewardsPda(
+      program,
+      firstPlayer.publicKey
+    );
+    const { data } = await context.banksClient.getAccount(referrerRewards);
+    const rent = (await context.banksClient.getRent()).minimumBalance(
+      BigInt(data.length)
+    );
+    const rewardsBefore = await program.account.referrerRewards.fetch(
+      referrerRewards
+    );
+    const claimable =
+      (await context.banksClient.getBalance(referrerRewards)) - rent;
+    const balanceBefore = await context.banksClient.getBalance(
+      firstPlayer.publicKey
+    );
+
+    const ix = await program.methods
+      .claimReferralRewards()
+      .accountsStrict({
+        eventAuthority,
+        program: program.programId,
+        referrerRewards,
+        referrer: firstPlayer.publicKey,
+      })
+      .instruction();
+
+    // Fees are paid by the admin so the balance change is the rewards alone
+    console.log(
+      "txId:",
+      await sendSignedVersionedTx(
+        provider,
+        authority.publicKey,
+        [authority, firstPlayer],
+        ...[ix]
+      )
+    );
+
+    const balanceAfter = await context.banksClient.getBalance(
+      firstPlayer.publicKey
+    );
+    expect((balanceAfter - balanceBefore).toString()).to.equal(
+      claimable.toString()
+    );
+    expect(
+      (await context.banksClient.getBalance(referrerRewards)).toString()
+    ).to.equal(rent.toString());
+    const rewards = await program.account.referrerRewards.fetch(
+      referrerRewards
+    );
+    expect(
+      rewards.claimedLamports.sub(rewardsBefore.claimedLamports).toString()
+    ).to.equal(claimable.toString());
+  });
+
+  it("[Referral] First player: Claims the token rewards", async () => {
+    const vaultAmount = await getTokenAmount(
+      context.banksClient,
+      referrerVault
+    );
+    const balanceBefore = await getTokenAmount(
+      context.banksClient,
+      firstPlayerAta
+    );
+
+    const ix = await program.methods
+      .claimReferralTokenRewards()
+      .accountsStrict({
+        eventAuthority,
+        program: program.programId,
+        referrerRewards: buildReferrerRewardsPda(
+          program,
+          firstPlayer.publicKey
+        )[0],
+        referrerVault,
+        referrerTokenAccount: firstPlayerAta,
+        mint: USDC_MINT,
+        referrer: firstPlayer.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .instruction();
+
+    console.log(
+      "txId:",
+      await sendSignedVersionedTx(
+        provider,
+        firstPlayer.publicKey,
+        [firstPlayer],
+        ...[ix]
+      )
+    );
+
+    expect(
+      (await getTokenAmount(context.banksClient, firstPlayerAta)) -
+        balanceBefore
+    ).to.equal(vaultAmount);
+    expect(await getTokenAmount(context.banksClient, referrerVault)).to.equal(
+      BigInt(0)
+    );
+  });
+
+  it("[First Game - Happy Path] Permissionless: Closes first game after the retention period", async () => {
+    const ix = await program.methods
+      .closeGame()
+      .accountsStrict({
+        eventAuthority,
+        program: program.programId,
+        game: firstGamePda,
+        firstPlayer: firstPlayer.publicKey,
+        settings: settingsPda,
+        signer: authority.publicKey,
+      })
+      .instruction();
+
+    const txId = await sendSignedVersionedTx(
+      provider,
+      authority.publicKey,
+      [authority],
+      ...[ix]
+    );
+
+    console.log("txId:", txId);
+
+    const gameAccount = await context.banksClient.getAccount(firstGamePda);
+    expect(gameAccount).to.equal(null);
   });
 });
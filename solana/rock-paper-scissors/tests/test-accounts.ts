@@ -32,12 +32,24 @@ export const buildLocalTestAta = (
   return ataBuffer;
 };
 
+// Upgradeable loader `ProgramData` header, the program's bytes aren't needed
+export const buildLocalProgramData = (upgradeAuthority: web3.PublicKey) => {
+  const programDataBuffer = Buffer.alloc(45);
+  programDataBuffer.writeUInt32LE(3, 0);
+  programDataBuffer.writeBigUInt64LE(BigInt(0), 4);
+  programDataBuffer.writeUInt8(1, 12);
+  upgradeAuthority.toBuffer().copy(programDataBuffer, 13);
+  return programDataBuffer;
+};
+
 export const buildAccounts = async (
   authorityPublicKey: web3.PublicKey,
   firstPlayerPublicKey: web3.PublicKey,
   firstPlayerAta: web3.PublicKey,
   secondPlayerPublicKey: web3.PublicKey,
   secondPlayerAta: web3.PublicKey,
+  treasuryAta: web3.PublicKey,
+  programData: web3.PublicKey,
   mainnetConnection: web3.Connection,
   mint: web3.PublicKey = USDC_MINT
 ): Promise<AddedAccount[]> => [
@@ -46,6 +58,17 @@ export const buildAccounts = async (
     address: mint,
     info: await mainnetConnection.getAccountInfo(mint),
   },
+  // Bankrun doesn't deploy programs as upgradeable, so make authority the
+  // upgrade authority allowed to initialize settings
+  {
+    address: programData,
+    info: {
+      data: buildLocalProgramData(authorityPublicKey),
+      lamports: 1_000000000,
+      owner: new web3.PublicKey("BPFLoaderUpgradeab1e11111111111111111111111"),
+      executable: false,
+    },
+  },
   // Allocate 1 sol to authority for gas and fees
   {
     address: authorityPublicKey,
@@ -96,4 +119,14 @@ export const buildAccounts = async (
       executable: false,
     },
   },
+  // Empty treasury account to receive the rake
+  {
+    address: treasuryAta,
+    info: {
+      data: buildLocalTestAta(mint, authorityPublicKey, 0),
+      lamports: 1_000000000,
+      owner: TOKEN_PROGRAM_ID,
+      executable: false,
+    },
+  },
 ];